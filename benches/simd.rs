@@ -0,0 +1,165 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use glmath::{
+    matrix::Matrix4x4,
+    simd,
+    vector::{generate_rand_vector4, Vector4},
+};
+
+macro_rules! Bench_with_input {
+    {$Criterion:ident,$function_name:expr, $parameter:expr,$input:expr,$var_name:ident$($doer:tt)+} => {
+        $Criterion.bench_with_input(
+            BenchmarkId::new($function_name, $parameter),
+            $input,
+            |b, $var_name| {
+                b.iter(|| {
+                    $($doer)+
+                })
+            },
+        );
+    };
+}
+
+fn generate_input_data_matrix4x4(len: usize) -> Vec<Matrix4x4<f32>> {
+    let input: Vec<Matrix4x4<f32>> = (0..len)
+        .into_iter()
+        .map(|_| {
+            Matrix4x4::from([
+                generate_rand_vector4(),
+                generate_rand_vector4(),
+                generate_rand_vector4(),
+                generate_rand_vector4(),
+            ])
+        })
+        .collect();
+    input
+}
+
+fn generate_input_data_vector4(len: usize) -> Vec<Vector4<f32>> {
+    let input: Vec<Vector4<f32>> = (0..len)
+        .into_iter()
+        .map(|_| generate_rand_vector4())
+        .collect();
+    input
+}
+
+// the simd kernels may use fma, so only check within tolerance
+fn check_against_scalar(matrices: &[Matrix4x4<f32>], vectors: &[Vector4<f32>]) {
+    for cx in 0..matrices.len() - 1 {
+        let (ca, cb) = (matrices[cx], matrices[cx + 1]);
+        assert!(simd::mul_matrix4x4(ca, cb)
+            .into_iter()
+            .zip(simd::scalar::mul_matrix4x4(ca, cb))
+            .all(|(ra, rb)| ra.eq_fast(rb, 1e-5)));
+        assert!(simd::mul_vector4x4(ca, vectors[cx])
+            .eq_fast(simd::scalar::mul_vector4x4(ca, vectors[cx]), 1e-5));
+        assert!(
+            (simd::mul_inner4(vectors[cx], vectors[cx + 1])
+                - simd::scalar::mul_inner4(vectors[cx], vectors[cx + 1]))
+            .abs()
+                < 1e-5
+        );
+    }
+}
+
+pub fn simd_matrix4x4(c: &mut Criterion) {
+    let input_matrices = generate_input_data_matrix4x4(101);
+    let input_vectors = generate_input_data_vector4(101);
+    let input_matrix_vector_pair = (input_matrices.clone(), input_vectors.clone());
+
+    check_against_scalar(&input_matrices, &input_vectors);
+
+    Bench_with_input! {
+        c,"mul_matrix simd","random float [0,1)",&input_matrices,myinput
+        {
+            for cx in 0..100 {
+                black_box(simd::mul_matrix4x4(black_box(myinput[cx]), black_box(myinput[cx + 1])));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"mul_matrix scalar","random float [0,1)",&input_matrices,myinput
+        {
+            for cx in 0..100 {
+                black_box(simd::scalar::mul_matrix4x4(black_box(myinput[cx]), black_box(myinput[cx + 1])));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"mul_vector simd","random float [0,1)",&input_matrix_vector_pair,myinput
+        {
+            for cx in 0..100 {
+                black_box(simd::mul_vector4x4(black_box(myinput.0[cx]), black_box(myinput.1[cx])));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"mul_vector scalar","random float [0,1)",&input_matrix_vector_pair,myinput
+        {
+            for cx in 0..100 {
+                black_box(simd::scalar::mul_vector4x4(black_box(myinput.0[cx]), black_box(myinput.1[cx])));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"mul_inner simd","random float [0,1)",&input_vectors,myinput
+        {
+            for cx in 0..100 {
+                black_box(simd::mul_inner4(black_box(myinput[cx]), black_box(myinput[cx + 1])));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"mul_inner scalar","random float [0,1)",&input_vectors,myinput
+        {
+            for cx in 0..100 {
+                black_box(simd::scalar::mul_inner4(black_box(myinput[cx]), black_box(myinput[cx + 1])));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "quaternions")]
+pub fn simd_quaternion(c: &mut Criterion) {
+    use glmath::quaternions::Quaternion;
+
+    let input_quaternions: Vec<Quaternion<f32>> = generate_input_data_vector4(101)
+        .into_iter()
+        .map(Quaternion::from)
+        .collect();
+
+    for cx in 0..100 {
+        let (ca, cb) = (input_quaternions[cx], input_quaternions[cx + 1]);
+        assert!(simd::mul_quaternion(ca, cb).eq_fast(simd::scalar::mul_quaternion(ca, cb), 1e-5));
+    }
+
+    Bench_with_input! {
+        c,"mul_quaternion simd","random float [0,1)",&input_quaternions,myinput
+        {
+            for cx in 0..100 {
+                black_box(simd::mul_quaternion(black_box(myinput[cx]), black_box(myinput[cx + 1])));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"mul_quaternion scalar","random float [0,1)",&input_quaternions,myinput
+        {
+            for cx in 0..100 {
+                black_box(simd::scalar::mul_quaternion(black_box(myinput[cx]), black_box(myinput[cx + 1])));
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "quaternions"))]
+pub fn simd_quaternion(_c: &mut Criterion) {}
+
+criterion_group!(benches, simd_matrix4x4, simd_quaternion);
+criterion_main!(benches);
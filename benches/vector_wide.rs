@@ -0,0 +1,204 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use glmath::{
+    matrix::Matrix4x4,
+    vector::{generate_rand_vector3, generate_rand_vector4, Vector3, Vector4},
+    wide::Vector3x8,
+};
+
+macro_rules! Bench_with_input {
+    {$Criterion:ident,$function_name:expr, $parameter:expr,$input:expr,$var_name:ident$($doer:tt)+} => {
+        $Criterion.bench_with_input(
+            BenchmarkId::new($function_name, $parameter),
+            $input,
+            |b, $var_name| {
+                b.iter(|| {
+                    $($doer)+
+                })
+            },
+        );
+    };
+}
+
+fn generate_input_data_vector3(len: usize) -> Vec<Vector3<f32>> {
+    let input: Vec<Vector3<f32>> = (0..len)
+        .into_iter()
+        .map(|_| generate_rand_vector3())
+        .collect();
+    input
+}
+
+// Each benchmark does the same 800 operations,
+// once on Vector3 and once on Vector3x8
+pub fn wide_vector3(c: &mut Criterion) {
+    let input_vector_pair = (
+        generate_input_data_vector3(800),
+        generate_input_data_vector3(800),
+    );
+    let input_wide_pair: (Vec<Vector3x8<f32>>, Vec<Vector3x8<f32>>) = (
+        input_vector_pair
+            .0
+            .chunks(8)
+            .map(Vector3x8::from_slice)
+            .collect(),
+        input_vector_pair
+            .1
+            .chunks(8)
+            .map(Vector3x8::from_slice)
+            .collect(),
+    );
+    let matrix = Matrix4x4::from([
+        generate_rand_vector4(),
+        generate_rand_vector4(),
+        generate_rand_vector4(),
+        Vector4::from([0f32, 0f32, 0f32, 1f32]),
+    ]);
+
+    Bench_with_input! {
+        c,"add","Vector3",&input_vector_pair,myinput
+        {
+            for cx in 0..800 {
+                black_box(black_box(myinput.0[cx]).add(black_box(myinput.1[cx])));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"add","Vector3x8",&input_wide_pair,myinput
+        {
+            for cx in 0..100 {
+                black_box(black_box(myinput.0[cx]).add(black_box(myinput.1[cx])));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"mul_inner","Vector3",&input_vector_pair,myinput
+        {
+            for cx in 0..800 {
+                black_box(black_box(myinput.0[cx]).mul_inner(black_box(myinput.1[cx])));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"mul_inner","Vector3x8",&input_wide_pair,myinput
+        {
+            for cx in 0..100 {
+                black_box(black_box(myinput.0[cx]).mul_inner(black_box(myinput.1[cx])));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"mul_cross","Vector3",&input_vector_pair,myinput
+        {
+            for cx in 0..800 {
+                black_box(black_box(myinput.0[cx]).mul_cross(black_box(myinput.1[cx])));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"mul_cross","Vector3x8",&input_wide_pair,myinput
+        {
+            for cx in 0..100 {
+                black_box(black_box(myinput.0[cx]).mul_cross(black_box(myinput.1[cx])));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"norm","Vector3",&input_vector_pair,myinput
+        {
+            for cx in 0..800 {
+                black_box(black_box(myinput.0[cx]).norm());
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"norm","Vector3x8",&input_wide_pair,myinput
+        {
+            for cx in 0..100 {
+                black_box(black_box(myinput.0[cx]).norm());
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"Matrix4x4::mul_vector","Vector3",&input_vector_pair,myinput
+        {
+            for cx in 0..800 {
+                black_box(matrix.mul_vector(Vector4::from((black_box(myinput.0[cx]), 1f32))));
+            }
+        }
+    }
+
+    Bench_with_input! {
+        c,"Matrix4x4::mul_vector","Vector3x8",&input_wide_pair,myinput
+        {
+            for cx in 0..100 {
+                black_box(matrix.mul_vector3x8(black_box(myinput.0[cx])));
+            }
+        }
+    }
+}
+
+// Transforms a whole buffer of points, like a particle system does, so the
+// time is spent in the math rather than in moving each Vector through black_box
+pub fn wide_transform_buffer(c: &mut Criterion) {
+    let points = generate_input_data_vector3(100_000);
+    let wide_points: Vec<Vector3x8<f32>> = points.chunks(8).map(Vector3x8::from_slice).collect();
+    let matrix = Matrix4x4::from([
+        generate_rand_vector4(),
+        generate_rand_vector4(),
+        generate_rand_vector4(),
+        Vector4::from([0f32, 0f32, 0f32, 1f32]),
+    ]);
+    let mut out = points.clone();
+    let mut wide_out = wide_points.clone();
+
+    c.bench_function("transform 100k points/Vector3", |b| {
+        b.iter(|| {
+            let matrix = black_box(matrix);
+            for (ca, cb) in black_box(&points).iter().zip(out.iter_mut()) {
+                let v = matrix.mul_vector(Vector4::from((*ca, 1f32)));
+                *cb = Vector3::from([v[0], v[1], v[2]]);
+            }
+            black_box(&mut out);
+        })
+    });
+
+    c.bench_function("transform 100k points/Vector3x8", |b| {
+        b.iter(|| {
+            let matrix = black_box(matrix);
+            for (ca, cb) in black_box(&wide_points).iter().zip(wide_out.iter_mut()) {
+                *cb = matrix.mul_vector3x8(*ca);
+            }
+            black_box(&mut wide_out);
+        })
+    });
+
+    c.bench_function("normalize 100k/Vector3", |b| {
+        b.iter(|| {
+            for (ca, cb) in black_box(&points).iter().zip(out.iter_mut()) {
+                *cb = ca.normalize_or_zero();
+            }
+            black_box(&mut out);
+        })
+    });
+
+    c.bench_function("normalize 100k/Vector3x8", |b| {
+        b.iter(|| {
+            for (ca, cb) in black_box(&wide_points).iter().zip(wide_out.iter_mut()) {
+                *cb = ca.normalize_or_zero();
+            }
+            black_box(&mut wide_out);
+        })
+    });
+}
+
+criterion_group!(benches, wide_vector3, wide_transform_buffer);
+criterion_main!(benches);
@@ -0,0 +1,214 @@
+//! Angles in radians ([`Rad`]) and degrees ([`Deg`]).
+//!
+//! The rotation constructors, like [`crate::vector::Vector2::from_polar`] and
+//! `Quaternion::from_axis_angle`, take `impl Into<Rad<T>>`. So they take either unit,
+//! and a bare `T` is still read as radians, as it always was.
+//!
+//! # Examples
+//!
+//! ```
+//! use glmath::angle::{Deg, Rad};
+//! use glmath::vector::Vector2;
+//!
+//! let right_angle: Rad<f32> = Deg(90f32).into();
+//! assert_eq!(right_angle, Rad(std::f32::consts::FRAC_PI_2));
+//!
+//! let up = Vector2::from_polar(Deg(90f32), 1f32);
+//! assert!(up.dist(Vector2::from([0f32, 1f32])) < 1e-6);
+//!
+//! // the short way round, from 350 to 10 degrees
+//! assert_eq!(Deg(350f32).shortest_difference(Deg(10f32)), Deg(20f32));
+//! assert_eq!(Deg(-90f32).normalize(), Deg(270f32));
+//! ```
+
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::Element;
+
+/// An angle in radians
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rad<T: Element>(pub T);
+
+/// An angle in degrees
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deg<T: Element>(pub T);
+
+macro_rules! GENERATE_ANGLE {
+    ($(($angle:ident, $full_turn:expr)),*) => {
+        $(
+            impl<T: Element> $angle<T> {
+                /// Gives the angle of a whole turn
+                #[inline(always)]
+                pub fn full_turn() -> Self {
+                    Self(num::cast($full_turn).unwrap())
+                }
+
+                /// Gives the angle of half a turn
+                #[inline(always)]
+                pub fn half_turn() -> Self {
+                    Self(num::cast($full_turn / 2f64).unwrap())
+                }
+
+                #[inline(always)]
+                pub fn sin(self) -> T {
+                    Rad::from(self).0.sin()
+                }
+
+                #[inline(always)]
+                pub fn cos(self) -> T {
+                    Rad::from(self).0.cos()
+                }
+
+                #[inline(always)]
+                pub fn tan(self) -> T {
+                    Rad::from(self).0.tan()
+                }
+
+                #[inline(always)]
+                pub fn sin_cos(self) -> (T, T) {
+                    Rad::from(self).0.sin_cos()
+                }
+
+                /// Wraps the angle into [0, full turn)
+                #[inline(always)]
+                pub fn normalize(self) -> Self {
+                    let full_turn = Self::full_turn().0;
+                    let mut ret = self.0 % full_turn;
+                    if ret < T::zero() {
+                        ret = ret + full_turn;
+                    }
+                    // a tiny negative angle can round up to a full turn
+                    if ret >= full_turn {
+                        ret = T::zero();
+                    }
+                    Self(ret)
+                }
+
+                /// Wraps the angle into [-half turn, half turn)
+                #[inline(always)]
+                pub fn normalize_signed(self) -> Self {
+                    let half_turn = Self::half_turn();
+                    (self + half_turn).normalize() - half_turn
+                }
+
+                /// Gives the smallest angle to turn by to get from self to other,
+                /// in [-half turn, half turn)
+                #[inline(always)]
+                pub fn shortest_difference(self, other: Self) -> Self {
+                    (other - self).normalize_signed()
+                }
+
+                /// Interpolates from self to other the short way round
+                #[inline(always)]
+                pub fn lerp_shortest(self, other: Self, t: T) -> Self {
+                    self + self.shortest_difference(other) * t
+                }
+            }
+
+            impl<T: Element> Add for $angle<T> {
+                type Output = Self;
+                #[inline(always)]
+                fn add(self, other: Self) -> Self {
+                    Self(self.0 + other.0)
+                }
+            }
+
+            impl<T: Element> Sub for $angle<T> {
+                type Output = Self;
+                #[inline(always)]
+                fn sub(self, other: Self) -> Self {
+                    Self(self.0 - other.0)
+                }
+            }
+
+            impl<T: Element> Neg for $angle<T> {
+                type Output = Self;
+                #[inline(always)]
+                fn neg(self) -> Self {
+                    Self(-self.0)
+                }
+            }
+
+            impl<T: Element> Mul<T> for $angle<T> {
+                type Output = Self;
+                #[inline(always)]
+                fn mul(self, scalar: T) -> Self {
+                    Self(self.0 * scalar)
+                }
+            }
+
+            impl<T: Element> Div<T> for $angle<T> {
+                type Output = Self;
+                #[inline(always)]
+                fn div(self, scalar: T) -> Self {
+                    Self(self.0 / scalar)
+                }
+            }
+
+            /// Gives the ratio of the two angles
+            impl<T: Element> Div for $angle<T> {
+                type Output = T;
+                #[inline(always)]
+                fn div(self, other: Self) -> T {
+                    self.0 / other.0
+                }
+            }
+
+            impl<T: Element> AddAssign for $angle<T> {
+                #[inline(always)]
+                fn add_assign(&mut self, other: Self) {
+                    *self = *self + other;
+                }
+            }
+
+            impl<T: Element> SubAssign for $angle<T> {
+                #[inline(always)]
+                fn sub_assign(&mut self, other: Self) {
+                    *self = *self - other;
+                }
+            }
+
+            impl<T: Element> MulAssign<T> for $angle<T> {
+                #[inline(always)]
+                fn mul_assign(&mut self, scalar: T) {
+                    *self = *self * scalar;
+                }
+            }
+
+            impl<T: Element> DivAssign<T> for $angle<T> {
+                #[inline(always)]
+                fn div_assign(&mut self, scalar: T) {
+                    *self = *self / scalar;
+                }
+            }
+        )*
+    };
+}
+
+GENERATE_ANGLE!((Rad, std::f64::consts::TAU), (Deg, 360f64));
+
+impl<T: Element> From<Deg<T>> for Rad<T> {
+    #[inline(always)]
+    fn from(value: Deg<T>) -> Self {
+        Self(value.0.to_radians())
+    }
+}
+
+impl<T: Element> From<Rad<T>> for Deg<T> {
+    #[inline(always)]
+    fn from(value: Rad<T>) -> Self {
+        Self(value.0.to_degrees())
+    }
+}
+
+/// Bare values are radians
+impl<T: Element> From<T> for Rad<T> {
+    #[inline(always)]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
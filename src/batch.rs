@@ -0,0 +1,238 @@
+//! Batch operations over slices of Vectors.
+//!
+//! These work through the slices 8 Vectors at a time using [`Vector3x8`],
+//! with any remainder done one at a time. With the `rayon` feature,
+//! large slices are also split across threads, and the slice routines then
+//! need the element type to be `Send + Sync`, which every float type is.
+//!
+//! Since they take plain slices, they also work on vertex buffers
+//! cast with `bytemuck::cast_slice` (with the `bytemuck` feature)
+
+use crate::matrix::Matrix4x4;
+#[cfg(feature = "quaternions")]
+use crate::quaternions::Quaternion;
+use crate::vector::Vector3;
+use crate::wide::Vector3x8;
+use crate::Element;
+
+mod bounds {
+    /// `Send + Sync` with the `rayon` feature, so the slices and the work
+    /// on them can be shared across threads, and no bound otherwise
+    #[cfg(feature = "rayon")]
+    pub trait MaybeSendSync: Send + Sync {}
+    #[cfg(feature = "rayon")]
+    impl<T: Send + Sync> MaybeSendSync for T {}
+    #[cfg(not(feature = "rayon"))]
+    pub trait MaybeSendSync {}
+    #[cfg(not(feature = "rayon"))]
+    impl<T> MaybeSendSync for T {}
+}
+use bounds::MaybeSendSync;
+
+/// Slices at least this long are split across threads
+#[cfg(feature = "rayon")]
+const PARALLEL_LEN: usize = 1 << 14;
+
+/// How many Vectors each thread works on at a time
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_LEN: usize = 1 << 12;
+
+#[inline(always)]
+fn map_serial<T: Element>(
+    src: &[Vector3<T>],
+    dst: &mut [Vector3<T>],
+    wide: &impl Fn(Vector3x8<T>) -> Vector3x8<T>,
+    single: &impl Fn(Vector3<T>) -> Vector3<T>,
+) {
+    let mut src_chunks = src.chunks_exact(8);
+    let mut dst_chunks = dst.chunks_exact_mut(8);
+    for (ca, cb) in (&mut src_chunks).zip(&mut dst_chunks) {
+        wide(Vector3x8::from_slice(ca)).write_to_slice(cb);
+    }
+    for (ca, cb) in src_chunks
+        .remainder()
+        .iter()
+        .zip(dst_chunks.into_remainder())
+    {
+        *cb = single(*ca);
+    }
+}
+
+#[inline(always)]
+fn map_serial_in_place<T: Element>(
+    points: &mut [Vector3<T>],
+    wide: &impl Fn(Vector3x8<T>) -> Vector3x8<T>,
+    single: &impl Fn(Vector3<T>) -> Vector3<T>,
+) {
+    let mut chunks = points.chunks_exact_mut(8);
+    for ca in &mut chunks {
+        wide(Vector3x8::from_slice(ca)).write_to_slice(ca);
+    }
+    for ca in chunks.into_remainder() {
+        *ca = single(*ca);
+    }
+}
+
+/// Writes the mapped src into dst.
+/// Panics if the slices have different lengths
+#[inline(always)]
+fn map<T: Element + MaybeSendSync>(
+    src: &[Vector3<T>],
+    dst: &mut [Vector3<T>],
+    wide: impl Fn(Vector3x8<T>) -> Vector3x8<T> + MaybeSendSync,
+    single: impl Fn(Vector3<T>) -> Vector3<T> + MaybeSendSync,
+) {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination slices must have the same length"
+    );
+    #[cfg(feature = "rayon")]
+    if src.len() >= PARALLEL_LEN {
+        use rayon::prelude::*;
+        src.par_chunks(PARALLEL_CHUNK_LEN)
+            .zip(dst.par_chunks_mut(PARALLEL_CHUNK_LEN))
+            .for_each(|(ca, cb)| map_serial(ca, cb, &wide, &single));
+        return;
+    }
+    map_serial(src, dst, &wide, &single)
+}
+
+#[inline(always)]
+fn map_in_place<T: Element + MaybeSendSync>(
+    points: &mut [Vector3<T>],
+    wide: impl Fn(Vector3x8<T>) -> Vector3x8<T> + MaybeSendSync,
+    single: impl Fn(Vector3<T>) -> Vector3<T> + MaybeSendSync,
+) {
+    #[cfg(feature = "rayon")]
+    if points.len() >= PARALLEL_LEN {
+        use rayon::prelude::*;
+        points
+            .par_chunks_mut(PARALLEL_CHUNK_LEN)
+            .for_each(|ca| map_serial_in_place(ca, &wide, &single));
+        return;
+    }
+    map_serial_in_place(points, &wide, &single)
+}
+
+impl<T: Element + MaybeSendSync> Matrix4x4<T> {
+    /// Transforms every point (w = 1) of src, writing them into dst.
+    /// Like an affine transform, the bottom row of the Matrix is not used.
+    /// Panics if the slices have different lengths
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use glmath::matrix::Matrix4x4;
+    /// use glmath::vector::Vector3;
+    ///
+    /// // translate by (1,2,3)
+    /// let m = Matrix4x4::from([1f32, 0f32, 0f32, 1f32,
+    ///                          0f32, 1f32, 0f32, 2f32,
+    ///                          0f32, 0f32, 1f32, 3f32,
+    ///                          0f32, 0f32, 0f32, 1f32]);
+    /// let points = [Vector3::from([0f32; 3]); 10];
+    /// let mut out = [Vector3::from([0f32; 3]); 10];
+    ///
+    /// m.transform_points(&points, &mut out);
+    ///
+    /// assert!(out.iter().all(|ca| *ca == Vector3::from([1f32, 2f32, 3f32])));
+    /// ```
+    #[inline(always)]
+    pub fn transform_points(self, src: &[Vector3<T>], dst: &mut [Vector3<T>]) {
+        map(
+            src,
+            dst,
+            |ca| self.mul_vector3x8(ca),
+            |ca| self.transform_point(ca),
+        )
+    }
+
+    /// Does [`Self::transform_points`] in place
+    #[inline(always)]
+    pub fn transform_points_in_place(self, points: &mut [Vector3<T>]) {
+        map_in_place(
+            points,
+            |ca| self.mul_vector3x8(ca),
+            |ca| self.transform_point(ca),
+        )
+    }
+
+    /// Transforms every direction (w = 0) of src, writing them into dst.
+    /// This leaves out the translation of the Matrix.
+    /// Panics if the slices have different lengths
+    #[inline(always)]
+    pub fn transform_vectors(self, src: &[Vector3<T>], dst: &mut [Vector3<T>]) {
+        let m = self.to_matrix3x3();
+        map(src, dst, |ca| m.mul_vector3x8(ca), |ca| m.mul_vector(ca))
+    }
+
+    /// Does [`Self::transform_vectors`] in place
+    #[inline(always)]
+    pub fn transform_vectors_in_place(self, points: &mut [Vector3<T>]) {
+        let m = self.to_matrix3x3();
+        map_in_place(points, |ca| m.mul_vector3x8(ca), |ca| m.mul_vector(ca))
+    }
+}
+
+impl<T: Element> Matrix4x4<T> {
+    /// Transforms a single point (w = 1), the same way
+    /// as [`Self::transform_points`]
+    #[inline(always)]
+    pub fn transform_point(self, point: Vector3<T>) -> Vector3<T> {
+        self.to_matrix3x3()
+            .mul_vector(point)
+            .add(Vector3([self[0][3], self[1][3], self[2][3]]))
+    }
+
+    /// Transforms a single vector (w = 0), the same way
+    /// as [`Self::transform_vectors`]
+    #[inline(always)]
+    pub fn transform_vector(self, vector: Vector3<T>) -> Vector3<T> {
+        self.to_matrix3x3().mul_vector(vector)
+    }
+}
+
+impl<T: Element + MaybeSendSync> Vector3<T> {
+    /// Does [`Self::normalize_or_zero`] on every Vector of src,
+    /// writing them into dst.
+    /// Panics if the slices have different lengths
+    #[inline(always)]
+    pub fn normalize_all(src: &[Self], dst: &mut [Self]) {
+        map(
+            src,
+            dst,
+            Vector3x8::normalize_or_zero,
+            Self::normalize_or_zero,
+        )
+    }
+
+    /// Does [`Self::normalize_all`] in place
+    #[inline(always)]
+    pub fn normalize_all_in_place(points: &mut [Self]) {
+        map_in_place(
+            points,
+            Vector3x8::normalize_or_zero,
+            Self::normalize_or_zero,
+        )
+    }
+}
+
+#[cfg(feature = "quaternions")]
+impl<T: Element + MaybeSendSync> Quaternion<T> {
+    /// Rotates every Vector of src by this unit Quaternion,
+    /// writing them into dst.
+    /// Panics if the slices have different lengths
+    #[inline(always)]
+    pub fn rotate_all(self, src: &[Vector3<T>], dst: &mut [Vector3<T>]) {
+        let m = self.to_matrix3x3();
+        map(src, dst, |ca| m.mul_vector3x8(ca), |ca| m.mul_vector(ca))
+    }
+
+    /// Does [`Self::rotate_all`] in place
+    #[inline(always)]
+    pub fn rotate_all_in_place(self, points: &mut [Vector3<T>]) {
+        let m = self.to_matrix3x3();
+        map_in_place(points, |ca| m.mul_vector3x8(ca), |ca| m.mul_vector(ca))
+    }
+}
@@ -0,0 +1,327 @@
+//! Conversions between coordinate systems with different up axes and handedness.
+//!
+//! A [`CoordinateSystem`] names the axes that point up and forward, and its
+//! [`Handedness`] gives the axis that points right. These are directions on the
+//! asset itself, so forward is where a character faces and right is its own right.
+//! Converting between two systems keeps up, forward and right the same,
+//! so a model made in one system looks the same in the other.
+//!
+//! A [`Conversion`] is made with a [`ConversionBuilder`]. Its change of basis is
+//! a signed permutation Matrix. When the systems differ in handedness it is a
+//! reflection, and rotations flip their direction: see [`Conversion::convert_quaternion`].
+//!
+//! # Examples
+//!
+//! ```
+//! use glmath::coordinate_system::{ConversionBuilder, CoordinateSystem};
+//! use glmath::vector::Vector3;
+//!
+//! // Blender's (x, y, z) is glTF's (x, z, -y)
+//! let blender_to_gltf = ConversionBuilder::new(CoordinateSystem::BLENDER, CoordinateSystem::GLTF).build();
+//! let v = Vector3::from([1f32, 2f32, 3f32]);
+//! assert_eq!(blender_to_gltf.convert_point(v), Vector3::from([1f32, 3f32, -2f32]));
+//!
+//! // centimeters to meters, and glTF's (x, y, z) is Unity's (-x, y, z)
+//! let gltf_to_unity = ConversionBuilder::new(CoordinateSystem::GLTF, CoordinateSystem::UNITY)
+//!     .scale(0.01f32)
+//!     .build();
+//! assert!(gltf_to_unity.flips_handedness());
+//! assert_eq!(gltf_to_unity.convert_point(Vector3::from([100f32, 200f32, 300f32])), Vector3::from([-1f32, 2f32, 3f32]));
+//! assert_eq!(gltf_to_unity.convert_direction(Vector3::from([1f32, 2f32, 3f32])), Vector3::from([-1f32, 2f32, 3f32]));
+//! ```
+
+use crate::matrix::{Matrix3x3, Matrix4x4};
+#[cfg(feature = "quaternions")]
+use crate::quaternions::Quaternion;
+use crate::vector::Vector3;
+use crate::Element;
+
+/// A positive or negative coordinate axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Axis {
+    PositiveX,
+    NegativeX,
+    PositiveY,
+    NegativeY,
+    PositiveZ,
+    NegativeZ,
+}
+
+impl Axis {
+    /// Gives the index of the coordinate, 0 for x, 1 for y and 2 for z
+    #[inline(always)]
+    pub fn index(self) -> usize {
+        match self {
+            Self::PositiveX | Self::NegativeX => 0,
+            Self::PositiveY | Self::NegativeY => 1,
+            Self::PositiveZ | Self::NegativeZ => 2,
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_positive(self) -> bool {
+        matches!(self, Self::PositiveX | Self::PositiveY | Self::PositiveZ)
+    }
+
+    #[inline(always)]
+    pub fn negate(self) -> Self {
+        Self::from_index(self.index(), !self.is_positive())
+    }
+
+    /// Gives the unit Vector along the axis
+    #[inline(always)]
+    pub fn to_vector<T: Element>(self) -> Vector3<T> {
+        let mut ret = Vector3([T::zero(); 3]);
+        ret[self.index()] = if self.is_positive() {
+            T::one()
+        } else {
+            -T::one()
+        };
+        ret
+    }
+
+    #[inline(always)]
+    fn from_index(index: usize, positive: bool) -> Self {
+        match (index, positive) {
+            (0, true) => Self::PositiveX,
+            (0, false) => Self::NegativeX,
+            (1, true) => Self::PositiveY,
+            (1, false) => Self::NegativeY,
+            (_, true) => Self::PositiveZ,
+            (_, false) => Self::NegativeZ,
+        }
+    }
+
+    /// Gives the cross product of two axes, which must be different
+    #[inline(always)]
+    fn cross(self, other: Self) -> Self {
+        let (i, j) = (self.index(), other.index());
+        // x cross y is z, and the reverse order is negative
+        let cyclic = j == (i + 1) % 3;
+        let positive = (self.is_positive() == other.is_positive()) == cyclic;
+        Self::from_index(3 - i - j, positive)
+    }
+}
+
+/// Which hand's fingers curl from x to y when its thumb points along z
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Handedness {
+    Right,
+    Left,
+}
+
+/// A coordinate system, as the axes pointing up and forward and its handedness
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoordinateSystem {
+    up: Axis,
+    forward: Axis,
+    handedness: Handedness,
+}
+
+impl CoordinateSystem {
+    /// glTF: +Y up, +Z forward and right-handed, so -X is right
+    pub const GLTF: Self = Self {
+        up: Axis::PositiveY,
+        forward: Axis::PositiveZ,
+        handedness: Handedness::Right,
+    };
+
+    /// Blender: +Z up, -Y forward and right-handed, so -X is right
+    pub const BLENDER: Self = Self {
+        up: Axis::PositiveZ,
+        forward: Axis::NegativeY,
+        handedness: Handedness::Right,
+    };
+
+    /// Unity: +Y up, +Z forward and left-handed, so +X is right
+    pub const UNITY: Self = Self {
+        up: Axis::PositiveY,
+        forward: Axis::PositiveZ,
+        handedness: Handedness::Left,
+    };
+
+    /// Gives None if up and forward are on the same axis
+    #[inline(always)]
+    pub fn new(up: Axis, forward: Axis, handedness: Handedness) -> Option<Self> {
+        if up.index() == forward.index() {
+            return None;
+        }
+        Some(Self {
+            up,
+            forward,
+            handedness,
+        })
+    }
+
+    #[inline(always)]
+    pub fn up(self) -> Axis {
+        self.up
+    }
+
+    #[inline(always)]
+    pub fn forward(self) -> Axis {
+        self.forward
+    }
+
+    #[inline(always)]
+    pub fn handedness(self) -> Handedness {
+        self.handedness
+    }
+
+    /// Gives the axis pointing right, which is forward cross up
+    /// in a right-handed system, and up cross forward in a left-handed one
+    #[inline(always)]
+    pub fn right(self) -> Axis {
+        match self.handedness {
+            Handedness::Right => self.forward.cross(self.up),
+            Handedness::Left => self.up.cross(self.forward),
+        }
+    }
+
+    /// Gives the Matrix with the right, up and forward axes as its columns,
+    /// which takes right, up and forward coordinates to this system
+    #[inline(always)]
+    pub fn basis<T: Element>(self) -> Matrix3x3<T> {
+        Matrix3x3::from_cols([
+            self.right().to_vector(),
+            self.up.to_vector(),
+            self.forward.to_vector(),
+        ])
+    }
+}
+
+/// Builds a [`Conversion`] from a source to a target [`CoordinateSystem`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConversionBuilder<T: Element> {
+    source: CoordinateSystem,
+    target: CoordinateSystem,
+    scale: T,
+}
+
+impl<T: Element> ConversionBuilder<T> {
+    #[inline(always)]
+    pub fn new(source: CoordinateSystem, target: CoordinateSystem) -> Self {
+        Self {
+            source,
+            target,
+            scale: T::one(),
+        }
+    }
+
+    /// Sets the factor that positions are scaled by, to change units.
+    /// The default is 1
+    #[inline(always)]
+    pub fn scale(mut self, scale: T) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    #[inline(always)]
+    pub fn build(self) -> Conversion<T> {
+        Conversion {
+            basis: self
+                .target
+                .basis()
+                .mul_matrix(self.source.basis::<T>().transpose()),
+            scale: self.scale,
+            flips_handedness: self.source.handedness != self.target.handedness,
+        }
+    }
+}
+
+/// A change of basis between two coordinate systems, with a scale for units
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Conversion<T: Element> {
+    basis: Matrix3x3<T>,
+    scale: T,
+    flips_handedness: bool,
+}
+
+impl<T: Element> Conversion<T> {
+    /// Gives the change of basis, without the scale.
+    /// It is a rotation, or a reflection if the handedness flips
+    #[inline(always)]
+    pub fn matrix3(self) -> Matrix3x3<T> {
+        self.basis
+    }
+
+    /// Gives the change of basis with the scale, as an affine Matrix
+    #[inline(always)]
+    pub fn matrix4(self) -> Matrix4x4<T> {
+        let mut ret = Matrix4x4::ident();
+        for cx in 0..3 {
+            for cy in 0..3 {
+                ret.0[cx][cy] = self.basis.0[cx][cy] * self.scale;
+            }
+        }
+        ret
+    }
+
+    #[inline(always)]
+    pub fn scale(self) -> T {
+        self.scale
+    }
+
+    #[inline(always)]
+    pub fn flips_handedness(self) -> bool {
+        self.flips_handedness
+    }
+
+    /// Gives the Conversion back from the target to the source
+    #[inline(always)]
+    pub fn inverse(self) -> Self {
+        Self {
+            basis: self.basis.transpose(),
+            scale: T::one() / self.scale,
+            flips_handedness: self.flips_handedness,
+        }
+    }
+
+    /// Converts a position, which is also scaled
+    #[inline(always)]
+    pub fn convert_point(self, point: Vector3<T>) -> Vector3<T> {
+        self.basis.mul_vector(point).mul_scalar(self.scale)
+    }
+
+    /// Converts a direction, like a normal, which is not scaled
+    #[inline(always)]
+    pub fn convert_direction(self, direction: Vector3<T>) -> Vector3<T> {
+        self.basis.mul_vector(direction)
+    }
+
+    /// Converts a rotation, so that it turns converted Vectors the way
+    /// the original turns the originals.
+    /// The axis of a rotation is a pseudovector: under a reflection it
+    /// is mirrored and then negated, so the rotation turns the other way
+    #[cfg(feature = "quaternions")]
+    #[inline(always)]
+    pub fn convert_quaternion(self, quaternion: Quaternion<T>) -> Quaternion<T> {
+        let (vector, scalar) = quaternion.separate();
+        let mut vector = self.basis.mul_vector(vector);
+        if self.flips_handedness {
+            vector = vector.mul_scalar(-T::one());
+        }
+        Quaternion::from_scalar_vector(scalar, vector)
+    }
+
+    /// Converts a linear transformation, like a rotation or scale Matrix,
+    /// so that it acts on converted Vectors
+    #[inline(always)]
+    pub fn convert_matrix3(self, matrix: Matrix3x3<T>) -> Matrix3x3<T> {
+        self.basis
+            .mul_matrix(matrix)
+            .mul_matrix(self.basis.transpose())
+    }
+
+    /// Converts an affine transformation, like a node's local transform,
+    /// so that it acts on converted positions. Its translation is scaled
+    #[inline(always)]
+    pub fn convert_transform(self, matrix: Matrix4x4<T>) -> Matrix4x4<T> {
+        self.matrix4()
+            .mul_matrix(matrix)
+            .mul_matrix(self.inverse().matrix4())
+    }
+}
@@ -0,0 +1,662 @@
+//! Curves for camera paths and animation: Bézier, Hermite, Catmull-Rom and B-splines.
+//!
+//! Every curve works on any [`Point`]: scalars and Vectors of any size.
+//! They all implement [`Curve`], which gives the position and derivative at
+//! a parameter `t` in the curve's domain, and finds the closest point on the curve.
+//! An [`ArcLengthTable`] maps between `t` and the distance along any curve,
+//! to move along it at a constant speed.
+//!
+//! # Examples
+//!
+//! ```
+//! use glmath::curves::{ArcLengthTable, CatmullRom, Curve};
+//! use glmath::vector::Vector2;
+//!
+//! let path = CatmullRom::centripetal(vec![
+//!     Vector2::from([0f32, 0f32]),
+//!     Vector2::from([1f32, 0f32]),
+//!     Vector2::from([1f32, 1f32]),
+//! ])
+//! .unwrap();
+//! assert_eq!(path.domain(), (0f32, 2f32));
+//! assert_eq!(path.evaluate(1f32), Vector2::from([1f32, 0f32]));
+//!
+//! // halfway along the path
+//! let table = ArcLengthTable::new(&path, 256);
+//! let t = table.param_at(table.length() / 2f32);
+//! assert!((t - 1f32).abs() < 1e-3);
+//! ```
+
+use crate::triangle::{Attribute, Point};
+use crate::Element;
+
+#[inline(always)]
+fn lerp<T: Element, P: Attribute<Scalar = T>>(a: P, b: P, t: T) -> P {
+    a.mul_scalar(T::one() - t).add(b.mul_scalar(t))
+}
+
+#[inline(always)]
+fn dist<T: Element, P: Point<Scalar = T>>(a: P, b: P) -> T {
+    let offset = b.sub(a);
+    offset.mul_inner(offset).sqrt()
+}
+
+#[inline(always)]
+fn from_usize<T: Element>(value: usize) -> T {
+    num::cast(value).unwrap()
+}
+
+/// A curve, going through the values of a [`Point`] as `t` goes through its domain
+pub trait Curve {
+    type Value: Point;
+
+    /// Gives the range of `t`. Parameters out of it are clamped to it
+    fn domain(
+        &self,
+    ) -> (
+        <Self::Value as Attribute>::Scalar,
+        <Self::Value as Attribute>::Scalar,
+    );
+
+    fn evaluate(&self, t: <Self::Value as Attribute>::Scalar) -> Self::Value;
+
+    /// Gives the derivative of the curve with respect to `t`,
+    /// which is its tangent
+    fn derivative(&self, t: <Self::Value as Attribute>::Scalar) -> Self::Value;
+
+    /// Gives the `t` of the point on the curve closest to the given point.
+    /// The curve is sampled at `samples` even steps of `t`, and the closest
+    /// sample refined between its neighbours. A curve that comes back close
+    /// to the same point needs enough samples to tell its passes apart
+    fn closest_param(
+        &self,
+        point: Self::Value,
+        samples: usize,
+    ) -> <Self::Value as Attribute>::Scalar {
+        closest_param(self, point, samples)
+    }
+
+    /// Gives the point on the curve closest to the given point,
+    /// see [`Self::closest_param`]
+    #[inline(always)]
+    fn closest_point(&self, point: Self::Value, samples: usize) -> Self::Value {
+        self.evaluate(self.closest_param(point, samples))
+    }
+}
+
+fn closest_param<T: Element, P: Point<Scalar = T>, C: Curve<Value = P> + ?Sized>(
+    curve: &C,
+    point: P,
+    samples: usize,
+) -> T {
+    let samples = samples.max(1);
+    let (start, end) = curve.domain();
+    let param = |cx: usize| lerp(start, end, from_usize::<T>(cx) / from_usize(samples));
+    let distance = |t: T| {
+        let offset = curve.evaluate(t).sub(point);
+        offset.mul_inner(offset)
+    };
+
+    let closest = (0..=samples)
+        .min_by(|ca, cb| {
+            distance(param(*ca))
+                .partial_cmp(&distance(param(*cb)))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap_or(0);
+    // golden section search between the neighbours of the closest sample
+    let mut low = param(closest.saturating_sub(1));
+    let mut high = param((closest + 1).min(samples));
+    let ratio = (from_usize::<T>(5).sqrt() - T::one()) / from_usize(2);
+    for _ in 0..64 {
+        let t1 = high - (high - low) * ratio;
+        let t2 = low + (high - low) * ratio;
+        if distance(t1) < distance(t2) {
+            high = t2;
+        } else {
+            low = t1;
+        }
+    }
+    (low + high) / from_usize(2)
+}
+
+#[inline(always)]
+fn clamp_param<T: Element>(t: T, (start, end): (T, T)) -> T {
+    t.max(start).min(end)
+}
+
+/// A quadratic Bézier curve, for `t` from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadraticBezier<P: Point> {
+    pub p0: P,
+    pub p1: P,
+    pub p2: P,
+}
+
+impl<T: Element, P: Point<Scalar = T>> QuadraticBezier<P> {
+    #[inline(always)]
+    pub fn new(p0: P, p1: P, p2: P) -> Self {
+        Self { p0, p1, p2 }
+    }
+
+    /// Splits the curve at `t` into two, with de Casteljau's algorithm
+    #[inline(always)]
+    pub fn split(self, t: T) -> (Self, Self) {
+        let q0 = lerp(self.p0, self.p1, t);
+        let q1 = lerp(self.p1, self.p2, t);
+        let r = lerp(q0, q1, t);
+        (Self::new(self.p0, q0, r), Self::new(r, q1, self.p2))
+    }
+
+    /// Gives the same curve as a cubic Bézier
+    #[inline(always)]
+    pub fn to_cubic(self) -> CubicBezier<P> {
+        let two_thirds = from_usize::<T>(2) / from_usize(3);
+        CubicBezier::new(
+            self.p0,
+            lerp(self.p0, self.p1, two_thirds),
+            lerp(self.p2, self.p1, two_thirds),
+            self.p2,
+        )
+    }
+}
+
+impl<T: Element, P: Point<Scalar = T>> Curve for QuadraticBezier<P> {
+    type Value = P;
+
+    #[inline(always)]
+    fn domain(&self) -> (T, T) {
+        (T::zero(), T::one())
+    }
+
+    #[inline(always)]
+    fn evaluate(&self, t: T) -> P {
+        let t = clamp_param(t, self.domain());
+        let s = T::one() - t;
+        self.p0
+            .mul_scalar(s * s)
+            .add(self.p1.mul_scalar(from_usize::<T>(2) * s * t))
+            .add(self.p2.mul_scalar(t * t))
+    }
+
+    #[inline(always)]
+    fn derivative(&self, t: T) -> P {
+        let t = clamp_param(t, self.domain());
+        let two = from_usize::<T>(2);
+        self.p1
+            .sub(self.p0)
+            .mul_scalar(two * (T::one() - t))
+            .add(self.p2.sub(self.p1).mul_scalar(two * t))
+    }
+}
+
+/// A cubic Bézier curve, for `t` from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezier<P: Point> {
+    pub p0: P,
+    pub p1: P,
+    pub p2: P,
+    pub p3: P,
+}
+
+impl<T: Element, P: Point<Scalar = T>> CubicBezier<P> {
+    #[inline(always)]
+    pub fn new(p0: P, p1: P, p2: P, p3: P) -> Self {
+        Self { p0, p1, p2, p3 }
+    }
+
+    /// Splits the curve at `t` into two, with de Casteljau's algorithm
+    #[inline(always)]
+    pub fn split(self, t: T) -> (Self, Self) {
+        let q0 = lerp(self.p0, self.p1, t);
+        let q1 = lerp(self.p1, self.p2, t);
+        let q2 = lerp(self.p2, self.p3, t);
+        let r0 = lerp(q0, q1, t);
+        let r1 = lerp(q1, q2, t);
+        let s = lerp(r0, r1, t);
+        (Self::new(self.p0, q0, r0, s), Self::new(s, r1, q2, self.p3))
+    }
+
+    /// Gives the same curve in Hermite form
+    #[inline(always)]
+    pub fn to_hermite(self) -> CubicHermite<P> {
+        let three = from_usize::<T>(3);
+        CubicHermite::new(
+            self.p0,
+            self.p1.sub(self.p0).mul_scalar(three),
+            self.p3,
+            self.p3.sub(self.p2).mul_scalar(three),
+        )
+    }
+}
+
+impl<T: Element, P: Point<Scalar = T>> Curve for CubicBezier<P> {
+    type Value = P;
+
+    #[inline(always)]
+    fn domain(&self) -> (T, T) {
+        (T::zero(), T::one())
+    }
+
+    #[inline(always)]
+    fn evaluate(&self, t: T) -> P {
+        let t = clamp_param(t, self.domain());
+        let s = T::one() - t;
+        let three = from_usize::<T>(3);
+        self.p0
+            .mul_scalar(s * s * s)
+            .add(self.p1.mul_scalar(three * s * s * t))
+            .add(self.p2.mul_scalar(three * s * t * t))
+            .add(self.p3.mul_scalar(t * t * t))
+    }
+
+    #[inline(always)]
+    fn derivative(&self, t: T) -> P {
+        let t = clamp_param(t, self.domain());
+        let s = T::one() - t;
+        let three = from_usize::<T>(3);
+        self.p1
+            .sub(self.p0)
+            .mul_scalar(three * s * s)
+            .add(
+                self.p2
+                    .sub(self.p1)
+                    .mul_scalar(three * from_usize(2) * s * t),
+            )
+            .add(self.p3.sub(self.p2).mul_scalar(three * t * t))
+    }
+}
+
+/// A cubic Hermite curve from p0 to p1, with the tangents m0 and m1
+/// at its ends, for `t` from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicHermite<P: Point> {
+    pub p0: P,
+    pub m0: P,
+    pub p1: P,
+    pub m1: P,
+}
+
+impl<T: Element, P: Point<Scalar = T>> CubicHermite<P> {
+    #[inline(always)]
+    pub fn new(p0: P, m0: P, p1: P, m1: P) -> Self {
+        Self { p0, m0, p1, m1 }
+    }
+
+    /// Gives the same curve in Bézier form
+    #[inline(always)]
+    pub fn to_bezier(self) -> CubicBezier<P> {
+        let third = T::one() / from_usize(3);
+        CubicBezier::new(
+            self.p0,
+            self.p0.add(self.m0.mul_scalar(third)),
+            self.p1.sub(self.m1.mul_scalar(third)),
+            self.p1,
+        )
+    }
+}
+
+impl<T: Element, P: Point<Scalar = T>> Curve for CubicHermite<P> {
+    type Value = P;
+
+    #[inline(always)]
+    fn domain(&self) -> (T, T) {
+        (T::zero(), T::one())
+    }
+
+    #[inline(always)]
+    fn evaluate(&self, t: T) -> P {
+        let t = clamp_param(t, self.domain());
+        let (two, three) = (from_usize::<T>(2), from_usize::<T>(3));
+        let t2 = t * t;
+        let t3 = t2 * t;
+        self.p0
+            .mul_scalar(two * t3 - three * t2 + T::one())
+            .add(self.m0.mul_scalar(t3 - two * t2 + t))
+            .add(self.p1.mul_scalar(three * t2 - two * t3))
+            .add(self.m1.mul_scalar(t3 - t2))
+    }
+
+    #[inline(always)]
+    fn derivative(&self, t: T) -> P {
+        let t = clamp_param(t, self.domain());
+        let (two, three, four, six) = (
+            from_usize::<T>(2),
+            from_usize::<T>(3),
+            from_usize::<T>(4),
+            from_usize::<T>(6),
+        );
+        let t2 = t * t;
+        self.p0
+            .mul_scalar(six * t2 - six * t)
+            .add(self.m0.mul_scalar(three * t2 - four * t + T::one()))
+            .add(self.p1.mul_scalar(six * t - six * t2))
+            .add(self.m1.mul_scalar(three * t2 - two * t))
+    }
+}
+
+/// A Catmull-Rom spline through all of its points, with `t` going from 0
+/// at the first point to `points.len() - 1` at the last.
+///
+/// Alpha picks how the spline is parameterized between the points:
+/// 0 for uniform, 0.5 for centripetal and 1 for chordal.
+/// The centripetal spline never forms cusps or loops within a segment.
+/// The first and last points are mirrored to give the end segments their tangents
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatmullRom<P: Point> {
+    points: Vec<P>,
+    alpha: P::Scalar,
+}
+
+impl<T: Element, P: Point<Scalar = T>> CatmullRom<P> {
+    /// Creates the spline.
+    /// Gives None for less than 2 points
+    #[inline(always)]
+    pub fn new(points: Vec<P>, alpha: T) -> Option<Self> {
+        if points.len() < 2 {
+            return None;
+        }
+        Some(Self { points, alpha })
+    }
+
+    /// Creates the centripetal spline, with an alpha of 0.5.
+    /// Gives None for less than 2 points
+    #[inline(always)]
+    pub fn centripetal(points: Vec<P>) -> Option<Self> {
+        Self::new(points, T::one() / from_usize(2))
+    }
+
+    #[inline(always)]
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    #[inline(always)]
+    pub fn alpha(&self) -> T {
+        self.alpha
+    }
+
+    #[inline(always)]
+    pub fn segment_count(&self) -> usize {
+        self.points.len() - 1
+    }
+
+    /// Gives the segment from `points[index]` to `points[index + 1]`
+    /// as a Hermite curve
+    #[inline(always)]
+    pub fn segment(&self, index: usize) -> CubicHermite<P> {
+        let last = self.points.len() - 1;
+        let p1 = self.points[index];
+        let p2 = self.points[index + 1];
+        let p0 = if index == 0 {
+            p1.add(p1.sub(p2))
+        } else {
+            self.points[index - 1]
+        };
+        let p3 = if index + 1 == last {
+            p2.add(p2.sub(p1))
+        } else {
+            self.points[index + 2]
+        };
+
+        // the knot intervals, with repeated points given a width
+        // so they don't divide by 0
+        let interval = |a: P, b: P| dist(a, b).powf(self.alpha);
+        let mut dt1 = interval(p1, p2);
+        if dt1 <= T::epsilon() {
+            dt1 = T::one();
+        }
+        let mut dt0 = interval(p0, p1);
+        if dt0 <= T::epsilon() {
+            dt0 = dt1;
+        }
+        let mut dt2 = interval(p2, p3);
+        if dt2 <= T::epsilon() {
+            dt2 = dt1;
+        }
+
+        // the tangents of the non-uniform spline, scaled to a segment of length 1
+        let m1 = p1
+            .sub(p0)
+            .mul_scalar(T::one() / dt0)
+            .sub(p2.sub(p0).mul_scalar(T::one() / (dt0 + dt1)))
+            .add(p2.sub(p1).mul_scalar(T::one() / dt1))
+            .mul_scalar(dt1);
+        let m2 = p2
+            .sub(p1)
+            .mul_scalar(T::one() / dt1)
+            .sub(p3.sub(p1).mul_scalar(T::one() / (dt1 + dt2)))
+            .add(p3.sub(p2).mul_scalar(T::one() / dt2))
+            .mul_scalar(dt1);
+        CubicHermite::new(p1, m1, p2, m2)
+    }
+
+    /// Gives the segment `t` is in and the `t` within it
+    #[inline(always)]
+    fn locate(&self, t: T) -> (usize, T) {
+        let t = clamp_param(t, self.domain());
+        let index = t
+            .floor()
+            .to_usize()
+            .unwrap_or(0)
+            .min(self.segment_count() - 1);
+        (index, t - from_usize(index))
+    }
+}
+
+impl<T: Element, P: Point<Scalar = T>> Curve for CatmullRom<P> {
+    type Value = P;
+
+    #[inline(always)]
+    fn domain(&self) -> (T, T) {
+        (T::zero(), from_usize(self.segment_count()))
+    }
+
+    #[inline(always)]
+    fn evaluate(&self, t: T) -> P {
+        let (index, t) = self.locate(t);
+        self.segment(index).evaluate(t)
+    }
+
+    #[inline(always)]
+    fn derivative(&self, t: T) -> P {
+        let (index, t) = self.locate(t);
+        self.segment(index).derivative(t)
+    }
+}
+
+/// A B-spline of any degree, with `t` going from `knots[degree]`
+/// to `knots[points.len()]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BSpline<P: Point> {
+    points: Vec<P>,
+    knots: Vec<P::Scalar>,
+    degree: usize,
+}
+
+/// Evaluates the B-spline with de Boor's algorithm
+fn de_boor<T: Element, P: Point<Scalar = T>>(points: &[P], knots: &[T], degree: usize, t: T) -> P {
+    let count = points.len();
+    let t = clamp_param(t, (knots[degree], knots[count]));
+    // the last span holding t, so the end of the domain falls in the last span
+    let span = (degree..count)
+        .rev()
+        .find(|cx| knots[*cx] <= t)
+        .unwrap_or(degree);
+
+    let mut d: Vec<P> = points[span - degree..=span].to_vec();
+    for cy in 1..=degree {
+        for cx in (cy..=degree).rev() {
+            let index = cx + span - degree;
+            let denom = knots[index + degree + 1 - cy] - knots[index];
+            let alpha = if denom > T::zero() {
+                (t - knots[index]) / denom
+            } else {
+                T::zero()
+            };
+            d[cx] = lerp(d[cx - 1], d[cx], alpha);
+        }
+    }
+    d[degree]
+}
+
+impl<T: Element, P: Point<Scalar = T>> BSpline<P> {
+    /// Creates the B-spline, which needs `points.len() + degree + 1`
+    /// non-decreasing knots.
+    /// Gives None for the wrong number of knots, decreasing knots,
+    /// not more points than the degree or an empty domain
+    #[inline(always)]
+    pub fn new(points: Vec<P>, knots: Vec<T>, degree: usize) -> Option<Self> {
+        if points.len() <= degree
+            || knots.len() != points.len() + degree + 1
+            || knots.windows(2).any(|ca| ca[0] > ca[1])
+            || knots[degree] >= knots[points.len()]
+        {
+            return None;
+        }
+        Some(Self {
+            points,
+            knots,
+            degree,
+        })
+    }
+
+    /// Creates the B-spline with the knots 0, 1, 2 and so on.
+    /// It doesn't go through its first and last points.
+    /// Gives None for not more points than the degree
+    #[inline(always)]
+    pub fn uniform(points: Vec<P>, degree: usize) -> Option<Self> {
+        let knots = (0..points.len() + degree + 1).map(from_usize).collect();
+        Self::new(points, knots, degree)
+    }
+
+    /// Creates the B-spline with uniform knots, except for the first and last
+    /// which repeat to make it start and end at its first and last points.
+    /// Gives None for not more points than the degree
+    #[inline(always)]
+    pub fn clamped(points: Vec<P>, degree: usize) -> Option<Self> {
+        let inner = points.len().saturating_sub(degree);
+        let knots = (0..points.len() + degree + 1)
+            .map(|cx| from_usize(cx.saturating_sub(degree).min(inner)))
+            .collect();
+        Self::new(points, knots, degree)
+    }
+
+    #[inline(always)]
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    #[inline(always)]
+    pub fn knots(&self) -> &[T] {
+        &self.knots
+    }
+
+    #[inline(always)]
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+}
+
+impl<T: Element, P: Point<Scalar = T>> Curve for BSpline<P> {
+    type Value = P;
+
+    #[inline(always)]
+    fn domain(&self) -> (T, T) {
+        (self.knots[self.degree], self.knots[self.points.len()])
+    }
+
+    #[inline(always)]
+    fn evaluate(&self, t: T) -> P {
+        de_boor(&self.points, &self.knots, self.degree, t)
+    }
+
+    /// The derivative is the B-spline of one degree less, over the
+    /// scaled differences of the points
+    #[inline(always)]
+    fn derivative(&self, t: T) -> P {
+        let t = clamp_param(t, self.domain());
+        let degree = self.degree;
+        if degree == 0 {
+            return self.points[0].mul_scalar(T::zero());
+        }
+        let points: Vec<P> = (0..self.points.len() - 1)
+            .map(|cx| {
+                let denom = self.knots[cx + degree + 1] - self.knots[cx + 1];
+                let scale = if denom > T::zero() {
+                    from_usize::<T>(degree) / denom
+                } else {
+                    T::zero()
+                };
+                self.points[cx + 1].sub(self.points[cx]).mul_scalar(scale)
+            })
+            .collect();
+        de_boor(&points, &self.knots[1..self.knots.len() - 1], degree - 1, t)
+    }
+}
+
+/// A table of distances along a curve, to convert between `t` and arc length.
+/// The curve is measured as straight lines between its samples
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcLengthTable<T: Element> {
+    params: Vec<T>,
+    lengths: Vec<T>,
+}
+
+impl<T: Element> ArcLengthTable<T> {
+    /// Measures the curve at `samples` even steps of `t`
+    pub fn new<P: Point<Scalar = T>>(curve: &impl Curve<Value = P>, samples: usize) -> Self {
+        let samples = samples.max(1);
+        let (start, end) = curve.domain();
+        let params: Vec<T> = (0..=samples)
+            .map(|cx| lerp(start, end, from_usize::<T>(cx) / from_usize(samples)))
+            .collect();
+        let mut lengths = Vec::with_capacity(params.len());
+        let mut length = T::zero();
+        let mut previous = curve.evaluate(start);
+        for t in &params {
+            let point = curve.evaluate(*t);
+            length = length + dist(previous, point);
+            lengths.push(length);
+            previous = point;
+        }
+        Self { params, lengths }
+    }
+
+    /// Gives the length of the whole curve
+    #[inline(always)]
+    pub fn length(&self) -> T {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    /// Gives the distance along the curve at `t`
+    #[inline(always)]
+    pub fn distance_at(&self, t: T) -> T {
+        Self::lookup(&self.params, &self.lengths, t)
+    }
+
+    /// Gives the `t` at the distance along the curve,
+    /// to sample the curve at a constant speed
+    #[inline(always)]
+    pub fn param_at(&self, distance: T) -> T {
+        Self::lookup(&self.lengths, &self.params, distance)
+    }
+
+    /// Interpolates `to` at the position of value in the sorted `from`
+    #[inline(always)]
+    fn lookup(from: &[T], to: &[T], value: T) -> T {
+        let last = from.len() - 1;
+        if value <= from[0] {
+            return to[0];
+        }
+        if value >= from[last] {
+            return to[last];
+        }
+        let index = from.partition_point(|ca| *ca <= value).min(last);
+        let (low, high) = (from[index - 1], from[index]);
+        if high <= low {
+            return to[index];
+        }
+        lerp(to[index - 1], to[index], (value - low) / (high - low))
+    }
+}
@@ -0,0 +1,504 @@
+//! Text formatting and parsing of Vectors, Matrices and Quaternions.
+//!
+//! Vectors print as `(1, 2, 3)`, with any width or precision flags applied to every element.
+//! Matrices print one row per line, padded so the columns line up:
+//!
+//! ```text
+//! [ 1.000, 0.000, 5.000]
+//! [-2.500, 1.000, 0.000]
+//! [ 0.000, 0.000, 1.000]
+//! ```
+//!
+//! Parsing (through [`str::parse`]) accepts the printed forms, plus
+//! - arrays: `[1, 2, 3]`, `[[1, 2], [3, 4]]`, or a flat row-major `[1, 2, 3, 4]` for Matrices
+//! - bare lists: `1, 2, 3` or `1 2 3`
+//! - GLSL constructors: `vec3(1.0, 2.0, 3.0)`, `vec4(vec3(1.0), 1.0)`, `dvec2(0.5)`,
+//!   `mat2(1.0, 2.0, 3.0, 4.0)` and `mat3(2.0)`. Like in GLSL, matrix constructors
+//!   take their elements column by column, and a single value fills the diagonal
+//!
+//! Numbers may have a GLSL `f` or `lf` suffix.
+//!
+//! # Examples
+//!
+//! ```
+//! use glmath::matrix::Matrix2x2;
+//! use glmath::vector::Vector3;
+//!
+//! let v: Vector3<f32> = "vec3(1.0, 2.0, 3.0)".parse().unwrap();
+//! assert_eq!(v, Vector3::from([1f32, 2f32, 3f32]));
+//! assert_eq!(format!("{v:.1}"), "(1.0, 2.0, 3.0)");
+//!
+//! let m: Matrix2x2<f32> = "mat2(1.0, 3.0, 2.0, 4.0)".parse().unwrap();
+//! assert_eq!(m, Matrix2x2::from([1f32, 2f32, 3f32, 4f32]));
+//! assert_eq!(format!("{m}"), "[1, 2]\n[3, 4]");
+//! assert_eq!(format!("{m}").parse::<Matrix2x2<f32>>().unwrap(), m);
+//! ```
+
+use std::fmt::{self, Display, LowerExp};
+use std::str::FromStr;
+
+use crate::matrix::{Matrix2x2, Matrix3x3, Matrix4x4};
+use crate::vector::{Vector2, Vector3, Vector4};
+use crate::Element;
+
+/// The reasons parsing a Vector, Matrix or Quaternion can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text has no elements at all
+    Empty,
+    /// An element is not a number
+    InvalidNumber(String),
+    /// A character that doesn't belong there, or None when the text ended early
+    UnexpectedChar {
+        found: Option<char>,
+        position: usize,
+    },
+    /// The number of elements (or rows) doesn't fit the type
+    WrongCount { expected: usize, found: usize },
+    /// A constructor that doesn't build the type, like `vec4(...)` for a Vector3
+    UnexpectedConstructor(String),
+    /// A nested group where a number was expected
+    UnexpectedGroup,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "no elements to parse"),
+            ParseError::InvalidNumber(text) => write!(f, "invalid number `{text}`"),
+            ParseError::UnexpectedChar {
+                found: Some(found),
+                position,
+            } => write!(f, "unexpected `{found}` at position {position}"),
+            ParseError::UnexpectedChar { found: None, .. } => write!(f, "unexpected end of text"),
+            ParseError::WrongCount { expected, found } => {
+                write!(f, "expected {expected} elements, found {found}")
+            }
+            ParseError::UnexpectedConstructor(name) => {
+                write!(f, "`{name}` doesn't construct this type")
+            }
+            ParseError::UnexpectedGroup => write!(f, "expected a number, found a group"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An element of the parsed text: a number, or a
+/// bracketed list of elements with an optional constructor name
+enum Node<'a> {
+    Number(&'a str),
+    Group {
+        name: Option<&'a str>,
+        items: Vec<Node<'a>>,
+    },
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn unexpected(&self) -> ParseError {
+        ParseError::UnexpectedChar {
+            found: self.peek(),
+            position: self.position,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Parses elements, separated by commas or whitespace,
+    /// until the closing delimiter (or the end of the text if None)
+    fn items(&mut self, close: Option<char>) -> Result<Vec<Node<'a>>, ParseError> {
+        let mut items = Vec::new();
+        let mut after_comma = false;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                found if found == close && !after_comma => {
+                    self.position += found.map_or(0, char::len_utf8);
+                    return Ok(items);
+                }
+                None | Some(',' | ')' | ']') => return Err(self.unexpected()),
+                Some(_) => {
+                    items.push(self.node()?);
+                    self.skip_whitespace();
+                    after_comma = self.peek() == Some(',');
+                    if after_comma {
+                        self.position += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn node(&mut self) -> Result<Node<'a>, ParseError> {
+        match self.peek() {
+            Some('(') => {
+                self.position += 1;
+                let items = self.items(Some(')'))?;
+                Ok(Node::Group { name: None, items })
+            }
+            Some('[') => {
+                self.position += 1;
+                let items = self.items(Some(']'))?;
+                Ok(Node::Group { name: None, items })
+            }
+            _ => {
+                let rest = &self.text[self.position..];
+                let len = rest
+                    .find(|ca: char| ca.is_whitespace() || ",()[]".contains(ca))
+                    .unwrap_or(rest.len());
+                if len == 0 {
+                    return Err(self.unexpected());
+                }
+                self.position += len;
+                if self.peek() == Some('(') {
+                    self.position += 1;
+                    let items = self.items(Some(')'))?;
+                    Ok(Node::Group {
+                        name: Some(&rest[..len]),
+                        items,
+                    })
+                } else {
+                    Ok(Node::Number(&rest[..len]))
+                }
+            }
+        }
+    }
+}
+
+fn parse_items(text: &str) -> Result<Vec<Node<'_>>, ParseError> {
+    let items = Parser { text, position: 0 }.items(None)?;
+    if items.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    Ok(items)
+}
+
+/// Parses a number, allowing the GLSL `f` and `lf` suffixes
+fn parse_number<T: Element>(text: &str) -> Result<T, ParseError> {
+    if let Ok(value) = T::from_str_radix(text, 10) {
+        return Ok(value);
+    }
+    ["lf", "LF", "f", "F"]
+        .iter()
+        .filter_map(|suffix| text.strip_suffix(suffix))
+        .filter(|number| number.ends_with(|ca: char| ca.is_ascii_digit() || ca == '.'))
+        .find_map(|number| T::from_str_radix(number, 10).ok())
+        .ok_or_else(|| ParseError::InvalidNumber(text.to_string()))
+}
+
+/// Gives the size of a `vecN`/`dvecN` constructor
+fn vector_size(name: &str) -> Option<usize> {
+    match name.strip_prefix('d').unwrap_or(name) {
+        "vec2" => Some(2),
+        "vec3" => Some(3),
+        "vec4" => Some(4),
+        _ => None,
+    }
+}
+
+/// Gives the size of a `matN`/`matNxN`/`dmatN`/`dmatNxN` constructor
+fn matrix_size(name: &str) -> Option<usize> {
+    match name.strip_prefix('d').unwrap_or(name) {
+        "mat2" | "mat2x2" => Some(2),
+        "mat3" | "mat3x3" => Some(3),
+        "mat4" | "mat4x4" => Some(4),
+        _ => None,
+    }
+}
+
+/// Parses plain numbers, without any nested groups
+fn numbers<T: Element>(items: &[Node<'_>]) -> Result<Vec<T>, ParseError> {
+    items
+        .iter()
+        .map(|item| match item {
+            Node::Number(text) => parse_number(text),
+            Node::Group { .. } => Err(ParseError::UnexpectedGroup),
+        })
+        .collect()
+}
+
+/// Flattens the arguments of a GLSL constructor into its components.
+/// Vector constructors with a single argument are splatted
+fn flatten<T: Element>(items: &[Node<'_>], ret: &mut Vec<T>) -> Result<(), ParseError> {
+    for item in items {
+        match item {
+            Node::Number(text) => ret.push(parse_number(text)?),
+            Node::Group {
+                name: Some(name),
+                items,
+            } => {
+                let size = vector_size(name)
+                    .ok_or_else(|| ParseError::UnexpectedConstructor(name.to_string()))?;
+                let mut components: Vec<T> = Vec::new();
+                flatten(items, &mut components)?;
+                match components.len() {
+                    1 => ret.extend([components[0]].repeat(size)),
+                    len if len == size => ret.extend(components),
+                    found => {
+                        return Err(ParseError::WrongCount {
+                            expected: size,
+                            found,
+                        })
+                    }
+                }
+            }
+            Node::Group { name: None, .. } => return Err(ParseError::UnexpectedGroup),
+        }
+    }
+    Ok(())
+}
+
+fn to_array<U, const N: usize>(values: Vec<U>) -> Result<[U; N], ParseError> {
+    values
+        .try_into()
+        .map_err(|values: Vec<U>| ParseError::WrongCount {
+            expected: N,
+            found: values.len(),
+        })
+}
+
+pub(crate) fn parse_vector<T: Element, const N: usize>(text: &str) -> Result<[T; N], ParseError> {
+    let items = parse_items(text)?;
+    let values = match items.as_slice() {
+        [Node::Group { name: None, items }] => numbers(items)?,
+        [Node::Group {
+            name: Some(name), ..
+        }] => {
+            if vector_size(name) != Some(N) {
+                return Err(ParseError::UnexpectedConstructor(name.to_string()));
+            }
+            let mut values = Vec::new();
+            flatten(&items, &mut values)?;
+            values
+        }
+        items => numbers(items)?,
+    };
+    to_array(values)
+}
+
+pub(crate) fn parse_matrix<T: Element, const N: usize>(
+    text: &str,
+) -> Result<[[T; N]; N], ParseError> {
+    let items = parse_items(text)?;
+    let is_row = |item: &Node<'_>| matches!(item, Node::Group { name: None, .. });
+    let rows = match items.as_slice() {
+        [Node::Group {
+            name: Some(name),
+            items,
+        }] => {
+            if matrix_size(name) != Some(N) {
+                return Err(ParseError::UnexpectedConstructor(name.to_string()));
+            }
+            let mut values = Vec::new();
+            flatten(items, &mut values)?;
+            return match values.len() {
+                1 => Ok(std::array::from_fn(|cx| {
+                    std::array::from_fn(|cy| if cx == cy { values[0] } else { T::zero() })
+                })),
+                len if len == N * N => Ok(std::array::from_fn(|cx| {
+                    std::array::from_fn(|cy| values[cy * N + cx])
+                })),
+                found => Err(ParseError::WrongCount {
+                    expected: N * N,
+                    found,
+                }),
+            };
+        }
+        [Node::Group { name: None, items }] if !items.is_empty() && items.iter().all(is_row) => {
+            items.as_slice()
+        }
+        [Node::Group { name: None, items }] => return flat_rows(numbers(items)?),
+        items if items.iter().all(is_row) => items,
+        items => return flat_rows(numbers(items)?),
+    };
+    let rows = rows
+        .iter()
+        .map(|row| match row {
+            Node::Group { items, .. } => to_array(numbers(items)?),
+            Node::Number(_) => Err(ParseError::UnexpectedGroup),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    to_array(rows)
+}
+
+/// Splits the row-major values into rows
+fn flat_rows<T: Element, const N: usize>(values: Vec<T>) -> Result<[[T; N]; N], ParseError> {
+    if values.len() != N * N {
+        return Err(ParseError::WrongCount {
+            expected: N * N,
+            found: values.len(),
+        });
+    }
+    Ok(std::array::from_fn(|cx| {
+        std::array::from_fn(|cy| values[cx * N + cy])
+    }))
+}
+
+/// Pads the text to the width, following the fill and alignment
+/// of the Formatter. Numbers are right aligned by default
+fn write_padded(f: &mut fmt::Formatter<'_>, text: &str, width: usize) -> fmt::Result {
+    let padding = width.saturating_sub(text.chars().count());
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (padding, 0),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        write!(f, "{fill}")?;
+    }
+    f.write_str(text)?;
+    for _ in 0..after {
+        write!(f, "{fill}")?;
+    }
+    Ok(())
+}
+
+macro_rules! FORMAT_ELEMENT {
+    ($f:expr, $value:expr, $kind:literal) => {
+        match ($f.precision(), $f.sign_plus()) {
+            (Some(precision), true) => format!(concat!("{:+.*", $kind, "}"), precision, $value),
+            (Some(precision), false) => format!(concat!("{:.*", $kind, "}"), precision, $value),
+            (None, true) => format!(concat!("{:+", $kind, "}"), $value),
+            (None, false) => format!(concat!("{:", $kind, "}"), $value),
+        }
+    };
+}
+
+macro_rules! GENERATE_FORMAT {
+    ($($n:expr),*) => {
+        $(
+            paste::item! {
+                /// Formats the Vector as `(x, y, ...)`.
+                /// Any width or precision flags are applied to every element
+                impl<T: Element + Display> Display for [<Vector $n>]<T> {
+                    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        f.write_str("(")?;
+                        for cx in 0..$n {
+                            if cx != 0 {
+                                f.write_str(", ")?;
+                            }
+                            Display::fmt(&self[cx], f)?;
+                        }
+                        f.write_str(")")
+                    }
+                }
+
+                /// Formats the Vector as `(x, y, ...)` in scientific notation.
+                /// Any width or precision flags are applied to every element
+                impl<T: Element + LowerExp> LowerExp for [<Vector $n>]<T> {
+                    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        f.write_str("(")?;
+                        for cx in 0..$n {
+                            if cx != 0 {
+                                f.write_str(", ")?;
+                            }
+                            LowerExp::fmt(&self[cx], f)?;
+                        }
+                        f.write_str(")")
+                    }
+                }
+
+                /// Parses the Vector, see the [module docs](self)
+                impl<T: Element> FromStr for [<Vector $n>]<T> {
+                    type Err = ParseError;
+                    fn from_str(text: &str) -> Result<Self, Self::Err> {
+                        parse_vector(text).map([<Vector $n>])
+                    }
+                }
+
+                /// Formats the Matrix with a row per line, with the columns lined up.
+                /// The precision flag is applied to every element, the width
+                /// is the minimum width of every element
+                impl<T: Element + Display> Display for [<Matrix $n x $n>]<T> {
+                    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        let elements = self.0.map(|row| row.0.map(|ca| FORMAT_ELEMENT!(f, ca, "")));
+                        write_matrix(f, &elements)
+                    }
+                }
+
+                /// Formats the Matrix like [`Display`], in scientific notation
+                impl<T: Element + LowerExp> LowerExp for [<Matrix $n x $n>]<T> {
+                    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        let elements = self.0.map(|row| row.0.map(|ca| FORMAT_ELEMENT!(f, ca, "e")));
+                        write_matrix(f, &elements)
+                    }
+                }
+
+                /// Parses the Matrix, see the [module docs](self)
+                impl<T: Element> FromStr for [<Matrix $n x $n>]<T> {
+                    type Err = ParseError;
+                    fn from_str(text: &str) -> Result<Self, Self::Err> {
+                        parse_matrix(text).map(|rows| [<Matrix $n x $n>](rows.map([<Vector $n>])))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+GENERATE_FORMAT!(2, 3, 4);
+
+fn write_matrix<const N: usize>(
+    f: &mut fmt::Formatter<'_>,
+    elements: &[[String; N]; N],
+) -> fmt::Result {
+    let width = elements
+        .iter()
+        .flatten()
+        .map(|ca| ca.chars().count())
+        .chain(f.width())
+        .max()
+        .unwrap_or(0);
+    for (cx, row) in elements.iter().enumerate() {
+        if cx != 0 {
+            f.write_str("\n")?;
+        }
+        f.write_str("[")?;
+        for (cy, ca) in row.iter().enumerate() {
+            if cy != 0 {
+                f.write_str(", ")?;
+            }
+            write_padded(f, ca, width)?;
+        }
+        f.write_str("]")?;
+    }
+    Ok(())
+}
+
+/// Formats the Quaternion as `(x, y, z, w)`, the same as its [`Vector4`]
+#[cfg(feature = "quaternions")]
+impl<T: Element + Display> Display for crate::quaternions::Quaternion<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&Vector4::from(self.to_xyzw()), f)
+    }
+}
+
+/// Formats the Quaternion as `(x, y, z, w)` in scientific notation, the same as its [`Vector4`]
+#[cfg(feature = "quaternions")]
+impl<T: Element + LowerExp> LowerExp for crate::quaternions::Quaternion<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        LowerExp::fmt(&Vector4::from(self.to_xyzw()), f)
+    }
+}
+
+/// Parses the Quaternion as `(x, y, z, w)`, see the [module docs](self)
+#[cfg(feature = "quaternions")]
+impl<T: Element> FromStr for crate::quaternions::Quaternion<T> {
+    type Err = ParseError;
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_vector::<T, 4>(text).map(Self::from)
+    }
+}
@@ -0,0 +1,187 @@
+//! View frustums, for culling against a camera.
+//!
+//! A [`Frustum`] is extracted from a view-projection Matrix4x4 with the
+//! Gribb–Hartmann method, which reads the planes off the rows of the Matrix.
+//! The Matrix maps to clip space for column vectors (`view_proj.mul_vector(p)`),
+//! with depth in any of the [`DepthRange`] conventions.
+//!
+//! # Examples
+//!
+//! ```
+//! use glmath::frustum::{Containment, Frustum};
+//! use glmath::geometry::Sphere;
+//! use glmath::matrix::Matrix4x4;
+//! use glmath::vector::Vector3;
+//!
+//! // an orthographic projection of the box from -1 to 1
+//! let frustum = Frustum::from_matrix(Matrix4x4::<f32>::ident());
+//! let sphere = |x| Sphere::new(Vector3::from([x, 0f32, 0f32]), 0.5f32);
+//!
+//! assert_eq!(frustum.classify_sphere(sphere(0f32)), Containment::Inside);
+//! assert_eq!(frustum.classify_sphere(sphere(1f32)), Containment::Intersecting);
+//! assert_eq!(frustum.classify_sphere(sphere(2f32)), Containment::Outside);
+//! ```
+
+use crate::geometry::{Aabb, Plane, Sphere};
+use crate::matrix::Matrix4x4;
+use crate::vector::{Vector3, Vector4};
+use crate::Element;
+
+/// The range of depth in clip space, after the divide by w
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DepthRange {
+    /// OpenGL's -1 to 1
+    #[default]
+    NegativeOneToOne,
+    /// Direct3D's, Vulkan's and Metal's 0 to 1, with near at 0
+    ZeroToOne,
+    /// 0 to 1 with near at 1 and far at 0, for reversed depth.
+    /// The far plane can be at infinity
+    ReversedZeroToOne,
+}
+
+/// Where a shape is relative to a [`Frustum`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Containment {
+    /// Fully inside
+    Inside,
+    /// Fully outside
+    Outside,
+    /// Crossing the boundary. Shapes just outside a corner
+    /// of the Frustum are also reported as this
+    Intersecting,
+}
+
+/// A view frustum, as 6 planes with their normals facing inwards
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Frustum<T: Element> {
+    /// Left, right, bottom, top, near and far
+    pub planes: [Plane<T>; 6],
+}
+
+/// Makes the Plane from the row `[a, b, c, d]` of `a*x + b*y + c*z + d*w >= 0`.
+/// An infinite far plane has a normal of 0, and then is left unnormalized,
+/// so it holds every point
+#[inline(always)]
+fn plane_from_row<T: Element>(row: Vector4<T>) -> Plane<T> {
+    let normal = Vector3([row[0], row[1], row[2]]);
+    if normal.length_squared() == T::zero() {
+        return Plane { normal, d: row[3] };
+    }
+    Plane::new(normal, row[3])
+}
+
+impl<T: Element> Frustum<T> {
+    /// Extracts the Frustum of the view-projection Matrix,
+    /// which has depth from -1 to 1 like OpenGL
+    #[inline(always)]
+    pub fn from_matrix(view_proj: Matrix4x4<T>) -> Self {
+        Self::from_matrix_with_depth(view_proj, DepthRange::NegativeOneToOne)
+    }
+
+    /// Extracts the Frustum of the view-projection Matrix,
+    /// which has depth in the given range
+    #[inline(always)]
+    pub fn from_matrix_with_depth(view_proj: Matrix4x4<T>, depth: DepthRange) -> Self {
+        let [r0, r1, r2, r3] = view_proj.0;
+        let (near, far) = match depth {
+            DepthRange::NegativeOneToOne => (r3.add(r2), r3.sub(r2)),
+            DepthRange::ZeroToOne => (r2, r3.sub(r2)),
+            DepthRange::ReversedZeroToOne => (r3.sub(r2), r2),
+        };
+        Self {
+            planes: [r3.add(r0), r3.sub(r0), r3.add(r1), r3.sub(r1), near, far].map(plane_from_row),
+        }
+    }
+
+    #[inline(always)]
+    pub fn contains_point(self, point: Vector3<T>) -> bool {
+        self.planes
+            .iter()
+            .all(|ca| ca.signed_distance(point) >= T::zero())
+    }
+
+    /// Classifies the point, which is either inside or outside.
+    /// Points on the boundary are inside
+    #[inline(always)]
+    pub fn classify_point(self, point: Vector3<T>) -> Containment {
+        if self.contains_point(point) {
+            Containment::Inside
+        } else {
+            Containment::Outside
+        }
+    }
+
+    #[inline(always)]
+    pub fn classify_sphere(self, sphere: Sphere<T>) -> Containment {
+        let mut ret = Containment::Inside;
+        for plane in self.planes {
+            let distance = plane.signed_distance(sphere.center);
+            if distance < -sphere.radius {
+                return Containment::Outside;
+            }
+            if distance < sphere.radius {
+                ret = Containment::Intersecting;
+            }
+        }
+        ret
+    }
+
+    #[inline(always)]
+    pub fn classify_aabb(self, aabb: Aabb<T>) -> Containment {
+        let center = aabb.center();
+        let half_extents = aabb.half_extents();
+        let mut ret = Containment::Inside;
+        for plane in self.planes {
+            // the distance from the center to the corner furthest along the normal
+            let radius = plane.normal.abs().mul_inner(half_extents);
+            let distance = plane.signed_distance(center);
+            if distance < -radius {
+                return Containment::Outside;
+            }
+            if distance < radius {
+                ret = Containment::Intersecting;
+            }
+        }
+        ret
+    }
+
+    /// Gives the 8 corners, where the planes meet.
+    /// Bit 0 of the index picks right over left, bit 1 top over bottom
+    /// and bit 2 far over near, like [`Aabb::corners`].
+    /// With an infinite far plane, the far corners are not finite
+    #[inline(always)]
+    pub fn corners(self) -> [Vector3<T>; 8] {
+        std::array::from_fn(|cx| {
+            let [x, y, z] = [0, 1, 2].map(|cy| self.planes[cy * 2 + ((cx >> cy) & 1)]);
+            intersect_planes(x, y, z)
+        })
+    }
+
+    /// Gives the corners of the slice of the Frustum between start and end,
+    /// as fractions of the way from the near to the far plane,
+    /// ordered like [`Self::corners`]. For a perspective projection these
+    /// fractions are linear in view depth, so they can come from cascade split distances
+    #[inline(always)]
+    pub fn slice_corners(self, start: T, end: T) -> [Vector3<T>; 8] {
+        let corners = self.corners();
+        std::array::from_fn(|cx| {
+            let t = if cx & 4 == 0 { start } else { end };
+            corners[cx & 3].lerp(corners[cx | 4], t)
+        })
+    }
+}
+
+/// Gives the point where the three planes meet
+#[inline(always)]
+fn intersect_planes<T: Element>(p1: Plane<T>, p2: Plane<T>, p3: Plane<T>) -> Vector3<T> {
+    let n23 = p2.normal.mul_cross(p3.normal);
+    let n31 = p3.normal.mul_cross(p1.normal);
+    let n12 = p1.normal.mul_cross(p2.normal);
+    let denom = p1.normal.mul_inner(n23);
+    n23.mul_scalar(p1.d)
+        .add(n31.mul_scalar(p2.d))
+        .add(n12.mul_scalar(p3.d))
+        .div_scalar(-denom)
+}
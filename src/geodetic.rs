@@ -0,0 +1,139 @@
+//! Geodetic coordinates: latitude, longitude and height above an [`Ellipsoid`].
+//!
+//! Positions are Earth-centered and Earth-fixed, with z through the north pole
+//! and x through latitude and longitude 0, so y is at longitude 90 degrees east.
+//! This keeps z as the top axis, like [`Vector3::from_spherical`].
+//! Latitude is geodetic, measured from the equator to the normal of the surface,
+//! so it is the same as the spherical latitude only on a sphere.
+//!
+//! # Examples
+//!
+//! ```
+//! use glmath::angle::Deg;
+//! use glmath::geodetic::Ellipsoid;
+//! use glmath::vector::Vector3;
+//!
+//! let wgs84 = Ellipsoid::<f64>::wgs84();
+//! let position = Vector3::from_geodetic(Deg(51.5f64), Deg(-0.1f64), 35f64, wgs84);
+//! let (latitude, longitude, height) = position.to_geodetic(wgs84);
+//! assert!((Deg::from(latitude).0 - 51.5f64).abs() < 1e-9);
+//! assert!((Deg::from(longitude).0 + 0.1f64).abs() < 1e-9);
+//! assert!((height - 35f64).abs() < 1e-6);
+//!
+//! // on a sphere, latitude is 90 degrees minus the spherical theta
+//! let north = Vector3::from_geodetic(Deg(90f32), Deg(0f32), 0f32, Ellipsoid::sphere(2f32));
+//! assert!(north.dist(Vector3::from([0f32, 0f32, 2f32])) < 1e-6);
+//! ```
+
+use crate::angle::Rad;
+use crate::vector::{Vector2, Vector3};
+use crate::Element;
+
+/// An ellipsoid of revolution around the z axis
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ellipsoid<T: Element> {
+    /// The radius at the equator
+    pub semi_major: T,
+    /// The radius at the poles
+    pub semi_minor: T,
+}
+
+impl<T: Element> Ellipsoid<T> {
+    #[inline(always)]
+    pub fn new(semi_major: T, semi_minor: T) -> Self {
+        Self {
+            semi_major,
+            semi_minor,
+        }
+    }
+
+    /// The WGS 84 ellipsoid used by GPS, in meters
+    #[inline(always)]
+    pub fn wgs84() -> Self {
+        let semi_major = 6378137f64;
+        let flattening = 1f64 / 298.257223563f64;
+        Self::new(
+            num::cast(semi_major).unwrap(),
+            num::cast(semi_major * (1f64 - flattening)).unwrap(),
+        )
+    }
+
+    #[inline(always)]
+    pub fn sphere(radius: T) -> Self {
+        Self::new(radius, radius)
+    }
+
+    /// Gives the squared eccentricity, which is 0 for a sphere
+    #[inline(always)]
+    pub fn eccentricity_squared(self) -> T {
+        let ratio = self.semi_minor / self.semi_major;
+        T::one() - ratio * ratio
+    }
+
+    /// Gives the radius of curvature in the prime vertical at the latitude,
+    /// the distance along the normal of the surface to the z axis
+    #[inline(always)]
+    fn prime_vertical_radius(self, latitude_sin: T) -> T {
+        let e2 = self.eccentricity_squared();
+        self.semi_major / (T::one() - e2 * latitude_sin * latitude_sin).sqrt()
+    }
+}
+
+impl<T: Element> Vector3<T> {
+    /// Creates the position at the geodetic latitude and longitude
+    /// and the height above the Ellipsoid.
+    /// The angles are in radians, unless given as [`crate::angle::Deg`]
+    #[inline(always)]
+    pub fn from_geodetic(
+        latitude: impl Into<Rad<T>>,
+        longitude: impl Into<Rad<T>>,
+        height: T,
+        ellipsoid: Ellipsoid<T>,
+    ) -> Self {
+        let (latitude_sin, latitude_cos) = latitude.into().sin_cos();
+        let (longitude_sin, longitude_cos) = longitude.into().sin_cos();
+        let n = ellipsoid.prime_vertical_radius(latitude_sin);
+        let ratio = ellipsoid.semi_minor / ellipsoid.semi_major;
+        let horizontal = (n + height) * latitude_cos;
+
+        Self([
+            horizontal * longitude_cos,
+            horizontal * longitude_sin,
+            (n * ratio * ratio + height) * latitude_sin,
+        ])
+    }
+
+    /// Gives the geodetic (latitude, longitude, height) of the position,
+    /// in the conventions of [`Self::from_geodetic`].
+    /// Latitude is in [-pi/2, pi/2] and longitude in (-pi, pi],
+    /// and on the poles longitude is 0.
+    /// Uses Bowring's iteration, which converges to full precision for
+    /// positions outside the small region around the center of the Ellipsoid
+    /// where the closest point on the surface is not unique
+    #[inline(always)]
+    pub fn to_geodetic(self, ellipsoid: Ellipsoid<T>) -> (Rad<T>, Rad<T>, T) {
+        let a = ellipsoid.semi_major;
+        let b = ellipsoid.semi_minor;
+        let e2 = ellipsoid.eccentricity_squared();
+        let ep2 = (a * a) / (b * b) - T::one();
+        let (longitude, p) = Vector2([self[0], self[1]]).to_polar();
+        let z = self[2];
+
+        // iterates on the reduced latitude, starting from the one of the position
+        let mut beta = (a * z).atan2(b * p);
+        let mut latitude = T::zero();
+        for _ in 0..4 {
+            let (beta_sin, beta_cos) = beta.sin_cos();
+            latitude = (z + ep2 * b * beta_sin * beta_sin * beta_sin)
+                .atan2(p - e2 * a * beta_cos * beta_cos * beta_cos);
+            let (latitude_sin, latitude_cos) = latitude.sin_cos();
+            beta = (b * latitude_sin).atan2(a * latitude_cos);
+        }
+
+        let (latitude_sin, latitude_cos) = latitude.sin_cos();
+        let n = ellipsoid.prime_vertical_radius(latitude_sin);
+        let height = p * latitude_cos + z * latitude_sin - a * a / n;
+        (Rad(latitude), longitude, height)
+    }
+}
@@ -0,0 +1,1060 @@
+//! Geometric primitives built on Vectors.
+//!
+//! The 3D shapes are [`Ray`], [`Plane`], [`Sphere`], [`Aabb`], [`Obb`], [`Triangle`],
+//! [`Segment`] and [`Capsule`], and the 2D ones are [`Rect`], [`Circle`] and [`Line2`].
+//!
+//! Every shape has `transform`, `contains_point`, `closest_point` and `distance_to_point`.
+//! 3D shapes are transformed by an affine Matrix4x4 (like [`Matrix4x4::transform_point`]),
+//! 2D shapes by a Matrix3x3 working on homogeneous 2D points.
+//!
+//! Spheres, boxes, capsules, rects and circles are solid, so a point inside one
+//! is its own closest point, at a distance of 0. Shapes without a volume
+//! (rays, planes, triangles, segments and lines) contain the points within
+//! `sqrt(T::epsilon())` of them.
+//!
+//! The bounding volumes ([`Aabb`], [`Sphere`], [`Rect`] and [`Circle`]) can be merged
+//! with each other, and expanded to hold a point
+//!
+//! # Examples
+//!
+//! ```
+//! use glmath::geometry::{Aabb, Sphere};
+//! use glmath::vector::Vector3;
+//!
+//! let mut bounds = Aabb::empty();
+//! for point in [[1f32, 0f32, 0f32], [0f32, 2f32, 0f32], [-1f32, 0f32, 3f32]] {
+//!     bounds = bounds.expand(Vector3::from(point));
+//! }
+//! assert_eq!(bounds.min, Vector3::from([-1f32, 0f32, 0f32]));
+//! assert_eq!(bounds.max, Vector3::from([1f32, 2f32, 3f32]));
+//!
+//! let sphere = Sphere::new(Vector3::from([0f32, 0f32, 0f32]), 1f32);
+//! assert_eq!(sphere.distance_to_point(Vector3::from([0f32, 3f32, 0f32])), 2f32);
+//! ```
+
+use crate::matrix::{Matrix3x3, Matrix4x4};
+use crate::vector::{Vector2, Vector3};
+use crate::Element;
+
+/// The distance within which a point lies on a shape without a volume
+#[inline(always)]
+fn tolerance<T: Element>() -> T {
+    T::epsilon().sqrt()
+}
+
+#[inline(always)]
+fn half<T: Element>() -> T {
+    T::one() / (T::one() + T::one())
+}
+
+/// Gives the largest scale the Matrix applies along any axis, which is
+/// the longest column of the upper-left part. This bounds how much
+/// the Matrix can grow a radius
+#[inline(always)]
+fn max_scale3<T: Element>(matrix: Matrix4x4<T>) -> T {
+    (0..3)
+        .map(|cy| Vector3([matrix[0][cy], matrix[1][cy], matrix[2][cy]]).len())
+        .fold(T::zero(), T::max)
+}
+
+#[inline(always)]
+fn max_scale2<T: Element>(matrix: Matrix3x3<T>) -> T {
+    (0..2)
+        .map(|cy| Vector2([matrix[0][cy], matrix[1][cy]]).len())
+        .fold(T::zero(), T::max)
+}
+
+/// Transforms the 2D point (w = 1) by the homogeneous Matrix.
+/// Like an affine transform, the bottom row of the Matrix is not used
+#[inline(always)]
+fn transform_point2<T: Element>(matrix: Matrix3x3<T>, point: Vector2<T>) -> Vector2<T> {
+    transform_vector2(matrix, point).add(Vector2([matrix[0][2], matrix[1][2]]))
+}
+
+/// Transforms the 2D vector (w = 0) by the homogeneous Matrix
+#[inline(always)]
+fn transform_vector2<T: Element>(matrix: Matrix3x3<T>, vector: Vector2<T>) -> Vector2<T> {
+    Vector2([
+        matrix[0][0] * vector[0] + matrix[0][1] * vector[1],
+        matrix[1][0] * vector[0] + matrix[1][1] * vector[1],
+    ])
+}
+
+/// A half-line starting at origin, going along direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ray<T: Element> {
+    pub origin: Vector3<T>,
+    pub direction: Vector3<T>,
+}
+
+impl<T: Element> Ray<T> {
+    /// Creates the Ray. The direction doesn't have to be normalized,
+    /// but then `t` in [`Self::at`] isn't a distance
+    #[inline(always)]
+    pub fn new(origin: Vector3<T>, direction: Vector3<T>) -> Self {
+        Self { origin, direction }
+    }
+
+    /// Creates the Ray starting at from, with a normalized direction towards to
+    #[inline(always)]
+    pub fn from_points(from: Vector3<T>, to: Vector3<T>) -> Self {
+        Self::new(from, to.sub(from).normalize_or_zero())
+    }
+
+    /// Gives the point at `origin + direction * t`
+    #[inline(always)]
+    pub fn at(self, t: T) -> Vector3<T> {
+        self.origin.add(self.direction.mul_scalar(t))
+    }
+
+    /// Transforms the Ray by the affine Matrix
+    #[inline(always)]
+    pub fn transform(self, matrix: Matrix4x4<T>) -> Self {
+        Self::new(
+            matrix.transform_point(self.origin),
+            matrix.transform_vector(self.direction),
+        )
+    }
+
+    /// Gives the point of the Ray closest to the given point
+    #[inline(always)]
+    pub fn closest_point(self, point: Vector3<T>) -> Vector3<T> {
+        let len_squared = self.direction.length_squared();
+        if len_squared == T::zero() {
+            return self.origin;
+        }
+        let t = point.sub(self.origin).mul_inner(self.direction) / len_squared;
+        self.at(t.max(T::zero()))
+    }
+
+    /// Gives the distance between the Ray and the point
+    #[inline(always)]
+    pub fn distance_to_point(self, point: Vector3<T>) -> T {
+        self.closest_point(point).dist(point)
+    }
+
+    /// Checks if the point is on the Ray
+    #[inline(always)]
+    pub fn contains_point(self, point: Vector3<T>) -> bool {
+        self.distance_to_point(point) <= tolerance()
+    }
+}
+
+/// A Plane holding the points p where `normal.mul_inner(p) + d == 0`.
+/// The normal is unit length and points to the positive side
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plane<T: Element> {
+    pub normal: Vector3<T>,
+    pub d: T,
+}
+
+impl<T: Element> Plane<T> {
+    /// Creates the Plane `normal.mul_inner(p) + d == 0`,
+    /// scaling both so the normal is unit length.
+    /// The normal must not be 0
+    #[inline(always)]
+    pub fn new(normal: Vector3<T>, d: T) -> Self {
+        let len = normal.len();
+        Self {
+            normal: normal.div_scalar(len),
+            d: d / len,
+        }
+    }
+
+    /// Creates the Plane through the point, facing the normal.
+    /// The normal doesn't have to be normalized
+    #[inline(always)]
+    pub fn from_point_normal(point: Vector3<T>, normal: Vector3<T>) -> Self {
+        let normal = normal.normalize_or_zero();
+        Self {
+            normal,
+            d: -normal.mul_inner(point),
+        }
+    }
+
+    /// Creates the Plane through the three points, with the normal facing
+    /// the side they are counter-clockwise from.
+    /// Gives None if the points are on a line
+    #[inline(always)]
+    pub fn from_points(a: Vector3<T>, b: Vector3<T>, c: Vector3<T>) -> Option<Self> {
+        let normal = b.sub(a).mul_cross(c.sub(a)).try_normalize()?;
+        Some(Self {
+            normal,
+            d: -normal.mul_inner(a),
+        })
+    }
+
+    /// Gives the distance of the point to the Plane,
+    /// which is negative behind the Plane
+    #[inline(always)]
+    pub fn signed_distance(self, point: Vector3<T>) -> T {
+        self.normal.mul_inner(point) + self.d
+    }
+
+    /// Transforms the Plane by the affine Matrix.
+    /// The normal is transformed by the cofactor Matrix,
+    /// so this works for non-uniform scales and mirroring
+    #[inline(always)]
+    pub fn transform(self, matrix: Matrix4x4<T>) -> Self {
+        let point = matrix.transform_point(self.normal.mul_scalar(-self.d));
+        let m = matrix.to_matrix3x3();
+        // the rows of the cofactor Matrix, which is det * inverse transpose
+        let cofactor = Matrix3x3([
+            m[1].mul_cross(m[2]),
+            m[2].mul_cross(m[0]),
+            m[0].mul_cross(m[1]),
+        ]);
+        let mut normal = cofactor.mul_vector(self.normal);
+        if m[0].mul_inner(cofactor[0]) < T::zero() {
+            normal = normal.mul_scalar(-T::one());
+        }
+        Self::from_point_normal(point, normal)
+    }
+
+    /// Gives the point of the Plane closest to the given point
+    #[inline(always)]
+    pub fn closest_point(self, point: Vector3<T>) -> Vector3<T> {
+        point.sub(self.normal.mul_scalar(self.signed_distance(point)))
+    }
+
+    /// Gives the distance between the Plane and the point
+    #[inline(always)]
+    pub fn distance_to_point(self, point: Vector3<T>) -> T {
+        self.signed_distance(point).abs()
+    }
+
+    /// Checks if the point is on the Plane
+    #[inline(always)]
+    pub fn contains_point(self, point: Vector3<T>) -> bool {
+        self.distance_to_point(point) <= tolerance()
+    }
+}
+
+/// A solid Sphere
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sphere<T: Element> {
+    pub center: Vector3<T>,
+    pub radius: T,
+}
+
+impl<T: Element> Sphere<T> {
+    #[inline(always)]
+    pub fn new(center: Vector3<T>, radius: T) -> Self {
+        Self { center, radius }
+    }
+
+    /// Creates a Sphere holding all the points, centered on their bounding box.
+    /// This is not the smallest such Sphere, but close to it.
+    /// Gives None for no points
+    #[inline(always)]
+    pub fn from_points(points: &[Vector3<T>]) -> Option<Self> {
+        let bounds = Aabb::from_points(points);
+        if bounds.is_empty() {
+            return None;
+        }
+        let center = bounds.center();
+        let radius_squared = points
+            .iter()
+            .map(|ca| ca.distance_squared(center))
+            .fold(T::zero(), T::max);
+        Some(Self::new(center, radius_squared.sqrt()))
+    }
+
+    /// Transforms the Sphere by the affine Matrix.
+    /// With a non-uniform scale, the radius grows by the largest scale,
+    /// so the result still holds the transformed Sphere
+    #[inline(always)]
+    pub fn transform(self, matrix: Matrix4x4<T>) -> Self {
+        Self::new(
+            matrix.transform_point(self.center),
+            self.radius * max_scale3(matrix),
+        )
+    }
+
+    #[inline(always)]
+    pub fn contains_point(self, point: Vector3<T>) -> bool {
+        self.center.distance_squared(point) <= self.radius * self.radius
+    }
+
+    /// Gives the point of the Sphere closest to the given point
+    #[inline(always)]
+    pub fn closest_point(self, point: Vector3<T>) -> Vector3<T> {
+        let offset = point.sub(self.center);
+        let len = offset.len();
+        if len <= self.radius {
+            return point;
+        }
+        self.center.add(offset.mul_scalar(self.radius / len))
+    }
+
+    #[inline(always)]
+    pub fn distance_to_point(self, point: Vector3<T>) -> T {
+        (self.center.dist(point) - self.radius).max(T::zero())
+    }
+
+    /// Gives the smallest Sphere holding both Spheres
+    #[inline(always)]
+    pub fn merge(self, other: Self) -> Self {
+        let offset = other.center.sub(self.center);
+        let len = offset.len();
+        if len + other.radius <= self.radius {
+            return self;
+        }
+        if len + self.radius <= other.radius {
+            return other;
+        }
+        let radius = (len + self.radius + other.radius) * half();
+        Self::new(
+            self.center
+                .add(offset.mul_scalar((radius - self.radius) / len)),
+            radius,
+        )
+    }
+
+    /// Gives the smallest Sphere holding this Sphere and the point
+    #[inline(always)]
+    pub fn expand(self, point: Vector3<T>) -> Self {
+        self.merge(Self::new(point, T::zero()))
+    }
+
+    /// Gives the bounding box of the Sphere
+    #[inline(always)]
+    pub fn to_aabb(self) -> Aabb<T> {
+        let radius = Vector3([self.radius; 3]);
+        Aabb {
+            min: self.center.sub(radius),
+            max: self.center.add(radius),
+        }
+    }
+}
+
+/// A solid axis-aligned bounding box.
+/// A box with any of min greater than max is empty
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Aabb<T: Element> {
+    pub min: Vector3<T>,
+    pub max: Vector3<T>,
+}
+
+impl<T: Element> Aabb<T> {
+    /// Creates the box spanning the two corners, in any order
+    #[inline(always)]
+    pub fn new(a: Vector3<T>, b: Vector3<T>) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    #[inline(always)]
+    pub fn from_center_half_extents(center: Vector3<T>, half_extents: Vector3<T>) -> Self {
+        Self::new(center.sub(half_extents), center.add(half_extents))
+    }
+
+    /// Gives the empty box, from infinity to -infinity.
+    /// Merging with it or expanding it gives the other box or the point
+    #[inline(always)]
+    pub fn empty() -> Self {
+        Self {
+            min: Vector3([T::infinity(); 3]),
+            max: Vector3([T::neg_infinity(); 3]),
+        }
+    }
+
+    /// Gives the smallest box holding all the points,
+    /// which is empty for no points
+    #[inline(always)]
+    pub fn from_points(points: &[Vector3<T>]) -> Self {
+        points
+            .iter()
+            .fold(Self::empty(), |bounds, ca| bounds.expand(*ca))
+    }
+
+    #[inline(always)]
+    pub fn is_empty(self) -> bool {
+        (0..3).any(|cx| self.min[cx] > self.max[cx])
+    }
+
+    #[inline(always)]
+    pub fn center(self) -> Vector3<T> {
+        self.min.add(self.max).mul_scalar(half())
+    }
+
+    #[inline(always)]
+    pub fn half_extents(self) -> Vector3<T> {
+        self.max.sub(self.min).mul_scalar(half())
+    }
+
+    #[inline(always)]
+    pub fn size(self) -> Vector3<T> {
+        self.max.sub(self.min)
+    }
+
+    /// Gives the 8 corners. Bit 0 of the index picks max for x,
+    /// bit 1 for y and bit 2 for z
+    #[inline(always)]
+    pub fn corners(self) -> [Vector3<T>; 8] {
+        std::array::from_fn(|cx| {
+            Vector3(std::array::from_fn(|cy| {
+                if cx & (1 << cy) == 0 {
+                    self.min[cy]
+                } else {
+                    self.max[cy]
+                }
+            }))
+        })
+    }
+
+    /// Gives the bounding box of the transformed box
+    #[inline(always)]
+    pub fn transform(self, matrix: Matrix4x4<T>) -> Self {
+        if self.is_empty() {
+            return self;
+        }
+        let m = matrix.to_matrix3x3();
+        let half_extents = Matrix3x3(m.0.map(|row| row.abs())).mul_vector(self.half_extents());
+        Self::from_center_half_extents(matrix.transform_point(self.center()), half_extents)
+    }
+
+    #[inline(always)]
+    pub fn contains_point(self, point: Vector3<T>) -> bool {
+        (0..3).all(|cx| self.min[cx] <= point[cx] && point[cx] <= self.max[cx])
+    }
+
+    /// Gives the point of the box closest to the given point
+    #[inline(always)]
+    pub fn closest_point(self, point: Vector3<T>) -> Vector3<T> {
+        point.clamp(self.min, self.max)
+    }
+
+    #[inline(always)]
+    pub fn distance_to_point(self, point: Vector3<T>) -> T {
+        self.closest_point(point).dist(point)
+    }
+
+    /// Gives the smallest box holding both boxes
+    #[inline(always)]
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Gives the smallest box holding this box and the point
+    #[inline(always)]
+    pub fn expand(self, point: Vector3<T>) -> Self {
+        Self {
+            min: self.min.min(point),
+            max: self.max.max(point),
+        }
+    }
+}
+
+/// A solid oriented bounding box.
+/// The axes are unit length and perpendicular to each other
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Obb<T: Element> {
+    pub center: Vector3<T>,
+    pub axes: [Vector3<T>; 3],
+    pub half_extents: Vector3<T>,
+}
+
+impl<T: Element> Obb<T> {
+    #[inline(always)]
+    pub fn new(center: Vector3<T>, axes: [Vector3<T>; 3], half_extents: Vector3<T>) -> Self {
+        Self {
+            center,
+            axes,
+            half_extents,
+        }
+    }
+
+    #[inline(always)]
+    pub fn from_aabb(aabb: Aabb<T>) -> Self {
+        Self::new(aabb.center(), Matrix3x3::ident().0, aabb.half_extents())
+    }
+
+    /// Gives the point in the box's own coordinates,
+    /// relative to the center and along the axes
+    #[inline(always)]
+    pub fn to_local(self, point: Vector3<T>) -> Vector3<T> {
+        let offset = point.sub(self.center);
+        Vector3(self.axes.map(|axis| axis.mul_inner(offset)))
+    }
+
+    /// Gives the point from the box's own coordinates
+    #[inline(always)]
+    pub fn from_local(self, local: Vector3<T>) -> Vector3<T> {
+        (0..3).fold(self.center, |point, cx| {
+            point.add(self.axes[cx].mul_scalar(local[cx]))
+        })
+    }
+
+    /// Gives the 8 corners, ordered like [`Aabb::corners`]
+    #[inline(always)]
+    pub fn corners(self) -> [Vector3<T>; 8] {
+        Aabb::from_center_half_extents(Vector3([T::zero(); 3]), self.half_extents)
+            .corners()
+            .map(|ca| self.from_local(ca))
+    }
+
+    /// Transforms the box by the affine Matrix.
+    /// The Matrix must not shear the box, or it won't be a box anymore
+    #[inline(always)]
+    pub fn transform(self, matrix: Matrix4x4<T>) -> Self {
+        let mut ret = self;
+        ret.center = matrix.transform_point(self.center);
+        for cx in 0..3 {
+            let axis = matrix.transform_vector(self.axes[cx].mul_scalar(self.half_extents[cx]));
+            ret.half_extents[cx] = axis.len();
+            ret.axes[cx] = axis.normalize_or_zero();
+        }
+        ret
+    }
+
+    #[inline(always)]
+    pub fn contains_point(self, point: Vector3<T>) -> bool {
+        let local = self.to_local(point);
+        (0..3).all(|cx| local[cx].abs() <= self.half_extents[cx])
+    }
+
+    /// Gives the point of the box closest to the given point
+    #[inline(always)]
+    pub fn closest_point(self, point: Vector3<T>) -> Vector3<T> {
+        let local = self.to_local(point);
+        self.from_local(local.clamp(self.half_extents.mul_scalar(-T::one()), self.half_extents))
+    }
+
+    #[inline(always)]
+    pub fn distance_to_point(self, point: Vector3<T>) -> T {
+        self.closest_point(point).dist(point)
+    }
+
+    /// Gives the axis-aligned bounding box of the box
+    #[inline(always)]
+    pub fn to_aabb(self) -> Aabb<T> {
+        let half_extents = (0..3).fold(Vector3([T::zero(); 3]), |sum, cx| {
+            sum.add(self.axes[cx].abs().mul_scalar(self.half_extents[cx]))
+        });
+        Aabb::from_center_half_extents(self.center, half_extents)
+    }
+}
+
+/// A Triangle with the corners a, b and c
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triangle<T: Element> {
+    pub a: Vector3<T>,
+    pub b: Vector3<T>,
+    pub c: Vector3<T>,
+}
+
+impl<T: Element> Triangle<T> {
+    #[inline(always)]
+    pub fn new(a: Vector3<T>, b: Vector3<T>, c: Vector3<T>) -> Self {
+        Self { a, b, c }
+    }
+
+    /// Transforms every corner by the affine Matrix
+    #[inline(always)]
+    pub fn transform(self, matrix: Matrix4x4<T>) -> Self {
+        Self::new(
+            matrix.transform_point(self.a),
+            matrix.transform_point(self.b),
+            matrix.transform_point(self.c),
+        )
+    }
+
+    /// Gives the point of the Triangle closest to the given point.
+    /// For a degenerate Triangle, this is the closest point of its edges
+    #[inline(always)]
+    pub fn closest_point(self, point: Vector3<T>) -> Vector3<T> {
+        let Self { a, b, c } = self;
+        let ab = b.sub(a);
+        let ac = c.sub(a);
+        if ab.mul_cross(ac).length_squared() == T::zero() {
+            return [
+                Segment::new(a, b).closest_point(point),
+                Segment::new(b, c).closest_point(point),
+                Segment::new(c, a).closest_point(point),
+            ]
+            .into_iter()
+            .fold(a, |closest, ca| {
+                if ca.distance_squared(point) < closest.distance_squared(point) {
+                    ca
+                } else {
+                    closest
+                }
+            });
+        }
+
+        // Ericson, Real-Time Collision Detection 5.1.5:
+        // find the Voronoi region of the point, then project onto it
+        let ap = point.sub(a);
+        let d1 = ab.mul_inner(ap);
+        let d2 = ac.mul_inner(ap);
+        if d1 <= T::zero() && d2 <= T::zero() {
+            return a;
+        }
+        let bp = point.sub(b);
+        let d3 = ab.mul_inner(bp);
+        let d4 = ac.mul_inner(bp);
+        if d3 >= T::zero() && d4 <= d3 {
+            return b;
+        }
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= T::zero() && d1 >= T::zero() && d3 <= T::zero() {
+            return a.add(ab.mul_scalar(d1 / (d1 - d3)));
+        }
+        let cp = point.sub(c);
+        let d5 = ab.mul_inner(cp);
+        let d6 = ac.mul_inner(cp);
+        if d6 >= T::zero() && d5 <= d6 {
+            return c;
+        }
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= T::zero() && d2 >= T::zero() && d6 <= T::zero() {
+            return a.add(ac.mul_scalar(d2 / (d2 - d6)));
+        }
+        let va = d3 * d6 - d5 * d4;
+        if va <= T::zero() && d4 - d3 >= T::zero() && d5 - d6 >= T::zero() {
+            let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+            return b.add(c.sub(b).mul_scalar(w));
+        }
+        let denom = T::one() / (va + vb + vc);
+        a.add(ab.mul_scalar(vb * denom))
+            .add(ac.mul_scalar(vc * denom))
+    }
+
+    #[inline(always)]
+    pub fn distance_to_point(self, point: Vector3<T>) -> T {
+        self.closest_point(point).dist(point)
+    }
+
+    /// Checks if the point is on the Triangle
+    #[inline(always)]
+    pub fn contains_point(self, point: Vector3<T>) -> bool {
+        self.distance_to_point(point) <= tolerance()
+    }
+
+    /// Gives the bounding box of the Triangle
+    #[inline(always)]
+    pub fn to_aabb(self) -> Aabb<T> {
+        Aabb::new(self.a, self.b).expand(self.c)
+    }
+}
+
+/// A line Segment between start and end
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment<T: Element> {
+    pub start: Vector3<T>,
+    pub end: Vector3<T>,
+}
+
+impl<T: Element> Segment<T> {
+    #[inline(always)]
+    pub fn new(start: Vector3<T>, end: Vector3<T>) -> Self {
+        Self { start, end }
+    }
+
+    /// Gives the Vector from start to end
+    #[inline(always)]
+    pub fn direction(self) -> Vector3<T> {
+        self.end.sub(self.start)
+    }
+
+    #[inline(always)]
+    pub fn length(self) -> T {
+        self.start.dist(self.end)
+    }
+
+    /// Gives the point at `t` from start (0) to end (1)
+    #[inline(always)]
+    pub fn at(self, t: T) -> Vector3<T> {
+        self.start.lerp(self.end, t)
+    }
+
+    /// Transforms both ends by the affine Matrix
+    #[inline(always)]
+    pub fn transform(self, matrix: Matrix4x4<T>) -> Self {
+        Self::new(
+            matrix.transform_point(self.start),
+            matrix.transform_point(self.end),
+        )
+    }
+
+    /// Gives the `t` of the point of the Segment closest to the given point
+    #[inline(always)]
+    pub fn closest_t(self, point: Vector3<T>) -> T {
+        let direction = self.direction();
+        let len_squared = direction.length_squared();
+        if len_squared == T::zero() {
+            return T::zero();
+        }
+        (point.sub(self.start).mul_inner(direction) / len_squared)
+            .max(T::zero())
+            .min(T::one())
+    }
+
+    /// Gives the point of the Segment closest to the given point
+    #[inline(always)]
+    pub fn closest_point(self, point: Vector3<T>) -> Vector3<T> {
+        self.at(self.closest_t(point))
+    }
+
+    #[inline(always)]
+    pub fn distance_to_point(self, point: Vector3<T>) -> T {
+        self.closest_point(point).dist(point)
+    }
+
+    /// Checks if the point is on the Segment
+    #[inline(always)]
+    pub fn contains_point(self, point: Vector3<T>) -> bool {
+        self.distance_to_point(point) <= tolerance()
+    }
+
+    /// Gives the bounding box of the Segment
+    #[inline(always)]
+    pub fn to_aabb(self) -> Aabb<T> {
+        Aabb::new(self.start, self.end)
+    }
+}
+
+/// A solid Capsule: every point within radius of the Segment from start to end
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capsule<T: Element> {
+    pub start: Vector3<T>,
+    pub end: Vector3<T>,
+    pub radius: T,
+}
+
+impl<T: Element> Capsule<T> {
+    #[inline(always)]
+    pub fn new(start: Vector3<T>, end: Vector3<T>, radius: T) -> Self {
+        Self { start, end, radius }
+    }
+
+    /// Gives the Segment at the core of the Capsule
+    #[inline(always)]
+    pub fn segment(self) -> Segment<T> {
+        Segment::new(self.start, self.end)
+    }
+
+    /// Transforms the Capsule by the affine Matrix.
+    /// Like [`Sphere::transform`], the radius grows by the largest scale
+    #[inline(always)]
+    pub fn transform(self, matrix: Matrix4x4<T>) -> Self {
+        Self::new(
+            matrix.transform_point(self.start),
+            matrix.transform_point(self.end),
+            self.radius * max_scale3(matrix),
+        )
+    }
+
+    #[inline(always)]
+    pub fn contains_point(self, point: Vector3<T>) -> bool {
+        self.segment().closest_point(point).distance_squared(point) <= self.radius * self.radius
+    }
+
+    /// Gives the point of the Capsule closest to the given point
+    #[inline(always)]
+    pub fn closest_point(self, point: Vector3<T>) -> Vector3<T> {
+        Sphere::new(self.segment().closest_point(point), self.radius).closest_point(point)
+    }
+
+    #[inline(always)]
+    pub fn distance_to_point(self, point: Vector3<T>) -> T {
+        (self.segment().distance_to_point(point) - self.radius).max(T::zero())
+    }
+
+    /// Gives the bounding box of the Capsule
+    #[inline(always)]
+    pub fn to_aabb(self) -> Aabb<T> {
+        Sphere::new(self.start, self.radius)
+            .to_aabb()
+            .merge(Sphere::new(self.end, self.radius).to_aabb())
+    }
+}
+
+/// A solid axis-aligned 2D rectangle.
+/// A Rect with any of min greater than max is empty
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect<T: Element> {
+    pub min: Vector2<T>,
+    pub max: Vector2<T>,
+}
+
+impl<T: Element> Rect<T> {
+    /// Creates the Rect spanning the two corners, in any order
+    #[inline(always)]
+    pub fn new(a: Vector2<T>, b: Vector2<T>) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    #[inline(always)]
+    pub fn from_center_half_extents(center: Vector2<T>, half_extents: Vector2<T>) -> Self {
+        Self::new(center.sub(half_extents), center.add(half_extents))
+    }
+
+    /// Gives the empty Rect, see [`Aabb::empty`]
+    #[inline(always)]
+    pub fn empty() -> Self {
+        Self {
+            min: Vector2([T::infinity(); 2]),
+            max: Vector2([T::neg_infinity(); 2]),
+        }
+    }
+
+    /// Gives the smallest Rect holding all the points,
+    /// which is empty for no points
+    #[inline(always)]
+    pub fn from_points(points: &[Vector2<T>]) -> Self {
+        points
+            .iter()
+            .fold(Self::empty(), |bounds, ca| bounds.expand(*ca))
+    }
+
+    #[inline(always)]
+    pub fn is_empty(self) -> bool {
+        (0..2).any(|cx| self.min[cx] > self.max[cx])
+    }
+
+    #[inline(always)]
+    pub fn center(self) -> Vector2<T> {
+        self.min.add(self.max).mul_scalar(half())
+    }
+
+    #[inline(always)]
+    pub fn half_extents(self) -> Vector2<T> {
+        self.max.sub(self.min).mul_scalar(half())
+    }
+
+    #[inline(always)]
+    pub fn size(self) -> Vector2<T> {
+        self.max.sub(self.min)
+    }
+
+    /// Gives the 4 corners, ordered like [`Aabb::corners`]
+    #[inline(always)]
+    pub fn corners(self) -> [Vector2<T>; 4] {
+        [
+            self.min,
+            Vector2([self.max[0], self.min[1]]),
+            Vector2([self.min[0], self.max[1]]),
+            self.max,
+        ]
+    }
+
+    /// Gives the bounding Rect of the Rect transformed by the homogeneous Matrix
+    #[inline(always)]
+    pub fn transform(self, matrix: Matrix3x3<T>) -> Self {
+        if self.is_empty() {
+            return self;
+        }
+        let half_extents = self.half_extents();
+        let half_extents = Vector2([0, 1].map(|cx| {
+            matrix[cx][0].abs() * half_extents[0] + matrix[cx][1].abs() * half_extents[1]
+        }));
+        Self::from_center_half_extents(transform_point2(matrix, self.center()), half_extents)
+    }
+
+    #[inline(always)]
+    pub fn contains_point(self, point: Vector2<T>) -> bool {
+        (0..2).all(|cx| self.min[cx] <= point[cx] && point[cx] <= self.max[cx])
+    }
+
+    /// Gives the point of the Rect closest to the given point
+    #[inline(always)]
+    pub fn closest_point(self, point: Vector2<T>) -> Vector2<T> {
+        point.clamp(self.min, self.max)
+    }
+
+    #[inline(always)]
+    pub fn distance_to_point(self, point: Vector2<T>) -> T {
+        self.closest_point(point).dist(point)
+    }
+
+    /// Gives the smallest Rect holding both Rects
+    #[inline(always)]
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Gives the smallest Rect holding this Rect and the point
+    #[inline(always)]
+    pub fn expand(self, point: Vector2<T>) -> Self {
+        Self {
+            min: self.min.min(point),
+            max: self.max.max(point),
+        }
+    }
+}
+
+/// A solid Circle
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Circle<T: Element> {
+    pub center: Vector2<T>,
+    pub radius: T,
+}
+
+impl<T: Element> Circle<T> {
+    #[inline(always)]
+    pub fn new(center: Vector2<T>, radius: T) -> Self {
+        Self { center, radius }
+    }
+
+    /// Creates a Circle holding all the points, see [`Sphere::from_points`].
+    /// Gives None for no points
+    #[inline(always)]
+    pub fn from_points(points: &[Vector2<T>]) -> Option<Self> {
+        let bounds = Rect::from_points(points);
+        if bounds.is_empty() {
+            return None;
+        }
+        let center = bounds.center();
+        let radius_squared = points
+            .iter()
+            .map(|ca| ca.distance_squared(center))
+            .fold(T::zero(), T::max);
+        Some(Self::new(center, radius_squared.sqrt()))
+    }
+
+    /// Transforms the Circle by the homogeneous Matrix.
+    /// Like [`Sphere::transform`], the radius grows by the largest scale
+    #[inline(always)]
+    pub fn transform(self, matrix: Matrix3x3<T>) -> Self {
+        Self::new(
+            transform_point2(matrix, self.center),
+            self.radius * max_scale2(matrix),
+        )
+    }
+
+    #[inline(always)]
+    pub fn contains_point(self, point: Vector2<T>) -> bool {
+        self.center.distance_squared(point) <= self.radius * self.radius
+    }
+
+    /// Gives the point of the Circle closest to the given point
+    #[inline(always)]
+    pub fn closest_point(self, point: Vector2<T>) -> Vector2<T> {
+        let offset = point.sub(self.center);
+        let len = offset.len();
+        if len <= self.radius {
+            return point;
+        }
+        self.center.add(offset.mul_scalar(self.radius / len))
+    }
+
+    #[inline(always)]
+    pub fn distance_to_point(self, point: Vector2<T>) -> T {
+        (self.center.dist(point) - self.radius).max(T::zero())
+    }
+
+    /// Gives the smallest Circle holding both Circles
+    #[inline(always)]
+    pub fn merge(self, other: Self) -> Self {
+        let offset = other.center.sub(self.center);
+        let len = offset.len();
+        if len + other.radius <= self.radius {
+            return self;
+        }
+        if len + self.radius <= other.radius {
+            return other;
+        }
+        let radius = (len + self.radius + other.radius) * half();
+        Self::new(
+            self.center
+                .add(offset.mul_scalar((radius - self.radius) / len)),
+            radius,
+        )
+    }
+
+    /// Gives the smallest Circle holding this Circle and the point
+    #[inline(always)]
+    pub fn expand(self, point: Vector2<T>) -> Self {
+        self.merge(Self::new(point, T::zero()))
+    }
+
+    /// Gives the bounding Rect of the Circle
+    #[inline(always)]
+    pub fn to_rect(self) -> Rect<T> {
+        Rect::from_center_half_extents(self.center, Vector2([self.radius; 2]))
+    }
+}
+
+/// An infinite 2D line through point, going along direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line2<T: Element> {
+    pub point: Vector2<T>,
+    pub direction: Vector2<T>,
+}
+
+impl<T: Element> Line2<T> {
+    /// Creates the line. The direction doesn't have to be normalized
+    #[inline(always)]
+    pub fn new(point: Vector2<T>, direction: Vector2<T>) -> Self {
+        Self { point, direction }
+    }
+
+    /// Creates the line through both points, with a normalized direction from a to b
+    #[inline(always)]
+    pub fn from_points(a: Vector2<T>, b: Vector2<T>) -> Self {
+        Self::new(a, b.sub(a).normalize_or_zero())
+    }
+
+    /// Gives the distance of the point to the line, which is
+    /// positive on the left side (counter-clockwise from the direction)
+    #[inline(always)]
+    pub fn signed_distance(self, point: Vector2<T>) -> T {
+        let normal = self.direction.perp1().normalize_or_zero();
+        point.sub(self.point).mul_inner(normal)
+    }
+
+    /// Transforms the line by the homogeneous Matrix
+    #[inline(always)]
+    pub fn transform(self, matrix: Matrix3x3<T>) -> Self {
+        Self::new(
+            transform_point2(matrix, self.point),
+            transform_vector2(matrix, self.direction),
+        )
+    }
+
+    /// Gives the point of the line closest to the given point
+    #[inline(always)]
+    pub fn closest_point(self, point: Vector2<T>) -> Vector2<T> {
+        let len_squared = self.direction.length_squared();
+        if len_squared == T::zero() {
+            return self.point;
+        }
+        let t = point.sub(self.point).mul_inner(self.direction) / len_squared;
+        self.point.add(self.direction.mul_scalar(t))
+    }
+
+    #[inline(always)]
+    pub fn distance_to_point(self, point: Vector2<T>) -> T {
+        self.closest_point(point).dist(point)
+    }
+
+    /// Checks if the point is on the line
+    #[inline(always)]
+    pub fn contains_point(self, point: Vector2<T>) -> bool {
+        self.distance_to_point(point) <= tolerance()
+    }
+}
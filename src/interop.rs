@@ -0,0 +1,438 @@
+//! Conversions to and from the types of other math crates.
+//!
+//! Each crate has its own feature (`mint`, `glam`, `nalgebra` and `cgmath`),
+//! which adds `From` impls in both directions for the Vectors, Matrices and Quaternions.
+//!
+//! The conversions keep the meaning of a value, not its memory layout.
+//! glmath Matrices are row-major, while glam, nalgebra and cgmath store columns,
+//! so element `m[r][c]` always ends up in row r and column c of the other type.
+//! mint has both, so `RowMatrix` and `ColumnMatrix` are supported
+
+#[cfg(feature = "mint")]
+mod impl_mint {
+    use crate::matrix::{Matrix2x2, Matrix3x3, Matrix4x4};
+    use crate::vector::{Vector2, Vector3, Vector4};
+    use crate::Element;
+
+    macro_rules! IMPL_MINT {
+        ($(($n:expr, $vec:ident, $mint_vec:ident, $mat:ident, $row_mat:ident, $col_mat:ident)),*) => {
+            $(
+                impl<T: Element> From<mint::$mint_vec<T>> for $vec<T> {
+                    #[inline(always)]
+                    fn from(value: mint::$mint_vec<T>) -> Self {
+                        $vec(value.into())
+                    }
+                }
+
+                impl<T: Element> From<$vec<T>> for mint::$mint_vec<T> {
+                    #[inline(always)]
+                    fn from(value: $vec<T>) -> Self {
+                        value.0.into()
+                    }
+                }
+
+                impl<T: Element> mint::IntoMint for $vec<T> {
+                    type MintType = mint::$mint_vec<T>;
+                }
+
+                impl<T: Element> From<mint::$row_mat<T>> for $mat<T> {
+                    #[inline(always)]
+                    fn from(value: mint::$row_mat<T>) -> Self {
+                        $mat::from_rows(<[[T; $n]; $n]>::from(value).map($vec))
+                    }
+                }
+
+                impl<T: Element> From<$mat<T>> for mint::$row_mat<T> {
+                    #[inline(always)]
+                    fn from(value: $mat<T>) -> Self {
+                        value.to_rows_array_2d().into()
+                    }
+                }
+
+                impl<T: Element> From<mint::$col_mat<T>> for $mat<T> {
+                    #[inline(always)]
+                    fn from(value: mint::$col_mat<T>) -> Self {
+                        $mat::from_cols(<[[T; $n]; $n]>::from(value).map($vec))
+                    }
+                }
+
+                impl<T: Element> From<$mat<T>> for mint::$col_mat<T> {
+                    #[inline(always)]
+                    fn from(value: $mat<T>) -> Self {
+                        value.to_cols_array_2d().into()
+                    }
+                }
+
+                /// Converts into the `RowMatrix`, which has the same storage
+                impl<T: Element> mint::IntoMint for $mat<T> {
+                    type MintType = mint::$row_mat<T>;
+                }
+            )*
+        };
+    }
+
+    IMPL_MINT!(
+        (2, Vector2, Vector2, Matrix2x2, RowMatrix2, ColumnMatrix2),
+        (3, Vector3, Vector3, Matrix3x3, RowMatrix3, ColumnMatrix3),
+        (4, Vector4, Vector4, Matrix4x4, RowMatrix4, ColumnMatrix4)
+    );
+
+    impl<T: Element> From<mint::Point2<T>> for Vector2<T> {
+        #[inline(always)]
+        fn from(value: mint::Point2<T>) -> Self {
+            Vector2(value.into())
+        }
+    }
+
+    impl<T: Element> From<Vector2<T>> for mint::Point2<T> {
+        #[inline(always)]
+        fn from(value: Vector2<T>) -> Self {
+            value.0.into()
+        }
+    }
+
+    impl<T: Element> From<mint::Point3<T>> for Vector3<T> {
+        #[inline(always)]
+        fn from(value: mint::Point3<T>) -> Self {
+            Vector3(value.into())
+        }
+    }
+
+    impl<T: Element> From<Vector3<T>> for mint::Point3<T> {
+        #[inline(always)]
+        fn from(value: Vector3<T>) -> Self {
+            value.0.into()
+        }
+    }
+
+    #[cfg(feature = "quaternions")]
+    impl<T: Element> From<mint::Quaternion<T>> for crate::quaternions::Quaternion<T> {
+        #[inline(always)]
+        fn from(value: mint::Quaternion<T>) -> Self {
+            Self::from_scalar_vector(value.s, Vector3(value.v.into()))
+        }
+    }
+
+    #[cfg(feature = "quaternions")]
+    impl<T: Element> From<crate::quaternions::Quaternion<T>> for mint::Quaternion<T> {
+        #[inline(always)]
+        fn from(value: crate::quaternions::Quaternion<T>) -> Self {
+            mint::Quaternion {
+                v: value.vector().0.into(),
+                s: value.scalar(),
+            }
+        }
+    }
+
+    #[cfg(feature = "quaternions")]
+    impl<T: Element> mint::IntoMint for crate::quaternions::Quaternion<T> {
+        type MintType = mint::Quaternion<T>;
+    }
+}
+
+#[cfg(feature = "glam")]
+mod impl_glam {
+    use crate::matrix::{Matrix2x2, Matrix3x3, Matrix4x4};
+    use crate::vector::{Vector2, Vector3, Vector4};
+
+    macro_rules! IMPL_GLAM {
+        ($(($t:ty, $vec:ident, $glam_vec:ident, $mat:ident, $glam_mat:ident)),*) => {
+            $(
+                impl From<glam::$glam_vec> for $vec<$t> {
+                    #[inline(always)]
+                    fn from(value: glam::$glam_vec) -> Self {
+                        $vec(value.to_array())
+                    }
+                }
+
+                impl From<$vec<$t>> for glam::$glam_vec {
+                    #[inline(always)]
+                    fn from(value: $vec<$t>) -> Self {
+                        glam::$glam_vec::from_array(value.0)
+                    }
+                }
+
+                impl From<glam::$glam_mat> for $mat<$t> {
+                    #[inline(always)]
+                    fn from(value: glam::$glam_mat) -> Self {
+                        $mat::from_cols(value.to_cols_array_2d().map($vec))
+                    }
+                }
+
+                impl From<$mat<$t>> for glam::$glam_mat {
+                    #[inline(always)]
+                    fn from(value: $mat<$t>) -> Self {
+                        glam::$glam_mat::from_cols_array_2d(&value.to_cols_array_2d())
+                    }
+                }
+            )*
+        };
+    }
+
+    IMPL_GLAM!(
+        (f32, Vector2, Vec2, Matrix2x2, Mat2),
+        (f32, Vector3, Vec3, Matrix3x3, Mat3),
+        (f32, Vector4, Vec4, Matrix4x4, Mat4),
+        (f64, Vector2, DVec2, Matrix2x2, DMat2),
+        (f64, Vector3, DVec3, Matrix3x3, DMat3),
+        (f64, Vector4, DVec4, Matrix4x4, DMat4)
+    );
+
+    impl From<glam::Vec3A> for Vector3<f32> {
+        #[inline(always)]
+        fn from(value: glam::Vec3A) -> Self {
+            Vector3(value.to_array())
+        }
+    }
+
+    impl From<Vector3<f32>> for glam::Vec3A {
+        #[inline(always)]
+        fn from(value: Vector3<f32>) -> Self {
+            glam::Vec3A::from_array(value.0)
+        }
+    }
+
+    impl From<glam::Mat3A> for Matrix3x3<f32> {
+        #[inline(always)]
+        fn from(value: glam::Mat3A) -> Self {
+            Matrix3x3::from_cols(value.to_cols_array_2d().map(Vector3))
+        }
+    }
+
+    impl From<Matrix3x3<f32>> for glam::Mat3A {
+        #[inline(always)]
+        fn from(value: Matrix3x3<f32>) -> Self {
+            glam::Mat3A::from_cols_array_2d(&value.to_cols_array_2d())
+        }
+    }
+
+    #[cfg(feature = "quaternions")]
+    impl From<glam::Quat> for crate::quaternions::Quaternion<f32> {
+        #[inline(always)]
+        fn from(value: glam::Quat) -> Self {
+            Self::from(value.to_array())
+        }
+    }
+
+    #[cfg(feature = "quaternions")]
+    impl From<crate::quaternions::Quaternion<f32>> for glam::Quat {
+        #[inline(always)]
+        fn from(value: crate::quaternions::Quaternion<f32>) -> Self {
+            glam::Quat::from_array(value.to_xyzw())
+        }
+    }
+
+    #[cfg(feature = "quaternions")]
+    impl From<glam::DQuat> for crate::quaternions::Quaternion<f64> {
+        #[inline(always)]
+        fn from(value: glam::DQuat) -> Self {
+            Self::from(value.to_array())
+        }
+    }
+
+    #[cfg(feature = "quaternions")]
+    impl From<crate::quaternions::Quaternion<f64>> for glam::DQuat {
+        #[inline(always)]
+        fn from(value: crate::quaternions::Quaternion<f64>) -> Self {
+            glam::DQuat::from_array(value.to_xyzw())
+        }
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+mod impl_nalgebra {
+    // nalgebra::Scalar needs 'static, which Element does not always have
+    use crate::matrix::{Matrix2x2, Matrix3x3, Matrix4x4};
+    use crate::vector::{Vector2, Vector3, Vector4};
+    use crate::Element;
+
+    macro_rules! IMPL_NALGEBRA {
+        ($(($vec:ident, $mat:ident, $na_mat:ident)),*) => {
+            $(
+                impl<T: Element + 'static> From<nalgebra::$vec<T>> for $vec<T> {
+                    #[inline(always)]
+                    fn from(value: nalgebra::$vec<T>) -> Self {
+                        $vec(value.into())
+                    }
+                }
+
+                impl<T: Element + 'static> From<$vec<T>> for nalgebra::$vec<T> {
+                    #[inline(always)]
+                    fn from(value: $vec<T>) -> Self {
+                        nalgebra::$vec::from(value.0)
+                    }
+                }
+
+                impl<T: Element + 'static> From<nalgebra::$na_mat<T>> for $mat<T> {
+                    #[inline(always)]
+                    fn from(value: nalgebra::$na_mat<T>) -> Self {
+                        $mat(std::array::from_fn(|cx| {
+                            $vec(std::array::from_fn(|cy| value[(cx, cy)]))
+                        }))
+                    }
+                }
+
+                impl<T: Element + 'static> From<$mat<T>> for nalgebra::$na_mat<T> {
+                    #[inline(always)]
+                    fn from(value: $mat<T>) -> Self {
+                        nalgebra::$na_mat::from_fn(|cx, cy| value[cx][cy])
+                    }
+                }
+            )*
+        };
+    }
+
+    IMPL_NALGEBRA!(
+        (Vector2, Matrix2x2, Matrix2),
+        (Vector3, Matrix3x3, Matrix3),
+        (Vector4, Matrix4x4, Matrix4)
+    );
+
+    impl<T: Element + 'static> From<nalgebra::Point2<T>> for Vector2<T> {
+        #[inline(always)]
+        fn from(value: nalgebra::Point2<T>) -> Self {
+            value.coords.into()
+        }
+    }
+
+    impl<T: Element + 'static> From<Vector2<T>> for nalgebra::Point2<T> {
+        #[inline(always)]
+        fn from(value: Vector2<T>) -> Self {
+            nalgebra::Point2::from(value.0)
+        }
+    }
+
+    impl<T: Element + 'static> From<nalgebra::Point3<T>> for Vector3<T> {
+        #[inline(always)]
+        fn from(value: nalgebra::Point3<T>) -> Self {
+            value.coords.into()
+        }
+    }
+
+    impl<T: Element + 'static> From<Vector3<T>> for nalgebra::Point3<T> {
+        #[inline(always)]
+        fn from(value: Vector3<T>) -> Self {
+            nalgebra::Point3::from(value.0)
+        }
+    }
+
+    #[cfg(feature = "quaternions")]
+    impl<T: Element + 'static> From<nalgebra::Quaternion<T>> for crate::quaternions::Quaternion<T> {
+        #[inline(always)]
+        fn from(value: nalgebra::Quaternion<T>) -> Self {
+            Self::from(Vector4::from(value.coords))
+        }
+    }
+
+    #[cfg(feature = "quaternions")]
+    impl<T: Element + 'static> From<crate::quaternions::Quaternion<T>> for nalgebra::Quaternion<T> {
+        #[inline(always)]
+        fn from(value: crate::quaternions::Quaternion<T>) -> Self {
+            nalgebra::Quaternion::from(nalgebra::Vector4::from(value.to_xyzw()))
+        }
+    }
+
+    /// Gives the underlying Quaternion, which is normalized
+    #[cfg(feature = "quaternions")]
+    impl<T: Element + 'static> From<nalgebra::UnitQuaternion<T>> for crate::quaternions::Quaternion<T> {
+        #[inline(always)]
+        fn from(value: nalgebra::UnitQuaternion<T>) -> Self {
+            value.into_inner().into()
+        }
+    }
+}
+
+#[cfg(feature = "cgmath")]
+mod impl_cgmath {
+    use crate::matrix::{Matrix2x2, Matrix3x3, Matrix4x4};
+    use crate::vector::{Vector2, Vector3, Vector4};
+    use crate::Element;
+
+    macro_rules! IMPL_CGMATH {
+        ($(($n:expr, $vec:ident, $mat:ident, $cg_mat:ident)),*) => {
+            $(
+                impl<T: Element> From<cgmath::$vec<T>> for $vec<T> {
+                    #[inline(always)]
+                    fn from(value: cgmath::$vec<T>) -> Self {
+                        $vec(value.into())
+                    }
+                }
+
+                impl<T: Element> From<$vec<T>> for cgmath::$vec<T> {
+                    #[inline(always)]
+                    fn from(value: $vec<T>) -> Self {
+                        value.0.into()
+                    }
+                }
+
+                impl<T: Element> From<cgmath::$cg_mat<T>> for $mat<T> {
+                    #[inline(always)]
+                    fn from(value: cgmath::$cg_mat<T>) -> Self {
+                        let cols: [[T; $n]; $n] = value.into();
+                        $mat::from_cols(cols.map($vec))
+                    }
+                }
+
+                impl<T: Element> From<$mat<T>> for cgmath::$cg_mat<T> {
+                    #[inline(always)]
+                    fn from(value: $mat<T>) -> Self {
+                        value.to_cols_array_2d().into()
+                    }
+                }
+            )*
+        };
+    }
+
+    IMPL_CGMATH!(
+        (2, Vector2, Matrix2x2, Matrix2),
+        (3, Vector3, Matrix3x3, Matrix3),
+        (4, Vector4, Matrix4x4, Matrix4)
+    );
+
+    impl<T: Element> From<cgmath::Point2<T>> for Vector2<T> {
+        #[inline(always)]
+        fn from(value: cgmath::Point2<T>) -> Self {
+            Vector2(value.into())
+        }
+    }
+
+    impl<T: Element> From<Vector2<T>> for cgmath::Point2<T> {
+        #[inline(always)]
+        fn from(value: Vector2<T>) -> Self {
+            value.0.into()
+        }
+    }
+
+    impl<T: Element> From<cgmath::Point3<T>> for Vector3<T> {
+        #[inline(always)]
+        fn from(value: cgmath::Point3<T>) -> Self {
+            Vector3(value.into())
+        }
+    }
+
+    impl<T: Element> From<Vector3<T>> for cgmath::Point3<T> {
+        #[inline(always)]
+        fn from(value: Vector3<T>) -> Self {
+            value.0.into()
+        }
+    }
+
+    #[cfg(feature = "quaternions")]
+    impl<T: Element> From<cgmath::Quaternion<T>> for crate::quaternions::Quaternion<T> {
+        #[inline(always)]
+        fn from(value: cgmath::Quaternion<T>) -> Self {
+            Self::from_scalar_vector(value.s, Vector3(value.v.into()))
+        }
+    }
+
+    #[cfg(feature = "quaternions")]
+    impl<T: Element> From<crate::quaternions::Quaternion<T>> for cgmath::Quaternion<T> {
+        #[inline(always)]
+        fn from(value: crate::quaternions::Quaternion<T>) -> Self {
+            cgmath::Quaternion {
+                v: value.vector().0.into(),
+                s: value.scalar(),
+            }
+        }
+    }
+}
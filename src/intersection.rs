@@ -0,0 +1,313 @@
+//! Intersection tests between the shapes of [`crate::geometry`].
+//!
+//! Rays report where they first hit a shape as a `t` along the Ray, see [`Ray::at`].
+//! Like the rest of the geometry module, spheres and boxes are solid,
+//! so a Ray starting inside one hits it at `t = 0`.
+//!
+//! The `intersects_*` tests only tell whether two shapes overlap.
+//! Shapes that just touch count as overlapping.
+//!
+//! # Examples
+//!
+//! ```
+//! use glmath::geometry::{Aabb, Ray, Triangle};
+//! use glmath::vector::Vector3;
+//!
+//! let ray = Ray::new(Vector3::from([0.25f32, 0.25f32, 5f32]), Vector3::from([0f32, 0f32, -1f32]));
+//! let triangle = Triangle::new(
+//!     Vector3::from([0f32, 0f32, 0f32]),
+//!     Vector3::from([1f32, 0f32, 0f32]),
+//!     Vector3::from([0f32, 1f32, 0f32]),
+//! );
+//! let hit = ray.intersect_triangle(triangle).unwrap();
+//! assert_eq!(hit.t, 5f32);
+//! assert_eq!((hit.u, hit.v), (0.25f32, 0.25f32));
+//!
+//! let aabb = Aabb::new(Vector3::from([-1f32, -1f32, -1f32]), Vector3::from([1f32, 1f32, 1f32]));
+//! assert_eq!(ray.intersect_aabb(aabb), Some(4f32));
+//! ```
+
+use crate::geometry::{Aabb, Obb, Plane, Ray, Segment, Sphere, Triangle};
+use crate::vector::Vector3;
+use crate::Element;
+
+/// Where a Ray hits a Triangle.
+/// The point hit is `a * (1 - u - v) + b * u + c * v`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangleHit<T: Element> {
+    pub t: T,
+    pub u: T,
+    pub v: T,
+}
+
+impl<T: Element> TriangleHit<T> {
+    /// Gives the barycentric coordinates of the point hit,
+    /// as the weights of a, b and c
+    #[inline(always)]
+    pub fn barycentric(self) -> Vector3<T> {
+        Vector3([T::one() - self.u - self.v, self.u, self.v])
+    }
+}
+
+impl<T: Element> Ray<T> {
+    /// Finds where the Ray hits the Triangle, from either side,
+    /// using the Möller–Trumbore algorithm.
+    /// Gives None for a miss, or if the Ray is parallel to the Triangle
+    #[inline(always)]
+    pub fn intersect_triangle(self, triangle: Triangle<T>) -> Option<TriangleHit<T>> {
+        let edge1 = triangle.b.sub(triangle.a);
+        let edge2 = triangle.c.sub(triangle.a);
+        let p = self.direction.mul_cross(edge2);
+        let det = edge1.mul_inner(p);
+        let scale =
+            (edge1.length_squared() * edge2.length_squared() * self.direction.length_squared())
+                .sqrt();
+        if det.abs() <= T::epsilon() * scale {
+            return None;
+        }
+        let inv_det = T::one() / det;
+        let s = self.origin.sub(triangle.a);
+        let u = s.mul_inner(p) * inv_det;
+        if u < T::zero() || u > T::one() {
+            return None;
+        }
+        let q = s.mul_cross(edge1);
+        let v = self.direction.mul_inner(q) * inv_det;
+        if v < T::zero() || u + v > T::one() {
+            return None;
+        }
+        let t = edge2.mul_inner(q) * inv_det;
+        if t < T::zero() {
+            return None;
+        }
+        Some(TriangleHit { t, u, v })
+    }
+
+    /// Finds the first `t` where the Ray is inside the box, using the slab method.
+    /// Gives None for a miss
+    #[inline(always)]
+    pub fn intersect_aabb(self, aabb: Aabb<T>) -> Option<T> {
+        let mut t_min = T::zero();
+        let mut t_max = T::infinity();
+        for cx in 0..3 {
+            if self.direction[cx] == T::zero() {
+                // parallel to the slab, so either always or never in it
+                if self.origin[cx] < aabb.min[cx] || self.origin[cx] > aabb.max[cx] {
+                    return None;
+                }
+                continue;
+            }
+            let inv = T::one() / self.direction[cx];
+            let t1 = (aabb.min[cx] - self.origin[cx]) * inv;
+            let t2 = (aabb.max[cx] - self.origin[cx]) * inv;
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+            if t_min > t_max {
+                return None;
+            }
+        }
+        Some(t_min)
+    }
+
+    /// Finds the first `t` where the Ray is inside the Sphere.
+    /// Gives None for a miss
+    #[inline(always)]
+    pub fn intersect_sphere(self, sphere: Sphere<T>) -> Option<T> {
+        let offset = self.origin.sub(sphere.center);
+        let c = offset.length_squared() - sphere.radius * sphere.radius;
+        if c <= T::zero() {
+            return Some(T::zero());
+        }
+        let a = self.direction.length_squared();
+        let b = offset.mul_inner(self.direction);
+        // starting outside and going away
+        if b > T::zero() || a == T::zero() {
+            return None;
+        }
+        let discriminant = b * b - a * c;
+        if discriminant < T::zero() {
+            return None;
+        }
+        Some((-b - discriminant.sqrt()) / a)
+    }
+
+    /// Finds the `t` where the Ray crosses the Plane.
+    /// Gives None if the Ray is parallel to the Plane or points away from it.
+    /// A Ray starting on the Plane hits it at `t = 0`
+    #[inline(always)]
+    pub fn intersect_plane(self, plane: Plane<T>) -> Option<T> {
+        let distance = plane.signed_distance(self.origin);
+        if distance == T::zero() {
+            return Some(T::zero());
+        }
+        let denom = plane.normal.mul_inner(self.direction);
+        if denom == T::zero() {
+            return None;
+        }
+        let t = -distance / denom;
+        if t < T::zero() {
+            return None;
+        }
+        Some(t)
+    }
+}
+
+impl<T: Element> Segment<T> {
+    /// Gives the closest pair of points between the two Segments,
+    /// the first on this Segment and the second on the other.
+    /// Degenerate Segments are treated as points
+    #[inline(always)]
+    pub fn closest_points(self, other: Self) -> (Vector3<T>, Vector3<T>) {
+        // Ericson, Real-Time Collision Detection 5.1.9
+        let d1 = self.direction();
+        let d2 = other.direction();
+        let r = self.start.sub(other.start);
+        let a = d1.length_squared();
+        let e = d2.length_squared();
+        let f = d2.mul_inner(r);
+        let clamp = |x: T| x.max(T::zero()).min(T::one());
+
+        let (s, t) = if a == T::zero() && e == T::zero() {
+            (T::zero(), T::zero())
+        } else if a == T::zero() {
+            (T::zero(), clamp(f / e))
+        } else {
+            let c = d1.mul_inner(r);
+            if e == T::zero() {
+                (clamp(-c / a), T::zero())
+            } else {
+                let b = d1.mul_inner(d2);
+                let denom = a * e - b * b;
+                // parallel Segments have many closest pairs, pick the one at s = 0
+                let s = if denom > T::zero() {
+                    clamp((b * f - c * e) / denom)
+                } else {
+                    T::zero()
+                };
+                let t = (b * s + f) / e;
+                if t < T::zero() {
+                    (clamp(-c / a), T::zero())
+                } else if t > T::one() {
+                    (clamp((b - c) / a), T::one())
+                } else {
+                    (s, t)
+                }
+            }
+        };
+        (self.at(s), other.at(t))
+    }
+
+    /// Gives the distance between the two Segments
+    #[inline(always)]
+    pub fn distance_to_segment(self, other: Self) -> T {
+        let (ca, cb) = self.closest_points(other);
+        ca.dist(cb)
+    }
+}
+
+impl<T: Element> Sphere<T> {
+    #[inline(always)]
+    pub fn intersects_sphere(self, other: Self) -> bool {
+        let radius = self.radius + other.radius;
+        self.center.distance_squared(other.center) <= radius * radius
+    }
+
+    #[inline(always)]
+    pub fn intersects_aabb(self, aabb: Aabb<T>) -> bool {
+        aabb.closest_point(self.center)
+            .distance_squared(self.center)
+            <= self.radius * self.radius
+    }
+}
+
+impl<T: Element> Aabb<T> {
+    #[inline(always)]
+    pub fn intersects_aabb(self, other: Self) -> bool {
+        (0..3).all(|cx| self.min[cx] <= other.max[cx] && other.min[cx] <= self.max[cx])
+    }
+
+    #[inline(always)]
+    pub fn intersects_sphere(self, sphere: Sphere<T>) -> bool {
+        sphere.intersects_aabb(self)
+    }
+}
+
+impl<T: Element> Triangle<T> {
+    /// Tests the Triangle against the box with the separating axis theorem,
+    /// on the box axes, the Triangle normal and the 9 cross products of their edges
+    #[inline(always)]
+    pub fn intersects_aabb(self, aabb: Aabb<T>) -> bool {
+        let center = aabb.center();
+        let half_extents = aabb.half_extents();
+        let points = [self.a, self.b, self.c].map(|ca| ca.sub(center));
+        let edges = [
+            points[1].sub(points[0]),
+            points[2].sub(points[1]),
+            points[0].sub(points[2]),
+        ];
+        let separated = |axis: Vector3<T>| {
+            let radius = axis.abs().mul_inner(half_extents);
+            let projected = points.map(|ca| ca.mul_inner(axis));
+            let min = projected[0].min(projected[1]).min(projected[2]);
+            let max = projected[0].max(projected[1]).max(projected[2]);
+            min > radius || max < -radius
+        };
+
+        let box_axes = crate::matrix::Matrix3x3::<T>::ident().0;
+        if box_axes.into_iter().any(separated) {
+            return false;
+        }
+        if separated(edges[0].mul_cross(edges[1])) {
+            return false;
+        }
+        !box_axes
+            .into_iter()
+            .any(|ca| edges.into_iter().any(|cb| separated(ca.mul_cross(cb))))
+    }
+}
+
+impl<T: Element> Obb<T> {
+    /// Tests the two boxes with the separating axis theorem,
+    /// on the 6 box axes and the 9 cross products between them
+    #[inline(always)]
+    pub fn intersects_obb(self, other: Self) -> bool {
+        // Ericson, Real-Time Collision Detection 4.4.1
+        let ea = self.half_extents;
+        let eb = other.half_extents;
+        // the other box's axes and center in this box's coordinates
+        let r: [[T; 3]; 3] = std::array::from_fn(|cx| {
+            std::array::from_fn(|cy| self.axes[cx].mul_inner(other.axes[cy]))
+        });
+        let t = self.to_local(other.center);
+        // an epsilon keeps parallel edges, with near-zero cross products, from
+        // finding a false separation
+        let abs_r = r.map(|row| row.map(|ca| ca.abs() + T::epsilon()));
+
+        for cx in 0..3 {
+            let rb = eb[0] * abs_r[cx][0] + eb[1] * abs_r[cx][1] + eb[2] * abs_r[cx][2];
+            if t[cx].abs() > ea[cx] + rb {
+                return false;
+            }
+        }
+        for cy in 0..3 {
+            let ra = ea[0] * abs_r[0][cy] + ea[1] * abs_r[1][cy] + ea[2] * abs_r[2][cy];
+            let tb = t[0] * r[0][cy] + t[1] * r[1][cy] + t[2] * r[2][cy];
+            if tb.abs() > ra + eb[cy] {
+                return false;
+            }
+        }
+        for cx in 0..3 {
+            let (i1, i2) = ((cx + 1) % 3, (cx + 2) % 3);
+            for cy in 0..3 {
+                let (j1, j2) = ((cy + 1) % 3, (cy + 2) % 3);
+                let ra = ea[i1] * abs_r[i2][cy] + ea[i2] * abs_r[i1][cy];
+                let rb = eb[j1] * abs_r[cx][j2] + eb[j2] * abs_r[cx][j1];
+                let tl = t[i2] * r[i1][cy] - t[i1] * r[i2][cy];
+                if tl.abs() > ra + rb {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,347 @@
+//! Padded types matching the GLSL `std140` and `std430` buffer layouts.
+//!
+//! The regular Vector and Matrix types are tightly packed, so a `Vector3<f32>` is
+//! 12 bytes and a `Matrix3x3<f32>` is 36 bytes. In a uniform buffer (`std140`),
+//! a vec3 is aligned to 16 bytes and every column of a matrix is padded to a vec4.
+//! Storage buffers (`std430`) are the same, except that arrays and mat2 columns
+//! are not rounded up to 16 bytes.
+//!
+//! The types here carry the right size and alignment, so a `#[repr(C)]` struct
+//! built out of them lines up with the GLSL block. Matrices are stored column-major,
+//! as GLSL expects.
+//!
+//! # Examples
+//!
+//! ```
+//! use glmath::layout::{AsStd140, Std140Matrix4x4, Std140Vector3};
+//! use glmath::matrix::Matrix4x4;
+//! use glmath::vector::Vector3;
+//!
+//! // layout(std140) uniform Camera {
+//! //     mat4 view_proj;
+//! //     vec3 position;
+//! //     float exposure;
+//! // };
+//! #[repr(C)]
+//! #[derive(Clone, Copy)]
+//! struct Camera {
+//!     view_proj: Std140Matrix4x4,
+//!     position: Std140Vector3,
+//! }
+//!
+//! let camera = Camera {
+//!     view_proj: Matrix4x4::<f32>::ident().as_std140(),
+//!     // the padding of a vec3 is where the following float goes
+//!     position: Std140Vector3::with_padding(Vector3::from([1f32, 2f32, 3f32]), 0.5f32),
+//! };
+//!
+//! assert_eq!(std::mem::size_of::<Camera>(), 80);
+//! ```
+
+use crate::matrix::{Matrix2x2, Matrix3x3, Matrix4x4};
+use crate::vector::{Vector2, Vector3, Vector4};
+
+/// A vec3, aligned and padded to 16 bytes.
+/// This is the same for `std140` and `std430`
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Std140Vector3 {
+    pub value: Vector3<f32>,
+    /// The last 4 bytes, which a following float can use
+    pub padding: f32,
+}
+
+/// A vec4, aligned to 16 bytes.
+/// This is the same for `std140` and `std430`
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Std140Vector4 {
+    pub value: Vector4<f32>,
+}
+
+/// A float as an element of a `std140` array,
+/// where each element is rounded up to 16 bytes
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Std140ArrayFloat {
+    pub value: f32,
+    pub padding: [f32; 3],
+}
+
+/// A vec2 as an element of a `std140` array,
+/// where each element is rounded up to 16 bytes
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Std140ArrayVector2 {
+    pub value: Vector2<f32>,
+    pub padding: [f32; 2],
+}
+
+/// A mat2 in `std140`, with each column padded to a vec4
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Std140Matrix2x2 {
+    pub cols: [[f32; 4]; 2],
+}
+
+/// A mat2 in `std430`, where the columns are not padded
+#[repr(C, align(8))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Std430Matrix2x2 {
+    pub cols: [[f32; 2]; 2],
+}
+
+/// A mat3, with each column padded to a vec4.
+/// This is the same for `std140` and `std430`
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Std140Matrix3x3 {
+    pub cols: [[f32; 4]; 3],
+}
+
+/// A mat4, aligned to 16 bytes.
+/// This is the same for `std140` and `std430`
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Std140Matrix4x4 {
+    pub cols: [[f32; 4]; 4],
+}
+
+pub type Std430Vector3 = Std140Vector3;
+pub type Std430Vector4 = Std140Vector4;
+pub type Std430Matrix3x3 = Std140Matrix3x3;
+pub type Std430Matrix4x4 = Std140Matrix4x4;
+
+const _: () = {
+    use core::mem::{align_of, size_of};
+    assert!(size_of::<Std140Vector3>() == 16 && align_of::<Std140Vector3>() == 16);
+    assert!(size_of::<Std140Vector4>() == 16 && align_of::<Std140Vector4>() == 16);
+    assert!(size_of::<Std140ArrayFloat>() == 16 && align_of::<Std140ArrayFloat>() == 16);
+    assert!(size_of::<Std140ArrayVector2>() == 16 && align_of::<Std140ArrayVector2>() == 16);
+    assert!(size_of::<Std140Matrix2x2>() == 32 && align_of::<Std140Matrix2x2>() == 16);
+    assert!(size_of::<Std430Matrix2x2>() == 16 && align_of::<Std430Matrix2x2>() == 8);
+    assert!(size_of::<Std140Matrix3x3>() == 48 && align_of::<Std140Matrix3x3>() == 16);
+    assert!(size_of::<Std140Matrix4x4>() == 64 && align_of::<Std140Matrix4x4>() == 16);
+};
+
+macro_rules! IMPL_POD {
+    ($($t:ty),*) => {
+        $(
+            #[cfg(feature = "bytemuck")]
+            unsafe impl bytemuck::Pod for $t {}
+
+            #[cfg(feature = "bytemuck")]
+            unsafe impl bytemuck::Zeroable for $t {}
+        )*
+    };
+}
+
+IMPL_POD!(
+    Std140Vector3,
+    Std140Vector4,
+    Std140ArrayFloat,
+    Std140ArrayVector2,
+    Std140Matrix2x2,
+    Std430Matrix2x2,
+    Std140Matrix3x3,
+    Std140Matrix4x4
+);
+
+impl Std140Vector3 {
+    /// Creates the padded vec3, filling the padding
+    /// with the float that follows it in the block
+    #[inline(always)]
+    pub fn with_padding(value: Vector3<f32>, padding: f32) -> Self {
+        Self { value, padding }
+    }
+}
+
+/// Converts a value into its `std140` form
+pub trait AsStd140 {
+    type Output: Copy;
+    fn as_std140(&self) -> Self::Output;
+}
+
+/// Converts a value into its `std430` form
+pub trait AsStd430 {
+    type Output: Copy;
+    fn as_std430(&self) -> Self::Output;
+}
+
+/// Converts a value into its form as an element of a `std140` array
+pub trait AsStd140ArrayElement {
+    type Output: Copy;
+    fn as_std140_array_element(&self) -> Self::Output;
+}
+
+impl AsStd140 for Vector3<f32> {
+    type Output = Std140Vector3;
+    #[inline(always)]
+    fn as_std140(&self) -> Self::Output {
+        Std140Vector3::with_padding(*self, 0f32)
+    }
+}
+
+impl AsStd140 for Vector4<f32> {
+    type Output = Std140Vector4;
+    #[inline(always)]
+    fn as_std140(&self) -> Self::Output {
+        Std140Vector4 { value: *self }
+    }
+}
+
+impl AsStd140 for Matrix2x2<f32> {
+    type Output = Std140Matrix2x2;
+    #[inline(always)]
+    fn as_std140(&self) -> Self::Output {
+        Std140Matrix2x2 {
+            cols: self
+                .to_cols_array_2d()
+                .map(|col| [col[0], col[1], 0f32, 0f32]),
+        }
+    }
+}
+
+impl AsStd140 for Matrix3x3<f32> {
+    type Output = Std140Matrix3x3;
+    #[inline(always)]
+    fn as_std140(&self) -> Self::Output {
+        Std140Matrix3x3 {
+            cols: self
+                .to_cols_array_2d()
+                .map(|col| [col[0], col[1], col[2], 0f32]),
+        }
+    }
+}
+
+impl AsStd140 for Matrix4x4<f32> {
+    type Output = Std140Matrix4x4;
+    #[inline(always)]
+    fn as_std140(&self) -> Self::Output {
+        Std140Matrix4x4 {
+            cols: self.to_cols_array_2d(),
+        }
+    }
+}
+
+impl AsStd430 for Vector3<f32> {
+    type Output = Std430Vector3;
+    #[inline(always)]
+    fn as_std430(&self) -> Self::Output {
+        self.as_std140()
+    }
+}
+
+impl AsStd430 for Vector4<f32> {
+    type Output = Std430Vector4;
+    #[inline(always)]
+    fn as_std430(&self) -> Self::Output {
+        self.as_std140()
+    }
+}
+
+impl AsStd430 for Matrix2x2<f32> {
+    type Output = Std430Matrix2x2;
+    #[inline(always)]
+    fn as_std430(&self) -> Self::Output {
+        Std430Matrix2x2 {
+            cols: self.to_cols_array_2d(),
+        }
+    }
+}
+
+impl AsStd430 for Matrix3x3<f32> {
+    type Output = Std430Matrix3x3;
+    #[inline(always)]
+    fn as_std430(&self) -> Self::Output {
+        self.as_std140()
+    }
+}
+
+impl AsStd430 for Matrix4x4<f32> {
+    type Output = Std430Matrix4x4;
+    #[inline(always)]
+    fn as_std430(&self) -> Self::Output {
+        self.as_std140()
+    }
+}
+
+impl AsStd140ArrayElement for f32 {
+    type Output = Std140ArrayFloat;
+    #[inline(always)]
+    fn as_std140_array_element(&self) -> Self::Output {
+        Std140ArrayFloat {
+            value: *self,
+            padding: [0f32; 3],
+        }
+    }
+}
+
+impl AsStd140ArrayElement for Vector2<f32> {
+    type Output = Std140ArrayVector2;
+    #[inline(always)]
+    fn as_std140_array_element(&self) -> Self::Output {
+        Std140ArrayVector2 {
+            value: *self,
+            padding: [0f32; 2],
+        }
+    }
+}
+
+impl AsStd140ArrayElement for Vector3<f32> {
+    type Output = Std140Vector3;
+    #[inline(always)]
+    fn as_std140_array_element(&self) -> Self::Output {
+        self.as_std140()
+    }
+}
+
+impl AsStd140ArrayElement for Vector4<f32> {
+    type Output = Std140Vector4;
+    #[inline(always)]
+    fn as_std140_array_element(&self) -> Self::Output {
+        self.as_std140()
+    }
+}
+
+impl From<Std140Vector3> for Vector3<f32> {
+    #[inline(always)]
+    fn from(value: Std140Vector3) -> Self {
+        value.value
+    }
+}
+
+impl From<Std140Vector4> for Vector4<f32> {
+    #[inline(always)]
+    fn from(value: Std140Vector4) -> Self {
+        value.value
+    }
+}
+
+impl From<Std140Matrix2x2> for Matrix2x2<f32> {
+    #[inline(always)]
+    fn from(value: Std140Matrix2x2) -> Self {
+        Matrix2x2::from_cols(value.cols.map(|col| Vector2([col[0], col[1]])))
+    }
+}
+
+impl From<Std430Matrix2x2> for Matrix2x2<f32> {
+    #[inline(always)]
+    fn from(value: Std430Matrix2x2) -> Self {
+        Matrix2x2::from_cols(value.cols.map(Vector2))
+    }
+}
+
+impl From<Std140Matrix3x3> for Matrix3x3<f32> {
+    #[inline(always)]
+    fn from(value: Std140Matrix3x3) -> Self {
+        Matrix3x3::from_cols(value.cols.map(|col| Vector3([col[0], col[1], col[2]])))
+    }
+}
+
+impl From<Std140Matrix4x4> for Matrix4x4<f32> {
+    #[inline(always)]
+    fn from(value: Std140Matrix4x4) -> Self {
+        Matrix4x4::from_cols(value.cols.map(Vector4))
+    }
+}
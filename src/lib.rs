@@ -1,27 +1,121 @@
+pub mod angle;
+pub mod batch;
+pub mod coordinate_system;
+pub mod curves;
+pub mod format;
+pub mod frustum;
+pub mod geodetic;
+pub mod geometry;
+mod interop;
+pub mod intersection;
+pub mod layout;
 pub mod matrix;
+pub mod packing;
 #[cfg(feature = "quaternions")]
 pub mod quaternions;
 pub mod scalar;
+#[cfg(feature = "serde")]
+pub mod serialize;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod triangle;
 pub mod vector;
+pub mod wide;
+
+mod bounds {
+    /// `'static` with the `simd` feature, which picks out f32 by its `TypeId`,
+    /// and no bound otherwise. Code that needs `'static` for another reason,
+    /// like the nalgebra impls, has to ask for it itself
+    #[cfg(feature = "simd")]
+    pub trait MaybeStatic: 'static {}
+    #[cfg(feature = "simd")]
+    impl<T: 'static> MaybeStatic for T {}
+    #[cfg(not(feature = "simd"))]
+    pub trait MaybeStatic {}
+    #[cfg(not(feature = "simd"))]
+    impl<T> MaybeStatic for T {}
+}
 
 #[cfg(feature = "bytemuck")]
 pub trait Element:
-    core::fmt::Debug + std::iter::Sum + num::Float + PartialOrd + Clone + Copy + bytemuck::Pod
+    bounds::MaybeStatic
+    + core::fmt::Debug
+    + std::iter::Sum
+    + num::Float
+    + PartialOrd
+    + Clone
+    + Copy
+    + bytemuck::Pod
 {
 }
 #[cfg(feature = "bytemuck")]
 impl<
-        T: core::fmt::Debug + num::Float + PartialOrd + std::iter::Sum + Clone + Copy + bytemuck::Pod,
+        T: bounds::MaybeStatic
+            + core::fmt::Debug
+            + num::Float
+            + PartialOrd
+            + std::iter::Sum
+            + Clone
+            + Copy
+            + bytemuck::Pod,
     > Element for T
 {
 }
 #[cfg(not(feature = "bytemuck"))]
 pub trait Element:
-    core::fmt::Debug + std::iter::Sum + num::Float + PartialOrd + Clone + Copy
+    bounds::MaybeStatic + core::fmt::Debug + std::iter::Sum + num::Float + PartialOrd + Clone + Copy
 {
 }
 #[cfg(not(feature = "bytemuck"))]
-impl<T: core::fmt::Debug + num::Float + PartialOrd + std::iter::Sum + Clone + Copy> Element for T {}
+impl<
+        T: bounds::MaybeStatic
+            + core::fmt::Debug
+            + num::Float
+            + PartialOrd
+            + std::iter::Sum
+            + Clone
+            + Copy,
+    > Element for T
+{
+}
+
+/// The seeded rng and random Vectors shared by the test modules
+#[cfg(test)]
+mod test_util {
+    use rand::{distributions::uniform::SampleUniform, rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{
+        matrix::Matrix4x4,
+        vector::{Vector2, Vector3, Vector4},
+        Element,
+    };
+
+    /// The rng every randomized test starts from,
+    /// so the cases are the same on every run
+    pub fn rng() -> StdRng {
+        StdRng::seed_from_u64(0x5eed)
+    }
+
+    /// Gives a Vector2 with every element in `-range..range`
+    pub fn rand_vector2<T: Element + SampleUniform>(rng: &mut StdRng, range: T) -> Vector2<T> {
+        Vector2::from([0; 2].map(|_| rng.gen_range(-range..range)))
+    }
+
+    /// Gives a Vector3 with every element in `-range..range`
+    pub fn rand_vector3<T: Element + SampleUniform>(rng: &mut StdRng, range: T) -> Vector3<T> {
+        Vector3::from([0; 3].map(|_| rng.gen_range(-range..range)))
+    }
+
+    /// Gives a Vector4 with every element in `-range..range`
+    pub fn rand_vector4<T: Element + SampleUniform>(rng: &mut StdRng, range: T) -> Vector4<T> {
+        Vector4::from([0; 4].map(|_| rng.gen_range(-range..range)))
+    }
+
+    /// Gives a Matrix4x4 with every element in `-range..range`
+    pub fn rand_matrix4x4<T: Element + SampleUniform>(rng: &mut StdRng, range: T) -> Matrix4x4<T> {
+        Matrix4x4::from([0; 4].map(|_| rand_vector4(rng, range)))
+    }
+}
 
 #[cfg(test)]
 mod test_matrix {
@@ -129,6 +223,57 @@ mod test_matrix {
         )
     }
 
+    #[test]
+    fn test_matrix_rows_cols() {
+        let mut m1 = Matrix3x3::from([1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32]);
+        let rows = [
+            Vector3::from([1f32, 2f32, 3f32]),
+            Vector3::from([4f32, 5f32, 6f32]),
+            Vector3::from([7f32, 8f32, 9f32]),
+        ];
+
+        assert_eq!(Matrix3x3::from_rows(rows), m1);
+        assert_eq!(Matrix3x3::from_cols(rows), m1.transpose());
+        assert_eq!(m1.row(1), rows[1]);
+        assert_eq!(m1.col(1), Vector3::from([2f32, 5f32, 8f32]));
+
+        assert_eq!(
+            m1.to_rows_array(),
+            [1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32]
+        );
+        assert_eq!(
+            m1.to_cols_array(),
+            [1f32, 4f32, 7f32, 2f32, 5f32, 8f32, 3f32, 6f32, 9f32]
+        );
+        assert_eq!(
+            m1.to_rows_array_2d(),
+            [[1f32, 2f32, 3f32], [4f32, 5f32, 6f32], [7f32, 8f32, 9f32]]
+        );
+        assert_eq!(
+            m1.to_cols_array_2d(),
+            [[1f32, 4f32, 7f32], [2f32, 5f32, 8f32], [3f32, 6f32, 9f32]]
+        );
+
+        m1.set_row(0, Vector3::from([0f32; 3]));
+        m1.set_col(2, Vector3::from([-1f32; 3]));
+        assert_eq!(
+            m1,
+            Matrix3x3::from([0f32, 0f32, -1f32, 4f32, 5f32, -1f32, 7f32, 8f32, -1f32])
+        );
+    }
+
+    #[test]
+    fn test_matrix_cols_translation() {
+        // a translation lives in the last column of the (row-major) Matrix,
+        // and in the last 4 elements of the column-major array
+        let mut m1 = Matrix4x4::ident();
+        m1.set_col(3, Vector4::from([1f32, 2f32, 3f32, 1f32]));
+
+        assert_eq!(m1[0][3], 1f32);
+        assert_eq!(m1.to_cols_array()[12..], [1f32, 2f32, 3f32, 1f32]);
+        assert_eq!(m1.to_cols_array_2d()[3], [1f32, 2f32, 3f32, 1f32]);
+    }
+
     #[test]
     fn test_matrix_mul_vector() {
         let m1 = Matrix4x4::from([
@@ -167,13 +312,52 @@ mod test_matrix {
         assert_eq!(m1.powi(1), m1);
         assert_eq!(m1.powi(10), ans_pow10);
     }
+
+    #[test]
+    fn test_matrix4x4_to_matrix3x3() {
+        let m4 = Matrix4x4::from([
+            1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32, 10f32, 11f32, 12f32, 13f32,
+            14f32, 15f32, 16f32,
+        ]);
+        let v = Vector3::from([1f32, -2f32, 0.5f32]);
+        let v4 = m4.mul_vector(Vector4::from((v, 0f32)));
+
+        assert!(m4.to_matrix3x3().eq_exact(Matrix3x3::from([
+            1f32, 2f32, 3f32, 5f32, 6f32, 7f32, 9f32, 10f32, 11f32
+        ])));
+        assert_eq!(
+            m4.to_matrix3x3().mul_vector(v),
+            Vector3::from([v4[0], v4[1], v4[2]])
+        );
+    }
+
+    #[test]
+    fn test_matrix_cast() {
+        let m2 = Matrix2x2::from([1f32, 2f32, 3f32, 4f32]);
+        let m3 = Matrix3x3::from([
+            0.1f64, 0.2f64, 0.3f64, 0.4f64, 0.5f64, 0.6f64, 0.7f64, 0.8f64, 0.9f64,
+        ]);
+        let m4 = Matrix4x4::<f32>::ident();
+
+        assert_eq!(
+            Matrix2x2::<f64>::from(m2),
+            Matrix2x2::from([1f64, 2f64, 3f64, 4f64])
+        );
+        assert_eq!(m3.cast::<f32>()[1], Vector3::from([0.4f32, 0.5f32, 0.6f32]));
+        assert_eq!(m3.try_cast::<f32>(), Some(m3.cast::<f32>()));
+        assert_eq!(m3.mul_scalar(1e300f64).try_cast::<f32>(), None);
+        assert_eq!(Matrix4x4::<f64>::from(m4), Matrix4x4::ident());
+        assert_eq!(Matrix4x4::<f64>::from(m4).cast::<f32>(), m4);
+    }
 }
+
 #[cfg(test)]
 mod test_vector {
     use std::f32::consts::PI;
 
     use approx::assert_relative_eq;
 
+    use super::test_util::{self, rand_vector2, rand_vector3};
     use super::vector::*;
     #[test]
     fn test_vectors_basic() {
@@ -236,6 +420,102 @@ mod test_vector {
         assert_eq!(v2.norm(), Vector2::from([11f32 / 61f32, 60f32 / 61f32]));
     }
 
+    #[test]
+    fn test_vectors_try_normalize() {
+        let v1 = Vector2::from([3f32, 4f32]);
+        let v_origin = Vector2::from([0f32; 2]);
+        // subnormal, and its squared length underflows to 0
+        let v_tiny = Vector2::from([f32::MIN_POSITIVE * 1e-3f32, 0f32]);
+        // its squared length overflows
+        let v_huge = Vector2::from([3e30f32, 4e30f32]);
+        let fallback = Vector2::from([1f32, 0f32]);
+
+        assert_relative_eq!(v1.length_squared(), 25f32);
+        assert_eq!(v1.try_normalize(), Some(Vector2::from([0.6f32, 0.8f32])));
+        assert_eq!(v_origin.try_normalize(), None);
+        assert_eq!(v_tiny.length_squared(), 0f32);
+        assert!(v_tiny.try_normalize().unwrap().eq_exact(fallback));
+        assert_eq!(v_huge.length_squared(), f32::INFINITY);
+        assert_eq!(
+            v_huge.try_normalize(),
+            Some(Vector2::from([0.6f32, 0.8f32]))
+        );
+        assert!(Vector2::from([f32::MAX, f32::MAX])
+            .try_normalize()
+            .is_some());
+        assert_eq!(
+            Vector2::from([f32::MAX, f32::INFINITY]).try_normalize(),
+            None
+        );
+        assert_eq!(Vector2::from([-0f32, 0f32]).try_normalize(), None);
+        assert_eq!(Vector2::from([f32::INFINITY, 0f32]).try_normalize(), None);
+        assert_eq!(Vector2::from([f32::NAN, 0f32]).try_normalize(), None);
+        assert_eq!(v_origin.normalize_or(fallback), fallback);
+        assert_eq!(v1.normalize_or(fallback), Vector2::from([0.6f32, 0.8f32]));
+        assert_eq!(v_origin.normalize_or_zero(), v_origin);
+        assert!(!v1.is_normalized(1e-6));
+        assert!(fallback.is_normalized(0f32));
+    }
+
+    #[test]
+    fn test_vectors_try_normalize_property() {
+        use rand::Rng;
+
+        let mut rng = test_util::rng();
+        for _ in 0..1000 {
+            let scale = 10f64.powi(rng.gen_range(-20..20));
+            let v2 = rand_vector2(&mut rng, 1f64).mul_scalar(scale);
+            let v3 = rand_vector3(&mut rng, 1f64).mul_scalar(scale);
+            let v4 = Vector4::from((v3, rng.gen_range(-1f64..1f64) * scale));
+
+            let n2 = v2.try_normalize().unwrap();
+            let n3 = v3.try_normalize().unwrap();
+            let n4 = v4.try_normalize().unwrap();
+
+            assert!(n2.is_normalized(4f64 * f64::EPSILON));
+            assert!(n3.is_normalized(4f64 * f64::EPSILON));
+            assert!(n4.is_normalized(4f64 * f64::EPSILON));
+            // norm is not biased towards the origin
+            assert!(v2.norm().eq_exact(n2));
+            assert!(v3.norm().eq_exact(n3));
+            assert!(v4.norm().eq_exact(n4));
+            // direction is kept
+            assert_relative_eq!(n3.mul_inner(v3), v3.len(), max_relative = 1e-12);
+            assert_relative_eq!(n3.len(), 1f64, max_relative = 4f64 * f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_vectors_try_normalize_property_f32() {
+        use rand::Rng;
+
+        let mut rng = test_util::rng();
+        let mut checked = 0;
+        for _ in 0..2000 {
+            // from subnormal to near f32::MAX, where the squared length under- or overflows
+            let scale = 10f32.powi(rng.gen_range(-42..38));
+            let v3 = rand_vector3(&mut rng, 1f32).mul_scalar(scale);
+            let v4 = Vector4::from((v3, rng.gen_range(-1f32..1f32) * scale));
+            if v3.eq_exact(Vector3::from([0f32; 3])) {
+                assert_eq!(v3.try_normalize(), None);
+                assert!(v3.norm().eq_exact(v3));
+                continue;
+            }
+            checked += 1;
+
+            let n3 = v3.try_normalize().unwrap();
+            let n4 = v4.try_normalize().unwrap();
+            assert!(n3.is_normalized(4f32 * f32::EPSILON), "{v3:?} {n3:?}");
+            assert!(n4.is_normalized(4f32 * f32::EPSILON), "{v4:?} {n4:?}");
+            // the same direction as normalizing in f64, where nothing overflows
+            let expected = v3.cast::<f64>().try_normalize().unwrap();
+            assert!(n3.cast::<f64>().dist(expected) < 1e-6, "{v3:?} {n3:?}");
+            assert!(v3.normalize_or_zero().eq_exact(n3));
+            assert!(v3.norm().eq_exact(n3));
+        }
+        assert!(checked > 1900);
+    }
+
     #[test]
     fn test_vectors_mul_inner() {
         let theta = PI / 4f32;
@@ -280,4 +560,3655 @@ mod test_vector {
         assert_eq!(v1.lerp(v2, 1f32), v2);
         assert_eq!(v1.lerp(v2, 0.5f32), Vector2::from([2f32, 2f32]));
     }
+
+    #[test]
+    fn test_vectors_elem() {
+        let v1 = Vector3::from([1f32, -2f32, 3f32]);
+        let v2 = Vector3::from([4f32, 5f32, -6f32]);
+
+        assert_eq!(v1.mul_elem(v2), Vector3::from([4f32, -10f32, -18f32]));
+        assert_eq!(v1.div_elem(v2), Vector3::from([0.25f32, -0.4f32, -0.5f32]));
+        assert_eq!(v1.mul_elem(v2).div_elem(v2), v1);
+        assert_eq!(v1.min(v2), Vector3::from([1f32, -2f32, -6f32]));
+        assert_eq!(v1.max(v2), Vector3::from([4f32, 5f32, 3f32]));
+        assert_eq!(
+            v2.clamp(Vector3::from([0f32; 3]), Vector3::from([2f32; 3])),
+            Vector3::from([2f32, 2f32, 0f32])
+        );
+        assert_eq!(
+            v1.zip_map(v2, |ca, cb| ca + 2f32 * cb),
+            Vector3::from([9f32, 8f32, -9f32])
+        );
+    }
+
+    #[test]
+    fn test_vectors_elem_math() {
+        let v1 = Vector4::from([-1.5f32, 0.25f32, 2.5f32, -0.75f32]);
+
+        assert_eq!(v1.abs(), Vector4::from([1.5f32, 0.25f32, 2.5f32, 0.75f32]));
+        assert_eq!(v1.floor(), Vector4::from([-2f32, 0f32, 2f32, -1f32]));
+        assert_eq!(v1.ceil(), Vector4::from([-1f32, 1f32, 3f32, -0f32]));
+        assert_eq!(v1.round(), Vector4::from([-2f32, 0f32, 3f32, -1f32]));
+        assert_eq!(
+            v1.fract(),
+            Vector4::from([-0.5f32, 0.25f32, 0.5f32, -0.75f32])
+        );
+        assert_eq!(v1.signum(), Vector4::from([-1f32, 1f32, 1f32, -1f32]));
+        assert_eq!(
+            v1.recip(),
+            Vector4::from([-2f32 / 3f32, 4f32, 0.4f32, -4f32 / 3f32])
+        );
+        assert_eq!(
+            v1.abs().sqrt(),
+            Vector4::from([1.5f32.sqrt(), 0.5f32, 2.5f32.sqrt(), 0.75f32.sqrt()])
+        );
+        assert_eq!(
+            Vector2::from([2f32, 3f32]).powf(3f32),
+            Vector2::from([8f32, 27f32])
+        );
+        assert_eq!(v1.map(|ca| ca * 2f32), v1.mul_scalar(2f32));
+    }
+
+    #[test]
+    fn test_vectors_reduce() {
+        let v1 = Vector4::from([-1.5f32, 0.25f32, 2.5f32, -0.75f32]);
+
+        assert_relative_eq!(v1.min_element(), -1.5f32);
+        assert_relative_eq!(v1.max_element(), 2.5f32);
+        assert_relative_eq!(v1.sum(), 0.5f32);
+        assert_relative_eq!(v1.product(), 0.703125f32);
+        assert_relative_eq!(Vector2::from([3f32, 4f32]).product(), 12f32);
+    }
+
+    #[test]
+    fn test_vectors_reflect_refract() {
+        let incident = Vector2::from([1f32, -1f32]).norm();
+        let normal = Vector2::from([0f32, 1f32]);
+
+        assert_eq!(incident.reflect(normal), Vector2::from([1f32, 1f32]).norm());
+        // eta of 1 passes straight through
+        assert_eq!(incident.refract(normal, 1f32), Some(incident));
+
+        // going from glass (1.5) into air (1.0) past the critical angle
+        let grazing = Vector3::from([0.9f32, -(1f32 - 0.81f32).sqrt(), 0f32]);
+        let normal3 = Vector3::from([0f32, 1f32, 0f32]);
+        assert_eq!(grazing.refract(normal3, 1.5f32), None);
+
+        // Snell's law: sin(theta_t) = eta * sin(theta_i)
+        let refracted = incident.refract(normal, 1f32 / 1.5f32).unwrap();
+        assert_relative_eq!(refracted.len(), 1f32, epsilon = 1e-6);
+        assert_relative_eq!(refracted[0], incident[0] / 1.5f32, epsilon = 1e-6);
+        assert!(refracted[1] < 0f32);
+    }
+
+    #[test]
+    fn test_vectors_faceforward_project() {
+        let normal = Vector3::from([0f32, 1f32, 0f32]);
+        let incident = Vector3::from([1f32, -1f32, 0f32]);
+
+        assert_eq!(normal.faceforward(incident, normal), normal);
+        assert_eq!(
+            normal.faceforward(incident.mul_scalar(-1f32), normal),
+            normal.mul_scalar(-1f32)
+        );
+
+        let v1 = Vector3::from([3f32, 4f32, 5f32]);
+        let onto = Vector3::from([2f32, 0f32, 0f32]);
+        assert_eq!(v1.project_onto(onto), Vector3::from([3f32, 0f32, 0f32]));
+        assert_eq!(v1.reject_from(onto), Vector3::from([0f32, 4f32, 5f32]));
+        assert_relative_eq!(v1.distance_squared(onto), 42f32);
+    }
+
+    #[test]
+    fn test_vectors_step_mix() {
+        let v1 = Vector4::from([0f32, 0.5f32, 1.5f32, 3f32]);
+        let edge0 = Vector4::from([1f32; 4]);
+        let edge1 = Vector4::from([3f32; 4]);
+
+        assert_eq!(v1.step(edge0), Vector4::from([0f32, 0f32, 1f32, 1f32]));
+        assert_eq!(
+            v1.smoothstep(edge0, edge1),
+            Vector4::from([0f32, 0f32, 0.15625f32, 1f32])
+        );
+        assert_eq!(
+            edge0.mix(edge1, Vector4::from([0f32, 0.25f32, 0.5f32, 1f32])),
+            Vector4::from([1f32, 1.5f32, 2f32, 3f32])
+        );
+        assert_eq!(
+            Vector2::from([4f32, 0.25f32]).inversesqrt(),
+            Vector2::from([0.5f32, 2f32])
+        );
+    }
+
+    #[test]
+    fn test_vectors_cast() {
+        let v2 = Vector2::from([0.1f64, -2f64]);
+        let v3 = Vector3::from([1f32, 2.5f32, -3f32]);
+        let v4 = Vector4::from([1f64, 2f64, 3f64, 1e300f64]);
+
+        assert_eq!(v2.cast::<f32>(), Vector2::from([0.1f32, -2f32]));
+        assert_eq!(v2.try_cast::<f32>(), Some(Vector2::from([0.1f32, -2f32])));
+        assert_eq!(
+            Vector3::<f64>::from(v3),
+            Vector3::from([1f64, 2.5f64, -3f64])
+        );
+        assert_eq!(v3.cast::<f64>().cast::<f32>(), v3);
+        // float to float is lossy, too large values saturate to infinity,
+        // which try_cast refuses
+        assert_eq!(v4.cast::<f32>()[3], f32::INFINITY);
+        assert_eq!(v4.try_cast::<f32>(), None);
+        assert_eq!(Vector2::from([-1e39f64, 0f64]).try_cast::<f32>(), None);
+        // but infinities that were already there are kept
+        let v_inf = Vector2::from([f64::INFINITY, 1f64]).try_cast::<f32>();
+        assert_eq!(v_inf.unwrap()[0], f32::INFINITY);
+        assert!(Vector2::from([f64::NAN, 0f64]).cast::<f32>()[0].is_nan());
+    }
+
+    #[test]
+    fn test_eq_exact() {
+        let v1 = Vector3::from([1f32, 2f32, 3f32]);
+        let v2 = v1.add(Vector3::from([f32::EPSILON; 3]));
+        assert_eq!(v1, v2);
+        assert!(!v1.eq_exact(v2));
+        assert!(v1.eq_exact(Vector3::from([1f32, 2f32, 3f32])));
+
+        let m1 = crate::matrix::Matrix3x3::from_rows([v1, v1, v1]);
+        let m2 = crate::matrix::Matrix3x3::from_rows([v1, v2, v1]);
+        assert_eq!(m1, m2);
+        assert!(!m1.eq_exact(m2));
+        assert!(m1.eq_exact(m1));
+    }
+}
+#[cfg(all(test, feature = "quaternions"))]
+mod test_quaternion {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    use approx::assert_relative_eq;
+
+    use crate::{
+        matrix::Matrix3x3,
+        quaternions::Quaternion,
+        test_util::{self, rand_vector4},
+        vector::{Vector3, Vector4},
+    };
+
+    #[test]
+    fn test_quaternion_basic() {
+        let q1 = Quaternion::from([1f32, 2f32, 3f32, 4f32]);
+        let q2 = Quaternion::from_wxyz(4f32, 1f32, 2f32, 3f32);
+        let q3 = Quaternion::from((Vector3::from([1f32, 2f32, 3f32]), 4f32));
+        let q4 = Quaternion::from_scalar_vector(4f32, Vector3::from([1f32, 2f32, 3f32]));
+
+        assert_eq!(q1, q2);
+        assert_eq!(q1, q3);
+        assert_eq!(q1, q4);
+        assert_eq!((q1.x(), q1.y(), q1.z(), q1.w()), (1f32, 2f32, 3f32, 4f32));
+        assert_eq!(q1.to_wxyz(), [4f32, 1f32, 2f32, 3f32]);
+        assert_eq!(q1.to_xyzw(), [1f32, 2f32, 3f32, 4f32]);
+        assert_eq!(q1.separate(), (Vector3::from([1f32, 2f32, 3f32]), 4f32));
+        assert_eq!(Vector4::from(q1), Vector4::from([1f32, 2f32, 3f32, 4f32]));
+        assert_eq!(q1.into_iter().sum::<f32>(), 10f32);
+    }
+
+    #[test]
+    fn test_quaternion_add_sub_scalar() {
+        let q1 = Quaternion::from([1f32, 2f32, 3f32, 4f32]);
+        let q2 = Quaternion::from([-4f32, 0.5f32, 7f32, -1f32]);
+
+        assert_eq!(q1.add(q2), Quaternion::from([-3f32, 2.5f32, 10f32, 3f32]));
+        assert_eq!(q1.add(q2).sub(q2), q1);
+        assert_eq!(
+            q1.mul_scalar(2f32),
+            Quaternion::from([2f32, 4f32, 6f32, 8f32])
+        );
+        assert_eq!(q1.mul_scalar(2f32).div_scalar(2f32), q1);
+    }
+
+    #[test]
+    fn test_quaternion_len_norm() {
+        let q1 = Quaternion::from([1f32, 2f32, 2f32, 4f32]);
+
+        assert_relative_eq!(q1.len(), 5f32);
+        assert_relative_eq!(q1.length(), 5f32);
+        assert_relative_eq!(q1.length_squared(), 25f32);
+        assert_relative_eq!(q1.dot(q1), q1.mul_inner(q1));
+        assert!(!q1.is_normalized(1e-6));
+        assert!(q1.normalize().is_normalized(1e-6));
+        assert!(Quaternion::<f32>::identity().is_normalized(0f32));
+    }
+
+    #[test]
+    fn test_quaternion_try_normalize() {
+        let q1 = Quaternion::from([1f32, 2f32, 2f32, 4f32]);
+
+        assert_eq!(
+            q1.try_normalize(),
+            Some(Quaternion::from([0.2f32, 0.4f32, 0.4f32, 0.8f32]))
+        );
+        assert!(q1.try_normalize().unwrap().is_normalized(1e-6));
+        assert_eq!(Quaternion::from([0f32; 4]).try_normalize(), None);
+        // the squared lengths overflow and underflow
+        let q_huge = q1.mul_scalar(1e36f32);
+        assert!(q_huge.try_normalize().unwrap().is_normalized(1e-6));
+        let q_tiny = Quaternion::from([1e-40f32, 0f32, 0f32, 0f32]);
+        assert!(q_tiny.try_normalize().unwrap().is_normalized(0f32));
+        assert_eq!(
+            Quaternion::from([0f32; 4]).normalize(),
+            Quaternion::from([0f32; 4])
+        );
+    }
+
+    #[test]
+    fn test_quaternion_normalize_property() {
+        use rand::Rng;
+
+        let mut rng = test_util::rng();
+        for _ in 0..1000 {
+            // the squared length under- or overflows at the ends of the range
+            let scale = 10f32.powi(rng.gen_range(-42..38));
+            let q = Quaternion::from(rand_vector4(&mut rng, 1f32).mul_scalar(scale));
+            let Some(expected) = q.try_normalize() else {
+                continue;
+            };
+            assert!(q.normalize().is_normalized(4f32 * f32::EPSILON), "{q:?}");
+            assert!(q.normalize().eq_exact(expected));
+
+            let (q1, q2) = (
+                Quaternion::from(rand_vector4(&mut rng, 1f32)).normalize(),
+                Quaternion::from(rand_vector4(&mut rng, 1f32)).normalize(),
+            );
+            let t = rng.gen_range(0f32..1f32);
+            assert!(q1.nlerp(q2, t).is_normalized(4f32 * f32::EPSILON));
+        }
+    }
+
+    #[test]
+    fn test_quaternion_mul_conjugate_inverse() {
+        let i = Quaternion::from_wxyz(0f32, 1f32, 0f32, 0f32);
+        let j = Quaternion::from_wxyz(0f32, 0f32, 1f32, 0f32);
+        let k = Quaternion::from_wxyz(0f32, 0f32, 0f32, 1f32);
+        let minus_one = Quaternion::identity().mul_scalar(-1f32);
+
+        // i^2 = j^2 = k^2 = ijk = -1
+        assert_eq!(i.mul(i), minus_one);
+        assert_eq!(j.mul(j), minus_one);
+        assert_eq!(k.mul(k), minus_one);
+        assert_eq!(i.mul(j).mul(k), minus_one);
+
+        let q1 = Quaternion::from([1f32, 2f32, 3f32, 4f32]);
+        assert_eq!(
+            q1.conjugate(),
+            Quaternion::from([-1f32, -2f32, -3f32, 4f32])
+        );
+        assert!(q1
+            .mul(q1.inverse().unwrap())
+            .eq_fast(Quaternion::identity(), 1e-6));
+        assert_eq!(Quaternion::from([0f32; 4]).inverse(), None);
+    }
+
+    #[test]
+    fn test_quaternion_rotation() {
+        let axis = Vector3::from([0f32, 0f32, 2f32]);
+        let q = Quaternion::from_axis_angle(axis, FRAC_PI_2);
+        let v = Vector3::from([1f32, 0f32, 0f32]);
+
+        assert!(q
+            .rotate_vector(v)
+            .eq_fast(Vector3::from([0f32, 1f32, 0f32]), 1e-6));
+        assert!(q
+            .to_matrix3x3()
+            .mul_vector(v)
+            .eq_fast(Vector3::from([0f32, 1f32, 0f32]), 1e-6));
+        assert!(q
+            .to_matrix4x4()
+            .mul_vector(Vector4::from((v, 1f32)))
+            .eq_fast(Vector4::from([0f32, 1f32, 0f32, 1f32]), 1e-6));
+
+        let (res_axis, res_angle) = q.to_axis_angle();
+        assert!(res_axis.eq_fast(Vector3::from([0f32, 0f32, 1f32]), 1e-6));
+        assert_relative_eq!(res_angle, FRAC_PI_2, epsilon = 1e-6);
+
+        assert_eq!(
+            Quaternion::<f32>::identity().to_matrix3x3(),
+            Matrix3x3::ident()
+        );
+    }
+
+    #[test]
+    fn test_quaternion_slerp() {
+        let axis = Vector3::from([0f32, 1f32, 0f32]);
+        let q1 = Quaternion::identity();
+        let q2 = Quaternion::from_axis_angle(axis, PI / 2f32);
+        let half = Quaternion::from_axis_angle(axis, PI / 4f32);
+
+        assert!(q1.slerp(q2, 0f32).eq_fast(q1, 1e-6));
+        assert!(q1.slerp(q2, 1f32).eq_fast(q2, 1e-6));
+        assert!(q1.slerp(q2, 0.5f32).eq_fast(half, 1e-6));
+        assert!(q1.nlerp(q2, 0.5f32).eq_fast(half, 1e-6));
+        // takes the shortest path
+        assert!(q1.slerp(q2.mul_scalar(-1f32), 0.5f32).eq_fast(half, 1e-6));
+    }
+
+    #[test]
+    fn test_quaternion_display() {
+        let q1 = Quaternion::from([1f32, 2.5f32, -3f32, 4f32]);
+
+        assert_eq!(format!("{q1}"), "(1, 2.5, -3, 4)");
+        assert_eq!(format!("{q1:.2}"), "(1.00, 2.50, -3.00, 4.00)");
+    }
+
+    #[test]
+    fn test_quaternion_cast() {
+        let q = Quaternion::from([0.1f32, 0.2f32, 0.3f32, 0.4f32]);
+        let q64: Quaternion<f64> = q.into();
+
+        assert_eq!(q64, q.cast::<f64>());
+        assert_eq!(q64.x(), 0.1f32 as f64);
+        assert_eq!(q64.try_cast::<f32>(), Some(q));
+        assert_eq!(q64.mul_scalar(1e300f64).try_cast::<f32>(), None);
+        assert_eq!(q64.cast::<f32>(), q);
+    }
+
+    #[test]
+    fn test_quaternion_euler() {
+        use rand::Rng;
+
+        let x_axis = Vector3::from([1f64, 0f64, 0f64]);
+        let y_axis = Vector3::from([0f64, 1f64, 0f64]);
+        let z_axis = Vector3::from([0f64, 0f64, 1f64]);
+
+        let mut rng = test_util::rng();
+        for _ in 0..1000 {
+            let x = rng.gen_range(-3.1f64..3.1f64);
+            let y = rng.gen_range(-1.5f64..1.5f64);
+            let z = rng.gen_range(-3.1f64..3.1f64);
+            let q = Quaternion::from_euler(x, y, z);
+            let composed = Quaternion::from_axis_angle(z_axis, z)
+                .mul(Quaternion::from_axis_angle(y_axis, y))
+                .mul(Quaternion::from_axis_angle(x_axis, x));
+
+            assert!(q.eq_fast(composed, 1e-12));
+            let (ex, ey, ez) = q.to_euler();
+            assert_relative_eq!(ex, x, epsilon = 1e-9);
+            assert_relative_eq!(ey, y, epsilon = 1e-9);
+            assert_relative_eq!(ez, z, epsilon = 1e-9);
+        }
+    }
+}
+#[cfg(all(test, feature = "approx"))]
+mod test_approx {
+    use approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, assert_ulps_eq};
+
+    use crate::{
+        matrix::{Matrix2x2, Matrix4x4},
+        vector::{Vector2, Vector3},
+    };
+
+    #[test]
+    fn test_approx_vector() {
+        let v1 = Vector3::from([1e6f32, 2e6f32, 3e6f32]);
+        let v2 = Vector3::from([1e6f32 + 0.0625f32, 2e6f32, 3e6f32]);
+
+        // relative comparisons scale with the magnitude
+        assert_relative_eq!(v1, v2);
+        assert_ulps_eq!(v1, v2);
+        assert_abs_diff_eq!(v1, v2, epsilon = 0.1f32);
+
+        let v3 = Vector2::from([1e-8f32, 0f32]);
+        let v4 = Vector2::from([2e-8f32, 0f32]);
+        assert_relative_ne!(v3, v4, epsilon = 0f32);
+        assert_abs_diff_eq!(v3, v4);
+    }
+
+    #[test]
+    fn test_approx_matrix() {
+        let m1 = Matrix2x2::from([1f64, 2f64, 3f64, 4f64]);
+        assert_relative_eq!(m1.inverse().unwrap().mul_matrix(m1), Matrix2x2::ident());
+
+        let m2 = Matrix4x4::from([
+            1f32, 2f32, 3f32, 4f32, -1f32, 5f32, 9f32, 0f32, 0f32, 1f32, 2f32, 3f32, 4f32, 5f32,
+            6f32, 9f32,
+        ]);
+        assert_relative_eq!(
+            m2.inverse().unwrap().mul_matrix(m2),
+            Matrix4x4::ident(),
+            epsilon = 1e-5
+        );
+        assert_relative_ne!(m2, Matrix4x4::ident());
+    }
+
+    #[cfg(feature = "quaternions")]
+    #[test]
+    fn test_approx_quaternion() {
+        use crate::quaternions::Quaternion;
+        use std::f64::consts::PI;
+
+        let q = Quaternion::from_axis_angle(Vector3::from([1f64, 1f64, 0f64]), PI / 3f64);
+        assert_relative_eq!(q.mul(q.conjugate()), Quaternion::identity());
+        assert_ulps_eq!(q.conjugate().conjugate(), q);
+    }
+}
+#[cfg(all(test, feature = "simd"))]
+mod test_simd {
+    use crate::{
+        matrix::Matrix4x4,
+        simd,
+        test_util::{self, rand_matrix4x4, rand_vector4},
+        vector::Vector4,
+    };
+
+    /// The rounding error allowed between two ways of summing the products
+    /// of a and b, which is a few ulps of the sum of their magnitudes
+    fn dot_tolerance(a: [f32; 4], b: [f32; 4]) -> f32 {
+        8f32 * f32::EPSILON * (0..4).map(|cx| (a[cx] * b[cx]).abs()).sum::<f32>()
+    }
+
+    #[test]
+    fn test_simd_against_scalar() {
+        let mut rng = test_util::rng();
+        for _ in 0..1000 {
+            let (m1, m2) = (
+                rand_matrix4x4(&mut rng, 10f32),
+                rand_matrix4x4(&mut rng, 10f32),
+            );
+            let (v1, v2) = (rand_vector4(&mut rng, 10f32), rand_vector4(&mut rng, 10f32));
+
+            let dot = simd::mul_inner4(v1, v2);
+            assert!((dot - simd::scalar::mul_inner4(v1, v2)).abs() <= dot_tolerance(v1.0, v2.0));
+
+            let mv = simd::mul_vector4x4(m1, v1);
+            let mv_scalar = simd::scalar::mul_vector4x4(m1, v1);
+            for cx in 0..4 {
+                assert!((mv[cx] - mv_scalar[cx]).abs() <= dot_tolerance(m1[cx].0, v1.0));
+            }
+
+            let mm = simd::mul_matrix4x4(m1, m2);
+            let mm_scalar = simd::scalar::mul_matrix4x4(m1, m2);
+            for cx in 0..4 {
+                for cy in 0..4 {
+                    let tolerance = dot_tolerance(m1[cx].0, m2.col(cy).0);
+                    assert!((mm[cx][cy] - mm_scalar[cx][cy]).abs() <= tolerance);
+                }
+            }
+
+            // the generic methods forward to the kernels, bit for bit
+            assert_eq!(v1.mul_inner(v2).to_bits(), dot.to_bits());
+            assert!(m1.mul_vector(v1).eq_exact(mv));
+            assert!(m1.mul_matrix(m2).eq_exact(mm));
+        }
+    }
+
+    #[test]
+    fn test_simd_exact_values() {
+        let m1 = Matrix4x4::from([
+            1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32, 10f32, 11f32, 12f32, 13f32,
+            14f32, 15f32, 16f32,
+        ]);
+        let v1 = Vector4::from([5f32, 6f32, 7f32, 8f32]);
+
+        // small integers are exact with or without fma
+        assert_eq!(
+            simd::mul_vector4x4(m1, v1).0,
+            [70f32, 174f32, 278f32, 382f32]
+        );
+        assert_eq!(
+            simd::scalar::mul_vector4x4(m1, v1).0,
+            [70f32, 174f32, 278f32, 382f32]
+        );
+        assert_eq!(simd::mul_inner4(v1, v1), 174f32);
+        assert_eq!(simd::scalar::mul_inner4(v1, v1), 174f32);
+        assert_eq!(
+            simd::mul_matrix4x4(m1, Matrix4x4::ident())
+                .0
+                .map(|row| row.0),
+            m1.0.map(|row| row.0)
+        );
+    }
+
+    #[cfg(feature = "quaternions")]
+    #[test]
+    fn test_simd_quaternion() {
+        use crate::quaternions::Quaternion;
+
+        let mut rng = test_util::rng();
+        for _ in 0..1000 {
+            let q1 = Quaternion::from(rand_vector4(&mut rng, 10f32));
+            let q2 = Quaternion::from(rand_vector4(&mut rng, 10f32));
+
+            let product = simd::mul_quaternion(q1, q2);
+            let product_scalar = simd::scalar::mul_quaternion(q1, q2);
+            // each element sums one product of every element of q1
+            // with some element of q2
+            let q2_max = q2.into_iter().fold(0f32, |acc, ca| acc.max(ca.abs()));
+            let tolerance = dot_tolerance(q1.to_xyzw(), [q2_max; 4]);
+            for cx in 0..4 {
+                assert!((product[cx] - product_scalar[cx]).abs() <= tolerance);
+            }
+            assert!(q1.mul(q2).eq_exact(product));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_wide {
+    use crate::{
+        matrix::{Matrix3x3, Matrix4x4},
+        vector::{Vector3, Vector4},
+        wide::{Vector3x4, Vector3x8},
+    };
+
+    fn vectors<const N: usize>() -> [Vector3<f32>; N] {
+        let mut cx = 0f32;
+        [0; N].map(|_| {
+            cx += 1f32;
+            Vector3::from([cx, -2f32 * cx, 0.5f32 + cx * cx])
+        })
+    }
+
+    #[test]
+    fn test_wide_conversion() {
+        let data = vectors::<4>();
+        let wide = Vector3x4::from(data);
+
+        assert_eq!(wide[0], [1f32, 2f32, 3f32, 4f32]);
+        assert_eq!(wide.lane(2), data[2]);
+        assert_eq!(<[Vector3<f32>; 4]>::from(wide), data);
+
+        let mut out = [Vector3::from([0f32; 3]); 10];
+        Vector3x8::from_slice(&vectors::<10>()).write_to_slice(&mut out);
+        assert_eq!(out[..8], vectors::<8>());
+        assert_eq!(out[8], Vector3::from([0f32; 3]));
+
+        let mut splat = Vector3x4::splat(data[0]);
+        splat.set_lane(3, data[3]);
+        assert_eq!(splat.lane(0), data[0]);
+        assert_eq!(splat.lane(3), data[3]);
+    }
+
+    #[test]
+    fn test_wide_against_vector3() {
+        let a = vectors::<8>();
+        let mut b = vectors::<8>();
+        b.reverse();
+        let (wa, wb) = (Vector3x8::from(a), Vector3x8::from(b));
+
+        let add = <[Vector3<f32>; 8]>::from(wa.add(wb));
+        let sub = <[Vector3<f32>; 8]>::from(wa.sub(wb));
+        let cross = <[Vector3<f32>; 8]>::from(wa.mul_cross(wb));
+        let norm = <[Vector3<f32>; 8]>::from(wa.norm());
+        let scaled = <[Vector3<f32>; 8]>::from(wa.mul_scalar(3f32).div_scalar(2f32));
+        let inner = wa.mul_inner(wb);
+        let len = wa.len();
+
+        for cx in 0..8 {
+            assert_eq!(add[cx], a[cx].add(b[cx]));
+            assert_eq!(sub[cx], a[cx].sub(b[cx]));
+            assert_eq!(cross[cx], a[cx].mul_cross(b[cx]));
+            assert_eq!(norm[cx], a[cx].norm());
+            assert_eq!(scaled[cx], a[cx].mul_scalar(3f32).div_scalar(2f32));
+            assert_eq!(inner[cx], a[cx].mul_inner(b[cx]));
+            assert_eq!(len[cx], a[cx].len());
+        }
+    }
+
+    #[test]
+    fn test_wide_normalize_or_zero() {
+        let mut data = vectors::<8>();
+        data[1] = Vector3::from([3e30f32, 4e30f32, 0f32]);
+        data[2] = Vector3::from([3e-30f32, -4e-30f32, 1e-31f32]);
+        data[3] = Vector3::from([0f32; 3]);
+        data[4] = Vector3::from([f32::INFINITY, 0f32, 0f32]);
+        data[5] = Vector3::from([f32::NAN, 1f32, 0f32]);
+        data[6] = Vector3::from([f32::MAX, f32::MAX, -f32::MAX]);
+
+        let normalized = <[Vector3<f32>; 8]>::from(Vector3x8::from(data).normalize_or_zero());
+        for cx in 0..8 {
+            assert!(normalized[cx].eq_exact(data[cx].normalize_or_zero()));
+        }
+        assert!(normalized[1].is_normalized(1e-6));
+        assert!(normalized[2].is_normalized(1e-6));
+        assert!(normalized[6].is_normalized(1e-6));
+    }
+
+    #[test]
+    fn test_wide_mul_matrix() {
+        let m3 = Matrix3x3::from([1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32]);
+        let m4 = Matrix4x4::from([
+            1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32, 10f32, 11f32, 12f32, 0f32, 0f32,
+            0f32, 1f32,
+        ]);
+        let data = vectors::<4>();
+
+        let res3 = <[Vector3<f32>; 4]>::from(m3.mul_vector3x4(Vector3x4::from(data)));
+        let res4 = <[Vector3<f32>; 4]>::from(m4.mul_vector3x4(Vector3x4::from(data)));
+        for cx in 0..4 {
+            assert_eq!(res3[cx], m3.mul_vector(data[cx]));
+            let point = m4.mul_vector(Vector4::from((data[cx], 1f32)));
+            assert_eq!(res4[cx], Vector3::from([point[0], point[1], point[2]]));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_batch {
+    use crate::{
+        matrix::Matrix4x4,
+        test_util::{self, rand_vector3},
+        vector::{Vector3, Vector4},
+    };
+
+    fn rand_vectors(len: usize) -> Vec<Vector3<f32>> {
+        let mut rng = test_util::rng();
+        (0..len).map(|_| rand_vector3(&mut rng, 10f32)).collect()
+    }
+
+    fn matrix() -> Matrix4x4<f32> {
+        Matrix4x4::from([
+            0.5f32, -1f32, 2f32, 4f32, 1f32, 0.25f32, -3f32, -2f32, 0f32, 2f32, 1f32, 7f32, 0f32,
+            0f32, 0f32, 1f32,
+        ])
+    }
+
+    #[test]
+    fn test_batch_transform() {
+        // cover both the chunks of 8 and the remainder
+        for len in [0, 1, 7, 8, 9, 100, 20_003] {
+            let m = matrix();
+            let src = rand_vectors(len);
+            let mut points = vec![Vector3::from([0f32; 3]); len];
+            let mut vectors = vec![Vector3::from([0f32; 3]); len];
+            m.transform_points(&src, &mut points);
+            m.transform_vectors(&src, &mut vectors);
+
+            let mut points_in_place = src.clone();
+            let mut vectors_in_place = src.clone();
+            m.transform_points_in_place(&mut points_in_place);
+            m.transform_vectors_in_place(&mut vectors_in_place);
+
+            for cx in 0..len {
+                let point = m.mul_vector(Vector4::from((src[cx], 1f32)));
+                let vector = m.mul_vector(Vector4::from((src[cx], 0f32)));
+                assert!(points[cx].eq_fast(Vector3::from([point[0], point[1], point[2]]), 1e-4));
+                assert!(vectors[cx].eq_fast(Vector3::from([vector[0], vector[1], vector[2]]), 1e-4));
+                assert_eq!(points_in_place[cx], points[cx]);
+                assert_eq!(vectors_in_place[cx], vectors[cx]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_normalize_all() {
+        let mut src = rand_vectors(21);
+        src[3] = Vector3::from([0f32; 3]);
+        src[20] = Vector3::from([0f32; 3]);
+        let mut dst = vec![Vector3::from([1f32; 3]); 21];
+        Vector3::normalize_all(&src, &mut dst);
+        Vector3::normalize_all_in_place(&mut src);
+
+        assert_eq!(dst, src);
+        assert_eq!(dst[3], Vector3::from([0f32; 3]));
+        assert_eq!(dst[20], Vector3::from([0f32; 3]));
+        assert!(dst
+            .iter()
+            .enumerate()
+            .all(|(cx, ca)| cx == 3 || cx == 20 || ca.is_normalized(1e-6)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_batch_length_mismatch() {
+        let src = rand_vectors(10);
+        let mut dst = vec![Vector3::from([0f32; 3]); 9];
+        matrix().transform_points(&src, &mut dst);
+    }
+
+    #[cfg(feature = "quaternions")]
+    #[test]
+    fn test_batch_rotate_all() {
+        use crate::quaternions::Quaternion;
+
+        let q = Quaternion::from_axis_angle(Vector3::from([1f32, 2f32, 3f32]), 1.2f32);
+        let src = rand_vectors(37);
+        let mut dst = vec![Vector3::from([0f32; 3]); 37];
+        q.rotate_all(&src, &mut dst);
+        let mut in_place = src.clone();
+        q.rotate_all_in_place(&mut in_place);
+
+        for cx in 0..37 {
+            assert!(dst[cx].eq_fast(q.rotate_vector(src[cx]), 1e-4));
+            assert_eq!(in_place[cx], dst[cx]);
+        }
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_batch_bytemuck_buffer() {
+        // a vertex buffer of positions, as plain floats
+        let mut buffer: Vec<f32> = (0..30).map(|cx| cx as f32).collect();
+        let m = matrix();
+        let expected: Vec<Vector3<f32>> = buffer
+            .chunks(3)
+            .map(|ca| m.transform_point(Vector3::from([ca[0], ca[1], ca[2]])))
+            .collect();
+
+        m.transform_points_in_place(bytemuck::cast_slice_mut(&mut buffer));
+
+        let res: &[Vector3<f32>] = bytemuck::cast_slice(&buffer);
+        assert_eq!(res, &expected[..]);
+    }
+}
+
+#[cfg(test)]
+mod test_layout {
+    use std::mem::{offset_of, size_of};
+
+    use crate::{
+        layout::*,
+        matrix::{Matrix2x2, Matrix3x3, Matrix4x4},
+        vector::{Vector2, Vector3, Vector4},
+    };
+
+    // layout(std140) uniform Block {
+    //     float a;      // offset 0
+    //     vec3 b;       // offset 16
+    //     mat3 c;       // offset 32
+    //     vec2 d[2];    // offset 80, stride 16
+    //     mat2 e;       // offset 112
+    //     vec4 f;       // offset 144
+    // };
+    #[repr(C)]
+    struct Block {
+        a: f32,
+        b: Std140Vector3,
+        c: Std140Matrix3x3,
+        d: [Std140ArrayVector2; 2],
+        e: Std140Matrix2x2,
+        f: Std140Vector4,
+    }
+
+    // layout(std430) buffer Block430 {
+    //     vec3 a;       // offset 0
+    //     mat2 b;       // offset 16
+    //     float c[3];   // offset 32, stride 4
+    //     vec3 d;       // offset 48
+    // };
+    #[repr(C)]
+    struct Block430 {
+        a: Std430Vector3,
+        b: Std430Matrix2x2,
+        c: [f32; 3],
+        d: Std430Vector3,
+    }
+
+    #[test]
+    fn test_layout_offsets() {
+        assert_eq!(offset_of!(Block, a), 0);
+        assert_eq!(offset_of!(Block, b), 16);
+        assert_eq!(offset_of!(Block, c), 32);
+        assert_eq!(offset_of!(Block, d), 80);
+        assert_eq!(offset_of!(Block, e), 112);
+        assert_eq!(offset_of!(Block, f), 144);
+        assert_eq!(size_of::<Block>(), 160);
+
+        assert_eq!(offset_of!(Block430, a), 0);
+        assert_eq!(offset_of!(Block430, b), 16);
+        assert_eq!(offset_of!(Block430, c), 32);
+        assert_eq!(offset_of!(Block430, d), 48);
+        assert_eq!(size_of::<Block430>(), 64);
+    }
+
+    #[test]
+    fn test_layout_conversion() {
+        let m2 = Matrix2x2::from([1f32, 2f32, 3f32, 4f32]);
+        let m3 = Matrix3x3::from([1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32]);
+        let m4 = Matrix4x4::from([
+            1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32, 10f32, 11f32, 12f32, 13f32,
+            14f32, 15f32, 16f32,
+        ]);
+
+        // columns are padded to vec4
+        assert_eq!(
+            m2.as_std140().cols,
+            [[1f32, 3f32, 0f32, 0f32], [2f32, 4f32, 0f32, 0f32]]
+        );
+        assert_eq!(m2.as_std430().cols, [[1f32, 3f32], [2f32, 4f32]]);
+        assert_eq!(
+            m3.as_std140().cols,
+            [
+                [1f32, 4f32, 7f32, 0f32],
+                [2f32, 5f32, 8f32, 0f32],
+                [3f32, 6f32, 9f32, 0f32]
+            ]
+        );
+        assert_eq!(m4.as_std140().cols, m4.to_cols_array_2d());
+
+        assert_eq!(Matrix2x2::from(m2.as_std140()), m2);
+        assert_eq!(Matrix2x2::from(m2.as_std430()), m2);
+        assert_eq!(Matrix3x3::from(m3.as_std430()), m3);
+        assert_eq!(Matrix4x4::from(m4.as_std140()), m4);
+
+        let v3 = Vector3::from([1f32, 2f32, 3f32]);
+        let v4 = Vector4::from([1f32, 2f32, 3f32, 4f32]);
+        assert_eq!(Vector3::from(v3.as_std140()), v3);
+        assert_eq!(Vector3::from(v3.as_std430()), v3);
+        assert_eq!(Vector4::from(v4.as_std140()), v4);
+        assert_eq!(2f32.as_std140_array_element().value, 2f32);
+        assert_eq!(
+            Vector2::from([1f32, 2f32]).as_std140_array_element().value,
+            Vector2::from([1f32, 2f32])
+        );
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_layout_bytemuck() {
+        let m3 = Matrix3x3::from([1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32]);
+        let std140 = m3.as_std140();
+        let floats: &[f32] = bytemuck::cast_slice(std::slice::from_ref(&std140));
+
+        assert_eq!(
+            floats,
+            &[1f32, 4f32, 7f32, 0f32, 2f32, 5f32, 8f32, 0f32, 3f32, 6f32, 9f32, 0f32]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_packing {
+    use rand::Rng;
+
+    use crate::{
+        packing::*,
+        test_util::{self, rand_vector3},
+        vector::{Vector2, Vector3, Vector4},
+    };
+
+    #[test]
+    fn test_packing_unorm_snorm() {
+        let mut rng = test_util::rng();
+        for _ in 0..1000 {
+            let v4 = Vector4::from([rng.gen::<f32>(), rng.gen(), rng.gen(), rng.gen()]);
+            let s4 = v4.mul_scalar(2f32).sub(Vector4::from([1f32; 4]));
+            let v2 = Vector2::from([v4[0], v4[1]]);
+            let s2 = Vector2::from([s4[0], s4[1]]);
+
+            assert!(unpack_unorm8x4(pack_unorm8x4(v4)).eq_fast(v4, 0.5f32 / 255f32 + 1e-6));
+            assert!(unpack_snorm8x4(pack_snorm8x4(s4)).eq_fast(s4, 0.5f32 / 127f32 + 1e-6));
+            assert!(unpack_unorm16x2(pack_unorm16x2(v2)).eq_fast(v2, 0.5f32 / 65535f32 + 1e-6));
+            assert!(unpack_snorm16x2(pack_snorm16x2(s2)).eq_fast(s2, 0.5f32 / 32767f32 + 1e-6));
+        }
+
+        // out of range values get clamped
+        assert_eq!(
+            pack_unorm8x4(Vector4::from([-1f32, 2f32, 0f32, 1f32])),
+            0xff00_ff00
+        );
+        assert_eq!(pack_snorm16x2(Vector2::from([-2f32, 2f32])), 0x7fff_8001);
+        assert_eq!(unpack_snorm8x4(0x0000_0080)[0], -1f32);
+        assert_eq!(unpack_snorm16x2(0x8000_8000), Vector2::from([-1f32, -1f32]));
+    }
+
+    #[test]
+    fn test_packing_half() {
+        assert_eq!(f32_to_f16_bits(0f32), 0x0000);
+        assert_eq!(f32_to_f16_bits(-0f32), 0x8000);
+        assert_eq!(f32_to_f16_bits(1f32), 0x3c00);
+        assert_eq!(f32_to_f16_bits(65504f32), 0x7bff);
+        assert_eq!(f32_to_f16_bits(65520f32), 0x7c00);
+        assert_eq!(f32_to_f16_bits(f32::INFINITY), 0x7c00);
+        assert_eq!(f32_to_f16_bits(f32::NEG_INFINITY), 0xfc00);
+        assert_eq!(f32_to_f16_bits(2f32.powi(-24)), 0x0001);
+        assert_eq!(f32_to_f16_bits(2f32.powi(-14)), 0x0400);
+        assert_eq!(f32_to_f16_bits(2f32.powi(-26)), 0x0000);
+        // ties round to even
+        assert_eq!(f32_to_f16_bits(1f32 + 2f32.powi(-11)), 0x3c00);
+        assert_eq!(f32_to_f16_bits(1f32 + 3f32 * 2f32.powi(-11)), 0x3c02);
+        assert!(f16_bits_to_f32(f32_to_f16_bits(f32::NAN)).is_nan());
+
+        // every finite half survives the round trip exactly
+        for bits in 0..=u16::MAX {
+            let value = f16_bits_to_f32(bits);
+            if value.is_finite() {
+                assert_eq!(f32_to_f16_bits(value), bits);
+            }
+        }
+
+        let mut rng = test_util::rng();
+        for _ in 0..1000 {
+            let v = Vector2::from([rng.gen_range(-1000f32..1000f32), rng.gen_range(-1f32..1f32)]);
+            let round_trip = unpack_half2x16(pack_half2x16(v));
+            for cx in 0..2 {
+                // half of an ulp, or half of the subnormal spacing
+                let bound = (v[cx].abs() * 2f32.powi(-11)).max(2f32.powi(-25));
+                assert!((round_trip[cx] - v[cx]).abs() <= bound);
+            }
+        }
+    }
+
+    #[test]
+    fn test_packing_rgb10a2() {
+        assert_eq!(
+            pack_rgb10a2(Vector4::from([1f32, 0f32, 0f32, 1f32])),
+            0xc000_03ff
+        );
+        assert_eq!(
+            unpack_rgb10a2(0xffff_ffff),
+            Vector4::from([1f32, 1f32, 1f32, 1f32])
+        );
+
+        let mut rng = test_util::rng();
+        for _ in 0..1000 {
+            let v = Vector4::from([rng.gen::<f32>(), rng.gen(), rng.gen(), rng.gen()]);
+            let round_trip = unpack_rgb10a2(pack_rgb10a2(v));
+            assert!(Vector3::from([round_trip[0], round_trip[1], round_trip[2]])
+                .eq_fast(Vector3::from([v[0], v[1], v[2]]), 0.5f32 / 1023f32 + 1e-6));
+            assert!((round_trip[3] - v[3]).abs() <= 0.5f32 / 3f32 + 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_packing_octahedral() {
+        let axes = [
+            [1f64, 0f64, 0f64],
+            [-1f64, 0f64, 0f64],
+            [0f64, 1f64, 0f64],
+            [0f64, -1f64, 0f64],
+            [0f64, 0f64, 1f64],
+            [0f64, 0f64, -1f64],
+        ];
+        for axis in axes {
+            let n = Vector3::from(axis);
+            let encoded = octahedral_encode(n);
+            assert!(encoded.abs().max_element() <= 1f64);
+            assert!(octahedral_decode(encoded).eq_fast(n, 1e-12));
+        }
+
+        let mut rng = test_util::rng();
+        for _ in 0..1000 {
+            let n = rand_vector3(&mut rng, 1f32).normalize_or_zero();
+            if n == Vector3::from([0f32; 3]) {
+                continue;
+            }
+
+            let encoded = octahedral_encode(n);
+            assert!(octahedral_decode(encoded).eq_fast(n, 1e-5));
+
+            // stored as snorm16, the angular error stays well below a thousandth of a radian
+            let decoded = octahedral_decode(unpack_snorm16x2(pack_snorm16x2(encoded)));
+            assert!(decoded.mul_inner(n).min(1f32).acos() < 1e-3);
+            assert!((decoded.len() - 1f32).abs() < 1e-5);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "half"))]
+mod test_half {
+    use half::{bf16, f16};
+
+    use crate::{
+        matrix::{Matrix3x3, Matrix4x4},
+        vector::{Vector3, Vector4},
+    };
+
+    #[test]
+    fn test_half_vector() {
+        let v1 = Vector3::from([1f32, 2f32, 3f32]).cast::<f16>();
+        let v2 = Vector3::from([f16::from_f32(4f32), f16::ONE, f16::from_f32(-2f32)]);
+
+        assert_eq!(v1[1], f16::from_f32(2f32));
+        assert_eq!(v1.add(v2).cast::<f32>(), Vector3::from([5f32, 3f32, 1f32]));
+        assert_eq!(v1.mul_inner(v2), f16::from_f32(0f32));
+        assert_eq!(
+            v1.mul_cross(v2).cast::<f32>(),
+            Vector3::from([-7f32, 14f32, -7f32])
+        );
+        assert!((v1.normalize_or_zero().len().to_f32() - 1f32).abs() < 1e-3);
+
+        let b = Vector4::from([1f32, 2f32, 3f32, 4f32]).cast::<bf16>();
+        assert_eq!(b.sum(), bf16::from_f32(10f32));
+        assert_eq!(b.cast::<f64>(), Vector4::from([1f64, 2f64, 3f64, 4f64]));
+
+        // out of range values become infinite
+        assert!(Vector3::from([1e6f32, 0f32, 0f32]).cast::<f16>()[0].is_infinite());
+    }
+
+    #[test]
+    fn test_half_matrix() {
+        let m =
+            Matrix3x3::from([2f32, 0f32, 0f32, 0f32, 4f32, 0f32, 0f32, 0f32, 8f32]).cast::<f16>();
+        let v = Vector3::from([1f32, 1f32, 1f32]).cast::<f16>();
+
+        assert_eq!(
+            m.mul_vector(v).cast::<f32>(),
+            Vector3::from([2f32, 4f32, 8f32])
+        );
+        assert_eq!(m.det(), f16::from_f32(64f32));
+        assert_eq!(
+            m.inverse().unwrap().cast::<f32>(),
+            Matrix3x3::from([0.5f32, 0f32, 0f32, 0f32, 0.25f32, 0f32, 0f32, 0f32, 0.125f32])
+        );
+        assert_eq!(
+            Matrix4x4::<f16>::ident().mul_matrix(Matrix4x4::ident()),
+            Matrix4x4::ident()
+        );
+    }
+
+    #[cfg(feature = "quaternions")]
+    #[test]
+    fn test_half_quaternion() {
+        use crate::quaternions::Quaternion;
+
+        let q = Quaternion::from_axis_angle(
+            Vector3::from([0f32, 0f32, 1f32]),
+            std::f32::consts::FRAC_PI_2,
+        )
+        .cast::<f16>();
+        let rotated = q.rotate_vector(Vector3::from([1f32, 0f32, 0f32]).cast());
+
+        assert!(rotated
+            .cast::<f32>()
+            .eq_fast(Vector3::from([0f32, 1f32, 0f32]), 1e-3));
+        assert_eq!(q.cast::<f32>().cast::<f16>(), q);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_half_bytemuck() {
+        let v = Vector4::from([1f32, -2f32, 0.5f32, 0f32]).cast::<f16>();
+        let bits: [u16; 4] = bytemuck::cast(v);
+
+        assert_eq!(bits, [0x3c00, 0xc000, 0x3800, 0x0000]);
+    }
+}
+
+#[cfg(all(
+    test,
+    any(
+        feature = "mint",
+        feature = "glam",
+        feature = "nalgebra",
+        feature = "cgmath"
+    )
+))]
+mod test_interop {
+    #[cfg(any(feature = "mint", feature = "nalgebra"))]
+    use crate::vector::Vector2;
+    #[cfg(any(feature = "glam", feature = "cgmath"))]
+    use crate::vector::Vector4;
+    use crate::{
+        matrix::{Matrix2x2, Matrix3x3, Matrix4x4},
+        vector::Vector3,
+    };
+
+    // not symmetric, so a missing transpose shows up
+    fn matrix3() -> Matrix3x3<f32> {
+        Matrix3x3::from([1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 10f32])
+    }
+
+    fn matrix4() -> Matrix4x4<f32> {
+        Matrix4x4::from([
+            1f32, 0f32, 0f32, 5f32, 0f32, 2f32, 0f32, 6f32, 0f32, 0f32, 3f32, 7f32, 0f32, 0f32,
+            0f32, 1f32,
+        ])
+    }
+
+    #[cfg(feature = "mint")]
+    #[test]
+    fn test_interop_mint() {
+        let v = Vector3::from([1f32, 2f32, 3f32]);
+        let mv: mint::Vector3<f32> = v.into();
+        assert_eq!((mv.x, mv.y, mv.z), (1f32, 2f32, 3f32));
+        assert_eq!(Vector3::from(mv), v);
+        assert_eq!(
+            Vector2::from(mint::Point2::from(Vector2::from([4f32, 5f32]))),
+            Vector2::from([4f32, 5f32])
+        );
+
+        let m = matrix3();
+        let rows: mint::RowMatrix3<f32> = m.into();
+        let cols: mint::ColumnMatrix3<f32> = m.into();
+        // m[0][1] is row 0, column 1
+        assert_eq!(rows.x.y, 2f32);
+        assert_eq!(cols.y.x, 2f32);
+        assert_eq!(Matrix3x3::from(rows), m);
+        assert_eq!(Matrix3x3::from(cols), m);
+        assert_eq!(
+            Matrix4x4::from(mint::ColumnMatrix4::from(matrix4())),
+            matrix4()
+        );
+        assert_eq!(
+            Matrix2x2::from(mint::RowMatrix2::from([[1f32, 2f32], [3f32, 4f32]])),
+            Matrix2x2::from([1f32, 2f32, 3f32, 4f32])
+        );
+
+        #[cfg(feature = "quaternions")]
+        {
+            use crate::quaternions::Quaternion;
+
+            let q = Quaternion::from([1f32, 2f32, 3f32, 4f32]);
+            let mq: mint::Quaternion<f32> = q.into();
+            assert_eq!((mq.v.x, mq.s), (1f32, 4f32));
+            assert_eq!(Quaternion::from(mq), q);
+        }
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn test_interop_glam() {
+        let v = Vector3::from([1f32, 2f32, 3f32]);
+        assert_eq!(glam::Vec3::from(v), glam::Vec3::new(1f32, 2f32, 3f32));
+        assert_eq!(Vector3::from(glam::Vec3A::from(v)), v);
+        assert_eq!(Vector4::from(glam::DVec4::ONE), Vector4::from([1f64; 4]));
+
+        let m = matrix3();
+        let gm = glam::Mat3::from(m);
+        assert_eq!(gm.col(1).x, 2f32);
+        assert_eq!(gm.row(0), glam::Vec3::new(1f32, 2f32, 3f32));
+        assert_eq!(Matrix3x3::from(gm), m);
+        assert_eq!(Matrix3x3::from(glam::Mat3A::from(m)), m);
+        // both multiply column vectors
+        assert_eq!(Vector3::from(gm * glam::Vec3::from(v)), m.mul_vector(v));
+
+        let m4 = matrix4();
+        let gm4 = glam::Mat4::from(m4);
+        assert_eq!(
+            gm4.transform_point3(glam::Vec3::ZERO),
+            glam::Vec3::new(5f32, 6f32, 7f32)
+        );
+        assert_eq!(Matrix4x4::from(gm4), m4);
+        assert_eq!(
+            Matrix4x4::from(glam::DMat4::from(m4.cast::<f64>())),
+            m4.cast::<f64>()
+        );
+        assert_eq!(
+            Matrix2x2::from(glam::Mat2::from_cols_array(&[1f32, 3f32, 2f32, 4f32])),
+            Matrix2x2::from([1f32, 2f32, 3f32, 4f32])
+        );
+
+        #[cfg(feature = "quaternions")]
+        {
+            use crate::quaternions::Quaternion;
+
+            let q = Quaternion::from_axis_angle(Vector3::from([0f32, 0f32, 1f32]), 1f32);
+            let gq = glam::Quat::from(q);
+            assert_eq!(gq, glam::Quat::from_rotation_z(1f32));
+            assert_eq!(Quaternion::from(gq), q);
+            assert!(Vector3::from(gq * glam::Vec3::from(v)).eq_fast(q.rotate_vector(v), 1e-6));
+            assert_eq!(
+                Quaternion::from(glam::DQuat::from(q.cast::<f64>())),
+                q.cast::<f64>()
+            );
+        }
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_interop_nalgebra() {
+        let v = Vector3::from([1f32, 2f32, 3f32]);
+        assert_eq!(
+            nalgebra::Vector3::from(v),
+            nalgebra::Vector3::new(1f32, 2f32, 3f32)
+        );
+        assert_eq!(Vector3::from(nalgebra::Vector3::from(v)), v);
+        assert_eq!(Vector3::from(nalgebra::Point3::from(v)), v);
+        assert_eq!(
+            Vector2::from(nalgebra::Point2::new(4f64, 5f64)),
+            Vector2::from([4f64, 5f64])
+        );
+
+        let m = matrix3();
+        let nm = nalgebra::Matrix3::from(m);
+        assert_eq!(nm[(0, 1)], 2f32);
+        assert_eq!(
+            nm,
+            nalgebra::Matrix3::new(1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 10f32)
+        );
+        assert_eq!(Matrix3x3::from(nm), m);
+        assert_eq!(
+            Vector3::from(nm * nalgebra::Vector3::from(v)),
+            m.mul_vector(v)
+        );
+        assert_eq!(
+            Matrix4x4::from(nalgebra::Matrix4::from(matrix4())),
+            matrix4()
+        );
+        assert_eq!(
+            Matrix2x2::from(nalgebra::Matrix2::new(1f64, 2f64, 3f64, 4f64)),
+            Matrix2x2::from([1f64, 2f64, 3f64, 4f64])
+        );
+
+        #[cfg(feature = "quaternions")]
+        {
+            use crate::quaternions::Quaternion;
+
+            let q = Quaternion::from([1f32, 2f32, 3f32, 4f32]);
+            let nq = nalgebra::Quaternion::from(q);
+            assert_eq!((nq.i, nq.j, nq.k, nq.w), (1f32, 2f32, 3f32, 4f32));
+            assert_eq!(Quaternion::from(nq), q);
+
+            let axis = Vector3::from([0f32, 0f32, 1f32]);
+            let unit =
+                nalgebra::UnitQuaternion::from_axis_angle(&nalgebra::Vector3::z_axis(), 1f32);
+            assert!(Quaternion::from(unit).eq_fast(Quaternion::from_axis_angle(axis, 1f32), 1e-6));
+        }
+    }
+
+    #[cfg(feature = "cgmath")]
+    #[test]
+    fn test_interop_cgmath() {
+        let v = Vector3::from([1f32, 2f32, 3f32]);
+        assert_eq!(
+            cgmath::Vector3::from(v),
+            cgmath::Vector3::new(1f32, 2f32, 3f32)
+        );
+        assert_eq!(Vector3::from(cgmath::Vector3::from(v)), v);
+        assert_eq!(Vector3::from(cgmath::Point3::from(v)), v);
+        assert_eq!(
+            Vector4::from(cgmath::Vector4::new(1f64, 2f64, 3f64, 4f64)),
+            Vector4::from([1f64, 2f64, 3f64, 4f64])
+        );
+
+        let m = matrix3();
+        let cm = cgmath::Matrix3::from(m);
+        // cgmath fields are columns
+        assert_eq!(cm.y.x, 2f32);
+        assert_eq!(Matrix3x3::from(cm), m);
+        assert_eq!(
+            Vector3::from(cm * cgmath::Vector3::from(v)),
+            m.mul_vector(v)
+        );
+        assert_eq!(Matrix4x4::from(cgmath::Matrix4::from(matrix4())), matrix4());
+        assert_eq!(
+            Matrix2x2::from(cgmath::Matrix2::new(1f32, 3f32, 2f32, 4f32)),
+            Matrix2x2::from([1f32, 2f32, 3f32, 4f32])
+        );
+
+        #[cfg(feature = "quaternions")]
+        {
+            use crate::quaternions::Quaternion;
+
+            let q = Quaternion::from([1f32, 2f32, 3f32, 4f32]);
+            let cq = cgmath::Quaternion::from(q);
+            assert_eq!(cq, cgmath::Quaternion::new(4f32, 1f32, 2f32, 3f32));
+            assert_eq!(Quaternion::from(cq), q);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test_serialize {
+    use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+    use crate::{
+        matrix::{Matrix2x2, Matrix3x3, Matrix4x4},
+        vector::{Vector2, Vector3, Vector4},
+    };
+
+    fn round_trip<V: Serialize + DeserializeOwned + PartialEq + core::fmt::Debug>(value: &V) {
+        let json = serde_json::to_string(value).unwrap();
+        assert_eq!(&serde_json::from_str::<V>(&json).unwrap(), value);
+        let bytes = bincode::serialize(value).unwrap();
+        assert_eq!(&bincode::deserialize::<V>(&bytes).unwrap(), value);
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Plain {
+        v: Vector3<f32>,
+        m: Matrix2x2<f32>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Xyzw {
+        #[serde(with = "crate::serialize::xyzw")]
+        v2: Vector2<f32>,
+        #[serde(with = "crate::serialize::xyzw")]
+        v3: Vector3<f64>,
+        #[serde(with = "crate::serialize::xyzw")]
+        v4: Vector4<f32>,
+        #[serde(with = "crate::serialize::array")]
+        v: Vector3<f32>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct ColumnMajor {
+        #[serde(with = "crate::serialize::column_major")]
+        m2: Matrix2x2<f32>,
+        #[serde(with = "crate::serialize::column_major")]
+        m3: Matrix3x3<f32>,
+        #[serde(with = "crate::serialize::column_major")]
+        m4: Matrix4x4<f64>,
+    }
+
+    #[test]
+    fn test_serialize_default() {
+        let value = Plain {
+            v: Vector3::from([1f32, 2f32, 3f32]),
+            m: Matrix2x2::from([1f32, 2f32, 3f32, 4f32]),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"v":[1.0,2.0,3.0],"m":[[1.0,2.0],[3.0,4.0]]}"#
+        );
+        round_trip(&value);
+    }
+
+    #[test]
+    fn test_serialize_xyzw() {
+        let value = Xyzw {
+            v2: Vector2::from([1f32, 2f32]),
+            v3: Vector3::from([1f64, 2f64, 3f64]),
+            v4: Vector4::from([1f32, 2f32, 3f32, 4f32]),
+            v: Vector3::from([5f32, 6f32, 7f32]),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"v2":{"x":1.0,"y":2.0},"v3":{"x":1.0,"y":2.0,"z":3.0},"v4":{"x":1.0,"y":2.0,"z":3.0,"w":4.0},"v":[5.0,6.0,7.0]}"#
+        );
+        round_trip(&value);
+        // missing components are an error
+        assert!(serde_json::from_str::<Xyzw>(
+            r#"{"v2":{"x":1.0},"v3":{"x":1.0,"y":2.0,"z":3.0},"v4":{"x":1.0,"y":2.0,"z":3.0,"w":4.0},"v":[5.0,6.0,7.0]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_serialize_column_major() {
+        let m4 = Matrix4x4::from([
+            1f64, 0f64, 0f64, 5f64, 0f64, 2f64, 0f64, 6f64, 0f64, 0f64, 3f64, 7f64, 0f64, 0f64,
+            0f64, 1f64,
+        ]);
+        let value = ColumnMajor {
+            m2: Matrix2x2::from([1f32, 2f32, 3f32, 4f32]),
+            m3: Matrix3x3::from([1f32, 2f32, 3f32, 4f32, 5f32, 6f32, 7f32, 8f32, 9f32]),
+            m4,
+        };
+
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(json["m2"], serde_json::json!([1.0, 3.0, 2.0, 4.0]));
+        assert_eq!(
+            json["m3"],
+            serde_json::json!([1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0])
+        );
+        // the translation is last, like in glTF
+        assert_eq!(json["m4"].as_array().unwrap()[12..15], [5.0, 6.0, 7.0]);
+        round_trip(&value);
+    }
+
+    #[cfg(feature = "quaternions")]
+    #[test]
+    fn test_serialize_quaternion() {
+        use std::f64::consts::FRAC_PI_2;
+
+        use crate::quaternions::Quaternion;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Rotations {
+            default: Quaternion<f32>,
+            #[serde(with = "crate::serialize::xyzw")]
+            xyzw: Quaternion<f32>,
+            #[serde(with = "crate::serialize::axis_angle")]
+            axis_angle: Quaternion<f64>,
+            #[serde(with = "crate::serialize::euler_degrees")]
+            euler: Quaternion<f64>,
+        }
+
+        let q = Quaternion::from_axis_angle(Vector3::from([0f64, 0f64, 1f64]), FRAC_PI_2);
+        let value = Rotations {
+            default: Quaternion::from([1f32, 2f32, 3f32, 4f32]),
+            xyzw: Quaternion::from([1f32, 2f32, 3f32, 4f32]),
+            axis_angle: q,
+            euler: q,
+        };
+
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(json["default"], serde_json::json!([1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(
+            json["xyzw"],
+            serde_json::json!({"x": 1.0, "y": 2.0, "z": 3.0, "w": 4.0})
+        );
+        assert_eq!(
+            json["axis_angle"]["axis"],
+            serde_json::json!([0.0, 0.0, 1.0])
+        );
+        assert!((json["axis_angle"]["angle"].as_f64().unwrap() - FRAC_PI_2).abs() < 1e-12);
+        assert!((json["euler"]["z"].as_f64().unwrap() - 90f64).abs() < 1e-12);
+        assert!(json["euler"]["x"].as_f64().unwrap().abs() < 1e-12);
+
+        // the angle forms aren't exact, so compare within a tolerance
+        for text in [
+            serde_json::to_string(&value).unwrap(),
+            r#"{"default":[1.0,2.0,3.0,4.0],"xyzw":{"x":1.0,"y":2.0,"z":3.0,"w":4.0},"axis_angle":{"axis":[0.0,0.0,2.0],"angle":1.5707963267948966},"euler":{"x":0.0,"y":0.0,"z":90.0}}"#.to_string(),
+        ] {
+            let back: Rotations = serde_json::from_str(&text).unwrap();
+            assert_eq!(back.default, value.default);
+            assert_eq!(back.xyzw, value.xyzw);
+            assert!(back.axis_angle.eq_fast(q, 1e-12));
+            assert!(back.euler.eq_fast(q, 1e-12));
+        }
+        let back: Rotations = bincode::deserialize(&bincode::serialize(&value).unwrap()).unwrap();
+        assert_eq!(back.xyzw, value.xyzw);
+        assert!(back.axis_angle.eq_fast(q, 1e-12));
+        assert!(back.euler.eq_fast(q, 1e-12));
+    }
+}
+
+#[cfg(test)]
+mod test_format {
+    use crate::{
+        format::ParseError,
+        matrix::{Matrix2x2, Matrix3x3, Matrix4x4},
+        test_util::{self, rand_matrix4x4, rand_vector4},
+        vector::{Vector2, Vector3, Vector4},
+    };
+
+    #[test]
+    fn test_format_vector() {
+        let v = Vector3::from([1f32, -2.5f32, 300f32]);
+
+        assert_eq!(format!("{v}"), "(1, -2.5, 300)");
+        assert_eq!(format!("{v:.2}"), "(1.00, -2.50, 300.00)");
+        assert_eq!(format!("{v:6.1}"), "(   1.0,   -2.5,  300.0)");
+        assert_eq!(format!("{v:+}"), "(+1, -2.5, +300)");
+        assert_eq!(format!("{v:e}"), "(1e0, -2.5e0, 3e2)");
+        assert_eq!(
+            format!("{:.1e}", Vector2::from([1234f64, 0.5f64])),
+            "(1.2e3, 5.0e-1)"
+        );
+    }
+
+    #[test]
+    fn test_format_matrix() {
+        let m = Matrix3x3::from([1f32, 0f32, 5f32, -2.5f32, 1f32, 0f32, 0f32, 0f32, 1f32]);
+
+        assert_eq!(
+            format!("{m}"),
+            "[   1,    0,    5]\n[-2.5,    1,    0]\n[   0,    0,    1]"
+        );
+        assert_eq!(
+            format!("{m:.3}"),
+            "[ 1.000,  0.000,  5.000]\n[-2.500,  1.000,  0.000]\n[ 0.000,  0.000,  1.000]"
+        );
+        assert_eq!(
+            format!("{m:8.3}").lines().next().unwrap(),
+            "[   1.000,    0.000,    5.000]"
+        );
+        assert_eq!(
+            format!("{m:<5}").lines().nth(1).unwrap(),
+            "[-2.5 , 1    , 0    ]"
+        );
+        assert_eq!(
+            format!("{m:*^6.1}").lines().nth(2).unwrap(),
+            "[*0.0**, *0.0**, *1.0**]"
+        );
+        assert_eq!(
+            format!("{:.1e}", Matrix2x2::from([1000f32, 0f32, 0f32, -0.25f32])),
+            "[  1.0e3,   0.0e0]\n[  0.0e0, -2.5e-1]"
+        );
+    }
+
+    #[test]
+    fn test_format_parse_round_trip() {
+        let mut rng = test_util::rng();
+        for _ in 0..100 {
+            let v = rand_vector4(&mut rng, 1e3f64);
+            let m = rand_matrix4x4(&mut rng, 1e3f32);
+
+            assert_eq!(format!("{v}").parse::<Vector4<f64>>().unwrap().0, v.0);
+            assert_eq!(format!("{v:e}").parse::<Vector4<f64>>().unwrap().0, v.0);
+            assert_eq!(
+                format!("{m}")
+                    .parse::<Matrix4x4<f32>>()
+                    .unwrap()
+                    .0
+                    .map(|row| row.0),
+                m.0.map(|row| row.0)
+            );
+            assert_eq!(
+                format!("{m:12e}")
+                    .parse::<Matrix4x4<f32>>()
+                    .unwrap()
+                    .0
+                    .map(|row| row.0),
+                m.0.map(|row| row.0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_parse_forms() {
+        let v3 = Vector3::from([1f32, 2f32, 3f32]);
+        for text in [
+            "(1, 2, 3)",
+            "[1, 2, 3]",
+            " 1, 2 , 3 ",
+            "1 2 3",
+            "vec3(1.0, 2.0, 3.0)",
+            "vec3(1.0f, 2.0F, 3.0lf)",
+            "dvec3(vec2(1, 2), 3)",
+            "vec3(1., 2e0, .3e1)",
+        ] {
+            assert_eq!(text.parse::<Vector3<f32>>(), Ok(v3), "{text}");
+        }
+        assert_eq!("vec4(0.5)".parse(), Ok(Vector4::from([0.5f64; 4])));
+        assert_eq!(
+            "vec4(vec3(0.5), 1)".parse(),
+            Ok(Vector4::from([0.5f64, 0.5f64, 0.5f64, 1f64]))
+        );
+        assert!("(inf, -inf)".parse::<Vector2<f32>>().unwrap()[1].is_infinite());
+
+        let m2 = Matrix2x2::from([1f32, 2f32, 3f32, 4f32]);
+        for text in [
+            "[1, 2]\n[3, 4]",
+            "[[1, 2], [3, 4]]",
+            "[1, 2, 3, 4]",
+            "1 2 3 4",
+            "mat2(1, 3, 2, 4)",
+            "mat2x2(vec2(1, 3), vec2(2, 4))",
+        ] {
+            assert_eq!(text.parse::<Matrix2x2<f32>>(), Ok(m2), "{text}");
+        }
+        assert_eq!("mat3(1.0)".parse(), Ok(Matrix3x3::<f64>::ident()));
+        assert_eq!(
+            "dmat4(2)".parse(),
+            Ok(Matrix4x4::<f64>::ident().mul_scalar(2f64))
+        );
+
+        #[cfg(feature = "quaternions")]
+        {
+            use crate::quaternions::Quaternion;
+
+            let q = Quaternion::from([1f32, 2.5f32, -3f32, 4f32]);
+            assert_eq!(format!("{q}").parse(), Ok(q));
+            assert_eq!(format!("{q:e}"), "(1e0, 2.5e0, -3e0, 4e0)");
+            assert_eq!("[1, 2.5, -3, 4]".parse(), Ok(q));
+        }
+    }
+
+    #[test]
+    fn test_format_parse_errors() {
+        assert_eq!("".parse::<Vector3<f32>>(), Err(ParseError::Empty));
+        assert_eq!(
+            "  ()".parse::<Vector3<f32>>(),
+            Err(ParseError::WrongCount {
+                expected: 3,
+                found: 0
+            })
+        );
+        assert_eq!(
+            "(1, 2)".parse::<Vector3<f32>>(),
+            Err(ParseError::WrongCount {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            "(1, x, 3)".parse::<Vector3<f32>>(),
+            Err(ParseError::InvalidNumber("x".to_string()))
+        );
+        assert_eq!(
+            "(1, 2, 3".parse::<Vector3<f32>>(),
+            Err(ParseError::UnexpectedChar {
+                found: None,
+                position: 8
+            })
+        );
+        assert_eq!(
+            "(1, 2, 3]".parse::<Vector3<f32>>(),
+            Err(ParseError::UnexpectedChar {
+                found: Some(']'),
+                position: 8
+            })
+        );
+        assert_eq!(
+            "(1, , 3)".parse::<Vector3<f32>>(),
+            Err(ParseError::UnexpectedChar {
+                found: Some(','),
+                position: 4
+            })
+        );
+        assert_eq!(
+            "(1, 2, 3,)".parse::<Vector3<f32>>(),
+            Err(ParseError::UnexpectedChar {
+                found: Some(')'),
+                position: 9
+            })
+        );
+        assert_eq!(
+            "vec4(1, 2, 3, 4)".parse::<Vector3<f32>>(),
+            Err(ParseError::UnexpectedConstructor("vec4".to_string()))
+        );
+        assert_eq!(
+            "vec3(1, 2)".parse::<Vector3<f32>>(),
+            Err(ParseError::WrongCount {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            "((1), 2, 3)".parse::<Vector3<f32>>(),
+            Err(ParseError::UnexpectedGroup)
+        );
+        assert_eq!(
+            "mat2(1, 2, 3)".parse::<Matrix2x2<f32>>(),
+            Err(ParseError::WrongCount {
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            "[1, 2]\n[3]".parse::<Matrix2x2<f32>>(),
+            Err(ParseError::WrongCount {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            "mat3(1)".parse::<Matrix2x2<f32>>(),
+            Err(ParseError::UnexpectedConstructor("mat3".to_string()))
+        );
+
+        assert_eq!(
+            ParseError::WrongCount {
+                expected: 3,
+                found: 2
+            }
+            .to_string(),
+            "expected 3 elements, found 2"
+        );
+        assert_eq!(
+            ParseError::UnexpectedChar {
+                found: Some(']'),
+                position: 8
+            }
+            .to_string(),
+            "unexpected `]` at position 8"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_geometry {
+    use rand::Rng;
+
+    use crate::{
+        geometry::{
+            Aabb, Capsule, Circle, Line2, Obb, Plane, Ray, Rect, Segment, Sphere, Triangle,
+        },
+        matrix::{Matrix3x3, Matrix4x4},
+        test_util::{self, rand_vector3},
+        vector::{Vector2, Vector3},
+    };
+
+    // rotation of 90 degrees around z, scale of 2 and a translation
+    fn matrix() -> Matrix4x4<f32> {
+        Matrix4x4::from([
+            0f32, -2f32, 0f32, 1f32, 2f32, 0f32, 0f32, 2f32, 0f32, 0f32, 2f32, 3f32, 0f32, 0f32,
+            0f32, 1f32,
+        ])
+    }
+
+    #[test]
+    fn test_geometry_closest_points() {
+        let mut rng = test_util::rng();
+        let triangle = Triangle::new(
+            Vector3::from([0f32, 0f32, 0f32]),
+            Vector3::from([2f32, 0f32, 0f32]),
+            Vector3::from([0f32, 3f32, 1f32]),
+        );
+        let segment = Segment::new(
+            Vector3::from([-1f32, 0f32, 1f32]),
+            Vector3::from([2f32, 1f32, -1f32]),
+        );
+        let aabb = Aabb::new(
+            Vector3::from([1f32, -1f32, 2f32]),
+            Vector3::from([-1f32, 2f32, 0f32]),
+        );
+        let obb = Obb::new(
+            Vector3::from([1f32, 0f32, 0f32]),
+            [
+                Vector3::from([0.6f32, 0.8f32, 0f32]),
+                Vector3::from([-0.8f32, 0.6f32, 0f32]),
+                Vector3::from([0f32, 0f32, 1f32]),
+            ],
+            Vector3::from([1f32, 0.5f32, 2f32]),
+        );
+
+        // sample the shapes, the closest point must beat every sample
+        let mut samples = vec![];
+        for _ in 0..2000 {
+            let u = rng.gen_range(0f32..1f32);
+            let v = rng.gen_range(0f32..1f32);
+            let (u, v) = if u + v > 1f32 {
+                (1f32 - u, 1f32 - v)
+            } else {
+                (u, v)
+            };
+            let local = rand_vector3(&mut rng, 1f32);
+            samples.push((
+                triangle
+                    .a
+                    .add(triangle.b.sub(triangle.a).mul_scalar(u))
+                    .add(triangle.c.sub(triangle.a).mul_scalar(v)),
+                segment.at(u),
+                aabb.center().add(local.mul_elem(aabb.half_extents())),
+                obb.from_local(local.mul_elem(obb.half_extents)),
+            ));
+        }
+
+        for _ in 0..200 {
+            let point = rand_vector3(&mut rng, 4f32);
+            let closest = (
+                triangle.closest_point(point),
+                segment.closest_point(point),
+                aabb.closest_point(point),
+                obb.closest_point(point),
+            );
+            assert!(triangle.contains_point(closest.0));
+            assert!(segment.contains_point(closest.1));
+            assert!(aabb.contains_point(closest.2));
+            assert!(obb.distance_to_point(closest.3) < 1e-5);
+            for sample in &samples {
+                assert!(closest.0.dist(point) <= sample.0.dist(point) + 1e-4);
+                assert!(closest.1.dist(point) <= sample.1.dist(point) + 1e-4);
+                assert!(closest.2.dist(point) <= sample.2.dist(point) + 1e-4);
+                assert!(closest.3.dist(point) <= sample.3.dist(point) + 1e-4);
+            }
+            assert_eq!(
+                aabb.contains_point(point),
+                aabb.distance_to_point(point) == 0f32
+            );
+        }
+    }
+
+    #[test]
+    fn test_geometry_degenerate_triangle() {
+        let triangle = Triangle::new(
+            Vector3::from([0f32, 0f32, 0f32]),
+            Vector3::from([1f32, 0f32, 0f32]),
+            Vector3::from([3f32, 0f32, 0f32]),
+        );
+        let point = Vector3::from([2f32, 1f32, 0f32]);
+        assert_eq!(
+            triangle.closest_point(point),
+            Vector3::from([2f32, 0f32, 0f32])
+        );
+        assert_eq!(triangle.distance_to_point(point), 1f32);
+    }
+
+    #[test]
+    fn test_geometry_ray_plane() {
+        let ray = Ray::from_points(
+            Vector3::from([1f32, 1f32, 1f32]),
+            Vector3::from([1f32, 1f32, 5f32]),
+        );
+        assert_eq!(ray.direction, Vector3::from([0f32, 0f32, 1f32]));
+        assert_eq!(ray.at(2f32), Vector3::from([1f32, 1f32, 3f32]));
+        assert_eq!(
+            ray.closest_point(Vector3::from([2f32, 1f32, -3f32])),
+            ray.origin
+        );
+        assert_eq!(
+            ray.distance_to_point(Vector3::from([1f32, 3f32, 4f32])),
+            2f32
+        );
+        assert!(ray.contains_point(Vector3::from([1f32, 1f32, 10f32])));
+        assert!(!ray.contains_point(Vector3::from([1f32, 1f32, 0f32])));
+
+        let plane = Plane::from_points(
+            Vector3::from([0f32, 0f32, 2f32]),
+            Vector3::from([1f32, 0f32, 2f32]),
+            Vector3::from([0f32, 1f32, 2f32]),
+        )
+        .unwrap();
+        assert_eq!(plane.normal, Vector3::from([0f32, 0f32, 1f32]));
+        assert_eq!(plane.d, -2f32);
+        assert_eq!(plane, Plane::new(Vector3::from([0f32, 0f32, 3f32]), -6f32));
+        let point = Vector3::from([4f32, 5f32, -1f32]);
+        assert_eq!(plane.signed_distance(point), -3f32);
+        assert_eq!(
+            plane.closest_point(point),
+            Vector3::from([4f32, 5f32, 2f32])
+        );
+        assert!(Plane::from_points(point, point, point.mul_scalar(2f32)).is_none());
+    }
+
+    #[test]
+    fn test_geometry_transform() {
+        let matrix = matrix();
+        let mut rng = test_util::rng();
+        let (a, b, c) = (
+            rand_vector3(&mut rng, 4f32),
+            rand_vector3(&mut rng, 4f32),
+            rand_vector3(&mut rng, 4f32),
+        );
+
+        let plane = Plane::from_points(a, b, c).unwrap();
+        let transformed = plane.transform(matrix);
+        let expected = Plane::from_points(
+            matrix.transform_point(a),
+            matrix.transform_point(b),
+            matrix.transform_point(c),
+        )
+        .unwrap();
+        assert!(transformed.normal.dist(expected.normal) < 1e-5);
+        assert!((transformed.d - expected.d).abs() < 1e-4);
+
+        // a mirroring, non-uniform scale keeps the normal on the same side
+        let mirror = Matrix4x4::from([
+            -1f32, 0f32, 0f32, 0f32, 0f32, 3f32, 0f32, 0f32, 0f32, 0f32, 1f32, 0f32, 0f32, 0f32,
+            0f32, 1f32,
+        ]);
+        let plane = Plane::from_point_normal(a, Vector3::from([1f32, 1f32, 0f32]));
+        let front = a.add(plane.normal);
+        let mirrored = plane.transform(mirror);
+        assert!(mirrored.contains_point(mirror.transform_point(a)));
+        assert!(mirrored.signed_distance(mirror.transform_point(front)) > 0f32);
+
+        let ray = Ray::new(a, b).transform(matrix);
+        assert!(ray.contains_point(matrix.transform_point(a.add(b))));
+
+        let sphere = Sphere::new(a, 1.5f32).transform(matrix);
+        assert_eq!(sphere.radius, 3f32);
+        assert!(sphere.center.dist(matrix.transform_point(a)) < 1e-5);
+
+        let capsule = Capsule::new(a, b, 0.5f32).transform(matrix);
+        assert_eq!(capsule.radius, 1f32);
+
+        let aabb = Aabb::new(a, b);
+        let transformed = aabb.transform(matrix);
+        let expected = Aabb::from_points(&aabb.corners().map(|ca| matrix.transform_point(ca)));
+        assert!(transformed.min.dist(expected.min) < 1e-5);
+        assert!(transformed.max.dist(expected.max) < 1e-5);
+        assert!(Aabb::<f32>::empty().transform(matrix).is_empty());
+
+        let obb = Obb::from_aabb(aabb).transform(matrix);
+        for (ca, cb) in obb.corners().iter().zip(aabb.corners()) {
+            assert!(ca.dist(matrix.transform_point(cb)) < 1e-4);
+        }
+        assert!(obb.to_aabb().min.dist(expected.min) < 1e-4);
+
+        let triangle = Triangle::new(a, b, c).transform(matrix);
+        assert_eq!(triangle.b, matrix.transform_point(b));
+        let segment = Segment::new(a, b).transform(matrix);
+        assert!((segment.length() - 2f32 * a.dist(b)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_geometry_bounding_volumes() {
+        let points = [
+            Vector3::from([1f32, 0f32, 0f32]),
+            Vector3::from([-3f32, 2f32, 0f32]),
+            Vector3::from([0f32, -1f32, 5f32]),
+        ];
+        let aabb = Aabb::from_points(&points);
+        assert_eq!(aabb.min, Vector3::from([-3f32, -1f32, 0f32]));
+        assert_eq!(aabb.max, Vector3::from([1f32, 2f32, 5f32]));
+        assert!(Aabb::<f32>::empty().is_empty());
+        assert!(Aabb::<f32>::from_points(&[]).is_empty());
+        assert_eq!(Aabb::empty().merge(aabb), aabb);
+        assert_eq!(aabb.corners()[5], Vector3::from([1f32, -1f32, 5f32]));
+
+        let sphere = Sphere::from_points(&points).unwrap();
+        assert!(points.iter().all(|ca| sphere.distance_to_point(*ca) < 1e-5));
+        assert!(Sphere::<f32>::from_points(&[]).is_none());
+
+        let s1 = Sphere::new(Vector3::from([0f32, 0f32, 0f32]), 1f32);
+        let s2 = Sphere::new(Vector3::from([4f32, 0f32, 0f32]), 2f32);
+        let merged = s1.merge(s2);
+        assert_eq!(
+            merged,
+            Sphere::new(Vector3::from([2.5f32, 0f32, 0f32]), 3.5f32)
+        );
+        assert_eq!(merged.merge(s1), merged);
+        assert_eq!(s1.merge(merged), merged);
+        let expanded = s1.expand(Vector3::from([0f32, 3f32, 0f32]));
+        assert_eq!(
+            expanded,
+            Sphere::new(Vector3::from([0f32, 1f32, 0f32]), 2f32)
+        );
+        assert_eq!(
+            s2.to_aabb(),
+            Aabb::new(
+                Vector3::from([2f32, -2f32, -2f32]),
+                Vector3::from([6f32, 2f32, 2f32])
+            )
+        );
+
+        let capsule = Capsule::new(
+            Vector3::from([0f32, 0f32, 0f32]),
+            Vector3::from([0f32, 4f32, 0f32]),
+            1f32,
+        );
+        assert!(capsule.contains_point(Vector3::from([1f32, 4f32, 0f32])));
+        assert!(!capsule.contains_point(Vector3::from([1f32, 5f32, 0f32])));
+        assert_eq!(
+            capsule.distance_to_point(Vector3::from([3f32, 2f32, 0f32])),
+            2f32
+        );
+        assert_eq!(
+            capsule.closest_point(Vector3::from([0f32, 7f32, 0f32])),
+            Vector3::from([0f32, 5f32, 0f32])
+        );
+        assert_eq!(
+            capsule.to_aabb(),
+            Aabb::new(
+                Vector3::from([-1f32, -1f32, -1f32]),
+                Vector3::from([1f32, 5f32, 1f32])
+            )
+        );
+    }
+
+    #[test]
+    fn test_geometry_2d() {
+        // rotation of 90 degrees, scale of 3 and a translation
+        let matrix = Matrix3x3::from([0f32, -3f32, 1f32, 3f32, 0f32, 2f32, 0f32, 0f32, 1f32]);
+
+        let rect = Rect::from_points(&[Vector2::from([1f32, 2f32]), Vector2::from([-1f32, 0f32])]);
+        assert_eq!(rect.size(), Vector2::from([2f32, 2f32]));
+        assert_eq!(
+            rect.closest_point(Vector2::from([3f32, 1f32])),
+            Vector2::from([1f32, 1f32])
+        );
+        assert_eq!(rect.distance_to_point(Vector2::from([4f32, 6f32])), 5f32);
+        assert!(rect.contains_point(Vector2::from([0f32, 1f32])));
+        assert_eq!(
+            rect.transform(matrix),
+            Rect::new(Vector2::from([-5f32, -1f32]), Vector2::from([1f32, 5f32]))
+        );
+        assert_eq!(rect.corners()[1], Vector2::from([1f32, 0f32]));
+        assert!(Rect::<f32>::empty().is_empty());
+        assert_eq!(rect.merge(Rect::empty()), rect);
+        assert_eq!(
+            rect.expand(Vector2::from([5f32, 1f32])).max,
+            Vector2::from([5f32, 2f32])
+        );
+
+        let circle = Circle::new(Vector2::from([1f32, 1f32]), 2f32);
+        assert_eq!(circle.distance_to_point(Vector2::from([1f32, 6f32])), 3f32);
+        assert_eq!(
+            circle.closest_point(Vector2::from([5f32, 1f32])),
+            Vector2::from([3f32, 1f32])
+        );
+        assert_eq!(
+            circle.transform(matrix),
+            Circle::new(Vector2::from([-2f32, 5f32]), 6f32)
+        );
+        assert_eq!(
+            circle.to_rect(),
+            Rect::new(Vector2::from([-1f32, -1f32]), Vector2::from([3f32, 3f32]))
+        );
+        assert_eq!(
+            circle.merge(Circle::new(Vector2::from([7f32, 1f32]), 0f32)),
+            Circle::new(Vector2::from([3f32, 1f32]), 4f32)
+        );
+
+        let line = Line2::from_points(Vector2::from([0f32, 1f32]), Vector2::from([2f32, 1f32]));
+        assert_eq!(line.signed_distance(Vector2::from([5f32, 3f32])), 2f32);
+        assert_eq!(line.signed_distance(Vector2::from([-5f32, -1f32])), -2f32);
+        assert_eq!(
+            line.closest_point(Vector2::from([-3f32, 4f32])),
+            Vector2::from([-3f32, 1f32])
+        );
+        assert!(line.contains_point(Vector2::from([100f32, 1f32])));
+        let transformed = line.transform(matrix);
+        assert!(transformed.contains_point(Vector2::from([-2f32, 2f32])));
+        assert!(transformed.contains_point(Vector2::from([-2f32, 8f32])));
+    }
+}
+
+#[cfg(test)]
+mod test_intersection {
+    use rand::{rngs::StdRng, Rng};
+
+    use crate::{
+        geometry::{Aabb, Obb, Plane, Ray, Segment, Sphere, Triangle},
+        test_util::{self, rand_vector3},
+        vector::Vector3,
+    };
+
+    fn rand_obb(rng: &mut StdRng) -> Obb<f64> {
+        let x = rand_vector3(rng, 1f64).normalize_or_zero();
+        let y = x.mul_cross(rand_vector3(rng, 1f64)).normalize_or_zero();
+        Obb::new(
+            rand_vector3(rng, 3f64),
+            [x, y, x.mul_cross(y)],
+            Vector3::from([0; 3].map(|_| rng.gen_range(0.1f64..2f64))),
+        )
+    }
+
+    /// The brute-force reference for a Ray: the first sampled t where
+    /// the shape contains the point, sampled every 1e-2
+    fn march(ray: Ray<f64>, contains: impl Fn(Vector3<f64>) -> bool) -> Option<f64> {
+        (0..2000)
+            .map(|cx| cx as f64 * 1e-2)
+            .find(|t| contains(ray.at(*t)))
+    }
+
+    /// The brute-force reference for two convex shapes: alternating projections
+    /// converge to the closest pair of points between them. Most pairs settle
+    /// in a few steps, only the ones that barely touch need the full count
+    fn distance(
+        closest_a: impl Fn(Vector3<f64>) -> Vector3<f64>,
+        closest_b: impl Fn(Vector3<f64>) -> Vector3<f64>,
+    ) -> f64 {
+        let mut point = closest_a(Vector3::from([0f64; 3]));
+        for _ in 0..2000 {
+            let next = closest_a(closest_b(point));
+            if next.dist(point) < 1e-12 {
+                break;
+            }
+            point = next;
+        }
+        point.dist(closest_b(point))
+    }
+
+    /// Checks the test against the brute-force distance, skipping
+    /// the cases too close to call
+    fn check_overlap(intersects: bool, distance: f64) -> bool {
+        if distance < 1e-6 {
+            assert!(intersects);
+        } else if distance > 1e-3 {
+            assert!(!intersects);
+        }
+        intersects
+    }
+
+    #[test]
+    fn test_intersection_ray_triangle() {
+        let mut rng = test_util::rng();
+        let mut hits = 0;
+        for _ in 0..500 {
+            let triangle = Triangle::new(
+                rand_vector3(&mut rng, 2f64),
+                rand_vector3(&mut rng, 2f64),
+                rand_vector3(&mut rng, 2f64),
+            );
+            let origin = rand_vector3(&mut rng, 6f64);
+            // aim near the Triangle, so about half of the Rays hit it
+            let target = triangle
+                .a
+                .add(triangle.b)
+                .add(triangle.c)
+                .div_scalar(3f64)
+                .add(rand_vector3(&mut rng, 1f64));
+            let ray = Ray::from_points(origin, target);
+            match ray.intersect_triangle(triangle) {
+                Some(hit) => {
+                    hits += 1;
+                    let point = ray.at(hit.t);
+                    let weights = hit.barycentric();
+                    let interpolated = triangle
+                        .a
+                        .mul_scalar(weights[0])
+                        .add(triangle.b.mul_scalar(weights[1]))
+                        .add(triangle.c.mul_scalar(weights[2]));
+                    assert!(point.dist(interpolated) < 1e-9);
+                    assert!(triangle.distance_to_point(point) < 1e-9);
+                    assert!(weights.0.iter().all(|ca| *ca >= 0f64));
+                }
+                None => {
+                    let plane = Plane::from_points(triangle.a, triangle.b, triangle.c).unwrap();
+                    if let Some(t) = ray.intersect_plane(plane) {
+                        assert!(triangle.distance_to_point(ray.at(t)) > 0f64);
+                    }
+                }
+            }
+        }
+        assert!(hits > 50);
+
+        let triangle = Triangle::new(
+            Vector3::from([0f64, 0f64, 0f64]),
+            Vector3::from([1f64, 0f64, 0f64]),
+            Vector3::from([0f64, 1f64, 0f64]),
+        );
+        let behind = Ray::new(
+            Vector3::from([0.2f64, 0.2f64, -1f64]),
+            Vector3::from([0f64, 0f64, -1f64]),
+        );
+        assert!(behind.intersect_triangle(triangle).is_none());
+        let parallel = Ray::new(
+            Vector3::from([-1f64, 0.2f64, 0f64]),
+            Vector3::from([1f64, 0f64, 0f64]),
+        );
+        assert!(parallel.intersect_triangle(triangle).is_none());
+    }
+
+    #[test]
+    fn test_intersection_ray_solids() {
+        let mut rng = test_util::rng();
+        let mut hits = 0;
+        for _ in 0..200 {
+            let aabb = Aabb::new(rand_vector3(&mut rng, 3f64), rand_vector3(&mut rng, 3f64));
+            let sphere = Sphere::new(rand_vector3(&mut rng, 3f64), rng.gen_range(0.2f64..2f64));
+            let ray = Ray::from_points(rand_vector3(&mut rng, 6f64), rand_vector3(&mut rng, 3f64));
+
+            for (result, reference) in [
+                (
+                    ray.intersect_aabb(aabb),
+                    march(ray, |ca| aabb.contains_point(ca)),
+                ),
+                (
+                    ray.intersect_sphere(sphere),
+                    march(ray, |ca| sphere.contains_point(ca)),
+                ),
+            ] {
+                match (result, reference) {
+                    (Some(t), Some(reference)) => {
+                        hits += 1;
+                        assert!(t <= reference && reference - t <= 1e-2);
+                    }
+                    // grazing hits can slip between the samples
+                    (Some(t), None) => {
+                        assert!(
+                            aabb.distance_to_point(ray.at(t)) < 1e-9
+                                || sphere.distance_to_point(ray.at(t)) < 1e-9
+                        );
+                    }
+                    (None, Some(_)) => panic!("missed {ray:?}"),
+                    (None, None) => {}
+                }
+            }
+        }
+        assert!(hits > 50);
+
+        // starting inside, or parallel to a slab
+        let aabb = Aabb::new(Vector3::from([0f64; 3]), Vector3::from([1f64; 3]));
+        let inside = Ray::new(
+            Vector3::from([0.5f64; 3]),
+            Vector3::from([1f64, 0f64, 0f64]),
+        );
+        assert_eq!(inside.intersect_aabb(aabb), Some(0f64));
+        let sphere = Sphere::new(Vector3::from([0.5f64; 3]), 1f64);
+        assert_eq!(inside.intersect_sphere(sphere), Some(0f64));
+        let parallel = Ray::new(
+            Vector3::from([-1f64, 0.5f64, 2f64]),
+            Vector3::from([1f64, 0f64, 0f64]),
+        );
+        assert_eq!(parallel.intersect_aabb(aabb), None);
+        let away = Ray::new(
+            Vector3::from([2f64, 0.5f64, 0.5f64]),
+            Vector3::from([1f64, 0f64, 0f64]),
+        );
+        assert_eq!(away.intersect_aabb(aabb), None);
+        assert_eq!(away.intersect_sphere(sphere), None);
+    }
+
+    #[test]
+    fn test_intersection_ray_plane() {
+        let plane = Plane::new(Vector3::from([0f64, 0f64, 1f64]), -2f64);
+        let ray = Ray::new(
+            Vector3::from([1f64, 1f64, 5f64]),
+            Vector3::from([0f64, 0f64, -0.5f64]),
+        );
+        assert_eq!(ray.intersect_plane(plane), Some(6f64));
+        assert_eq!(ray.at(6f64), Vector3::from([1f64, 1f64, 2f64]));
+        let away = Ray::new(ray.origin, Vector3::from([0f64, 0f64, 1f64]));
+        assert_eq!(away.intersect_plane(plane), None);
+        let parallel = Ray::new(ray.origin, Vector3::from([1f64, 0f64, 0f64]));
+        assert_eq!(parallel.intersect_plane(plane), None);
+        let on = Ray::new(
+            Vector3::from([0f64, 0f64, 2f64]),
+            Vector3::from([1f64, 0f64, 0f64]),
+        );
+        assert_eq!(on.intersect_plane(plane), Some(0f64));
+    }
+
+    #[test]
+    fn test_intersection_segments() {
+        let mut rng = test_util::rng();
+        for _ in 0..100 {
+            let s1 = Segment::new(rand_vector3(&mut rng, 3f64), rand_vector3(&mut rng, 3f64));
+            let s2 = Segment::new(rand_vector3(&mut rng, 3f64), rand_vector3(&mut rng, 3f64));
+            let (ca, cb) = s1.closest_points(s2);
+            assert!(s1.contains_point(ca));
+            assert!(s2.contains_point(cb));
+            let reference = (0..=100)
+                .flat_map(|cx| (0..=100).map(move |cy| (cx as f64 / 100f64, cy as f64 / 100f64)))
+                .map(|(s, t)| s1.at(s).dist(s2.at(t)))
+                .fold(f64::INFINITY, f64::min);
+            assert!(ca.dist(cb) <= reference + 1e-9);
+            assert!(reference - ca.dist(cb) < 0.05);
+        }
+
+        // parallel, crossing and degenerate Segments
+        let s1 = Segment::new(
+            Vector3::from([0f64, 0f64, 0f64]),
+            Vector3::from([2f64, 0f64, 0f64]),
+        );
+        let s2 = Segment::new(
+            Vector3::from([1f64, 1f64, 0f64]),
+            Vector3::from([3f64, 1f64, 0f64]),
+        );
+        assert_eq!(s1.distance_to_segment(s2), 1f64);
+        let s3 = Segment::new(
+            Vector3::from([1f64, -1f64, 1f64]),
+            Vector3::from([1f64, 1f64, 1f64]),
+        );
+        assert_eq!(
+            s1.closest_points(s3),
+            (
+                Vector3::from([1f64, 0f64, 0f64]),
+                Vector3::from([1f64, 0f64, 1f64])
+            )
+        );
+        let point = Segment::new(
+            Vector3::from([5f64, 0f64, 0f64]),
+            Vector3::from([5f64, 0f64, 0f64]),
+        );
+        assert_eq!(s1.distance_to_segment(point), 3f64);
+        assert_eq!(point.distance_to_segment(s1), 3f64);
+        assert_eq!(point.distance_to_segment(point), 0f64);
+    }
+
+    #[test]
+    fn test_intersection_overlaps() {
+        let mut rng = test_util::rng();
+        let mut overlaps = [0; 5];
+        for _ in 0..300 {
+            let a1 = Aabb::from_center_half_extents(
+                rand_vector3(&mut rng, 3f64),
+                rand_vector3(&mut rng, 1.5f64).abs(),
+            );
+            let a2 = Aabb::from_center_half_extents(
+                rand_vector3(&mut rng, 3f64),
+                rand_vector3(&mut rng, 1.5f64).abs(),
+            );
+            let s1 = Sphere::new(rand_vector3(&mut rng, 3f64), rng.gen_range(0.1f64..2f64));
+            let s2 = Sphere::new(rand_vector3(&mut rng, 3f64), rng.gen_range(0.1f64..2f64));
+            let triangle = Triangle::new(
+                rand_vector3(&mut rng, 3f64),
+                rand_vector3(&mut rng, 3f64),
+                rand_vector3(&mut rng, 3f64),
+            );
+            let (o1, o2) = (rand_obb(&mut rng), rand_obb(&mut rng));
+
+            let results = [
+                check_overlap(
+                    a1.intersects_aabb(a2),
+                    distance(|ca| a1.closest_point(ca), |ca| a2.closest_point(ca)),
+                ),
+                check_overlap(
+                    s1.intersects_sphere(s2),
+                    distance(|ca| s1.closest_point(ca), |ca| s2.closest_point(ca)),
+                ),
+                check_overlap(
+                    s1.intersects_aabb(a1),
+                    distance(|ca| s1.closest_point(ca), |ca| a1.closest_point(ca)),
+                ),
+                check_overlap(
+                    triangle.intersects_aabb(a1),
+                    distance(|ca| triangle.closest_point(ca), |ca| a1.closest_point(ca)),
+                ),
+                check_overlap(
+                    o1.intersects_obb(o2),
+                    distance(|ca| o1.closest_point(ca), |ca| o2.closest_point(ca)),
+                ),
+            ];
+            assert_eq!(a1.intersects_sphere(s1), s1.intersects_aabb(a1));
+            for (ca, cb) in overlaps.iter_mut().zip(results) {
+                *ca += cb as usize;
+            }
+        }
+        // both outcomes are covered
+        assert!(
+            overlaps.iter().all(|ca| *ca > 10 && *ca < 290),
+            "{overlaps:?}"
+        );
+
+        // touching shapes overlap
+        let aabb = Aabb::new(Vector3::from([0f64; 3]), Vector3::from([1f64; 3]));
+        let touching = Aabb::new(Vector3::from([1f64, 0f64, 0f64]), Vector3::from([2f64; 3]));
+        assert!(aabb.intersects_aabb(touching));
+        let obb = Obb::from_aabb(aabb);
+        assert!(obb.intersects_obb(Obb::from_aabb(touching)));
+        let parallel = Obb::from_aabb(Aabb::new(
+            Vector3::from([1.5f64, 0f64, 0f64]),
+            Vector3::from([2f64; 3]),
+        ));
+        assert!(!obb.intersects_obb(parallel));
+        let triangle = Triangle::new(
+            Vector3::from([2f64, 2f64, 0.5f64]),
+            Vector3::from([-1f64, 2f64, 0.5f64]),
+            Vector3::from([2f64, -1f64, 0.5f64]),
+        );
+        assert!(triangle.intersects_aabb(aabb));
+        let triangle = Triangle::new(
+            Vector3::from([3f64, 2f64, 0.5f64]),
+            Vector3::from([1.5f64, 2f64, 0.5f64]),
+            Vector3::from([3f64, 0.5f64, 0.5f64]),
+        );
+        assert!(!triangle.intersects_aabb(aabb));
+    }
+}
+
+#[cfg(test)]
+mod test_frustum {
+    use rand::{rngs::StdRng, Rng};
+
+    use crate::{
+        frustum::{Containment, DepthRange, Frustum},
+        geometry::{Aabb, Sphere},
+        matrix::Matrix4x4,
+        test_util::{self, rand_vector3},
+        vector::{Vector3, Vector4},
+    };
+
+    /// A right-handed perspective projection, looking down -z
+    fn perspective(depth: DepthRange, near: f64, far: f64) -> Matrix4x4<f64> {
+        let focal = 1f64 / (0.5f64).tan();
+        let aspect = 1.5f64;
+        let (z, w) = match depth {
+            DepthRange::NegativeOneToOne => (
+                (far + near) / (near - far),
+                2f64 * far * near / (near - far),
+            ),
+            DepthRange::ZeroToOne => (far / (near - far), far * near / (near - far)),
+            DepthRange::ReversedZeroToOne => (near / (far - near), far * near / (far - near)),
+        };
+        Matrix4x4::from([
+            focal / aspect,
+            0f64,
+            0f64,
+            0f64,
+            0f64,
+            focal,
+            0f64,
+            0f64,
+            0f64,
+            0f64,
+            z,
+            w,
+            0f64,
+            0f64,
+            -1f64,
+            0f64,
+        ])
+    }
+
+    /// A camera at (1, 2, 3), turned 90 degrees around y
+    fn view() -> Matrix4x4<f64> {
+        Matrix4x4::from([
+            0f64, 0f64, -1f64, 3f64, 0f64, 1f64, 0f64, -2f64, 1f64, 0f64, 0f64, -1f64, 0f64, 0f64,
+            0f64, 1f64,
+        ])
+    }
+
+    /// The reference: the point is inside if its clip coordinates are
+    fn clip_contains(view_proj: Matrix4x4<f64>, depth: DepthRange, point: Vector3<f64>) -> bool {
+        let clip = view_proj.mul_vector(Vector4::from([point[0], point[1], point[2], 1f64]));
+        let w = clip[3];
+        let z_min = match depth {
+            DepthRange::NegativeOneToOne => -w,
+            DepthRange::ZeroToOne | DepthRange::ReversedZeroToOne => 0f64,
+        };
+        clip[0].abs() <= w && clip[1].abs() <= w && z_min <= clip[2] && clip[2] <= w
+    }
+
+    #[test]
+    fn test_frustum_culling() {
+        let mut rng = test_util::rng();
+        for depth in [
+            DepthRange::NegativeOneToOne,
+            DepthRange::ZeroToOne,
+            DepthRange::ReversedZeroToOne,
+        ] {
+            let view_proj = perspective(depth, 0.5f64, 10f64).mul_matrix(view());
+            let frustum = Frustum::from_matrix_with_depth(view_proj, depth);
+            let rand_point = |rng: &mut StdRng| {
+                Vector3::from([
+                    rng.gen_range(-12f64..2f64),
+                    rng.gen_range(-4f64..8f64),
+                    rng.gen_range(-4f64..10f64),
+                ])
+            };
+
+            for _ in 0..1000 {
+                let point = rand_point(&mut rng);
+                assert_eq!(
+                    frustum.contains_point(point),
+                    clip_contains(view_proj, depth, point)
+                );
+            }
+
+            let mut counts = [0; 3];
+            for _ in 0..300 {
+                let center = rand_point(&mut rng);
+                let sphere = Sphere::new(center, rng.gen_range(0.1f64..1.5f64));
+                let aabb = Aabb::from_center_half_extents(
+                    center,
+                    Vector3::from([0; 3].map(|_| rng.gen_range(0.1f64..1.5f64))),
+                );
+                let samples: Vec<_> = (0..200)
+                    .map(|_| rand_vector3(&mut rng, 1f64).normalize_or_zero())
+                    .collect();
+
+                for (containment, points) in [
+                    (
+                        frustum.classify_sphere(sphere),
+                        samples
+                            .iter()
+                            .map(|ca| center.add(ca.mul_scalar(sphere.radius)))
+                            .collect::<Vec<_>>(),
+                    ),
+                    (
+                        frustum.classify_aabb(aabb),
+                        samples
+                            .iter()
+                            .map(|ca| aabb.closest_point(center.add(ca.mul_scalar(3f64))))
+                            .chain(aabb.corners())
+                            .collect::<Vec<_>>(),
+                    ),
+                ] {
+                    let inside = points
+                        .iter()
+                        .filter(|ca| clip_contains(view_proj, depth, **ca))
+                        .count();
+                    match containment {
+                        Containment::Inside => {
+                            counts[0] += 1;
+                            assert_eq!(inside, points.len());
+                        }
+                        Containment::Outside => {
+                            counts[1] += 1;
+                            assert_eq!(inside, 0);
+                        }
+                        Containment::Intersecting => counts[2] += 1,
+                    }
+                }
+            }
+            assert!(counts.iter().all(|ca| *ca > 20), "{counts:?}");
+        }
+    }
+
+    #[test]
+    fn test_frustum_corners() {
+        for (depth, z_near, z_far) in [
+            (DepthRange::NegativeOneToOne, -1f64, 1f64),
+            (DepthRange::ZeroToOne, 0f64, 1f64),
+            (DepthRange::ReversedZeroToOne, 1f64, 0f64),
+        ] {
+            let view_proj = perspective(depth, 0.5f64, 10f64).mul_matrix(view());
+            let frustum = Frustum::from_matrix_with_depth(view_proj, depth);
+            for (cx, corner) in frustum.corners().into_iter().enumerate() {
+                let clip =
+                    view_proj.mul_vector(Vector4::from([corner[0], corner[1], corner[2], 1f64]));
+                let ndc = Vector3::from([clip[0], clip[1], clip[2]]).div_scalar(clip[3]);
+                let expected = Vector3::from([
+                    if cx & 1 == 0 { -1f64 } else { 1f64 },
+                    if cx & 2 == 0 { -1f64 } else { 1f64 },
+                    if cx & 4 == 0 { z_near } else { z_far },
+                ]);
+                assert!(ndc.dist(expected) < 1e-9, "{ndc:?} {expected:?}");
+            }
+            // the near corners are 0.5 in front of the camera, looking down -x
+            assert!((frustum.corners()[0][0] - 0.5f64).abs() < 1e-9);
+            assert!((frustum.corners()[7][0] + 9f64).abs() < 1e-9);
+
+            // a cascade from 1.925 to 4.775 in front of the camera
+            let slice = frustum.slice_corners(0.15f64, 0.45f64);
+            assert!((slice[0][0] + 0.925f64).abs() < 1e-9);
+            assert!((slice[7][0] + 3.775f64).abs() < 1e-9);
+            let corners = frustum.corners();
+            assert!(frustum
+                .slice_corners(0f64, 1f64)
+                .iter()
+                .zip(corners)
+                .all(|(ca, cb)| ca.dist(cb) < 1e-9));
+        }
+
+        // the default is OpenGL's depth
+        let view_proj = perspective(DepthRange::NegativeOneToOne, 1f64, 100f64);
+        assert_eq!(
+            Frustum::from_matrix(view_proj),
+            Frustum::from_matrix_with_depth(view_proj, DepthRange::default())
+        );
+    }
+
+    #[test]
+    fn test_frustum_infinite_far() {
+        // the limit of the OpenGL projection as far goes to infinity
+        let mut proj = perspective(DepthRange::NegativeOneToOne, 1f64, 10f64);
+        proj[2][2] = -1f64;
+        proj[2][3] = -2f64;
+        let frustum = Frustum::from_matrix(proj);
+        let far_away = Vector3::from([0f64, 0f64, -1e12f64]);
+        assert!(frustum.contains_point(far_away));
+        assert!(!frustum.contains_point(Vector3::from([0f64, 0f64, -0.5f64])));
+        assert_eq!(
+            frustum.classify_sphere(Sphere::new(far_away, 1f64)),
+            Containment::Inside
+        );
+    }
+
+    #[test]
+    fn test_frustum_infinite_reversed() {
+        // the limit of the reversed projection as far goes to infinity,
+        // where the far plane is z = 0 in clip space
+        let mut proj = perspective(DepthRange::ReversedZeroToOne, 1f64, 10f64);
+        proj[2][2] = 0f64;
+        proj[2][3] = 1f64;
+        let frustum = Frustum::from_matrix_with_depth(proj, DepthRange::ReversedZeroToOne);
+        let far_away = Vector3::from([0f64, 0f64, -1e12f64]);
+        assert!(frustum.contains_point(far_away));
+        assert!(!frustum.contains_point(Vector3::from([0f64, 0f64, -0.5f64])));
+
+        // the near corners are finite and on the near plane, the far ones are not
+        let corners = frustum.corners();
+        for (cx, corner) in corners.into_iter().enumerate() {
+            if cx & 4 == 0 {
+                assert!(corner.into_iter().all(f64::is_finite), "{corner:?}");
+                assert!((corner[2] + 1f64).abs() < 1e-9);
+                let clip = proj.mul_vector(Vector4::from([corner[0], corner[1], corner[2], 1f64]));
+                assert!((clip[2] / clip[3] - 1f64).abs() < 1e-9);
+            } else {
+                assert!(!corner.into_iter().all(f64::is_finite), "{corner:?}");
+            }
+        }
+        // right over left and top over bottom
+        assert!(corners[1][0] > corners[0][0]);
+        assert!(corners[2][1] > corners[0][1]);
+    }
+}
+
+#[cfg(test)]
+mod test_triangle {
+    use crate::{
+        geometry::Segment,
+        test_util::{self, rand_vector2, rand_vector3},
+        triangle::{
+            area, barycentric, centroid, circumcenter, incenter, interpolate, normal,
+            point_in_triangle, signed_area,
+        },
+        vector::{Vector2, Vector3, Vector4},
+    };
+
+    #[test]
+    fn test_triangle_barycentric() {
+        let mut rng = test_util::rng();
+        let mut inside = 0;
+        for _ in 0..500 {
+            let (a, b, c, p) = (
+                rand_vector2(&mut rng, 5f64),
+                rand_vector2(&mut rng, 5f64),
+                rand_vector2(&mut rng, 5f64),
+                rand_vector2(&mut rng, 5f64),
+            );
+            let weights = barycentric(p, a, b, c).unwrap();
+            assert!((weights.sum() - 1f64).abs() < 1e-9);
+            assert!(interpolate(weights, a, b, c).dist(p) < 1e-9);
+
+            // the reference: inside when on the same side of every edge
+            let sides = [
+                signed_area(a, b, p),
+                signed_area(b, c, p),
+                signed_area(c, a, p),
+            ];
+            let reference =
+                sides.iter().all(|ca| *ca >= 0f64) || sides.iter().all(|ca| *ca <= 0f64);
+            assert_eq!(point_in_triangle(p, a, b, c), reference);
+            inside += reference as usize;
+
+            // in 3D the point is projected onto the triangle first
+            let (a, b, c) = (
+                rand_vector3(&mut rng, 5f64),
+                rand_vector3(&mut rng, 5f64),
+                rand_vector3(&mut rng, 5f64),
+            );
+            let n = normal(a, b, c).unwrap();
+            let weights = barycentric(a.add(n.mul_scalar(3f64)), a, b, c).unwrap();
+            assert!(weights.dist(Vector3::from([1f64, 0f64, 0f64])) < 1e-9);
+            let on_plane = interpolate(Vector3::from([0.2f64, 0.3f64, 0.5f64]), a, b, c);
+            let weights = barycentric(on_plane.add(n), a, b, c).unwrap();
+            assert!(weights.dist(Vector3::from([0.2f64, 0.3f64, 0.5f64])) < 1e-9);
+        }
+        assert!(inside > 10);
+    }
+
+    #[test]
+    fn test_triangle_measures() {
+        let mut rng = test_util::rng();
+        for _ in 0..200 {
+            let (a, b, c) = (
+                rand_vector3(&mut rng, 5f64),
+                rand_vector3(&mut rng, 5f64),
+                rand_vector3(&mut rng, 5f64),
+            );
+            let cross = b.sub(a).mul_cross(c.sub(a));
+            assert!((area(a, b, c) - cross.len() / 2f64).abs() < 1e-9);
+            assert!(normal(a, b, c).unwrap().dist(cross.normalize_or_zero()) < 1e-9);
+            assert!(centroid(a, b, c).dist(a.add(b).add(c).div_scalar(3f64)) < 1e-9);
+
+            let center = circumcenter(a, b, c).unwrap();
+            let radius = center.dist(a);
+            assert!((center.dist(b) - radius).abs() < 1e-6);
+            assert!((center.dist(c) - radius).abs() < 1e-6);
+            assert!(barycentric(center, a, b, c).is_some_and(|ca| (ca.sum() - 1f64).abs() < 1e-9));
+            assert!(normal(a, b, c).unwrap().mul_inner(center.sub(a)).abs() < 1e-6);
+
+            let center = incenter(a, b, c).unwrap();
+            let radius = Segment::new(a, b).distance_to_point(center);
+            assert!((Segment::new(b, c).distance_to_point(center) - radius).abs() < 1e-9);
+            assert!((Segment::new(c, a).distance_to_point(center) - radius).abs() < 1e-9);
+            assert!(point_in_triangle(center, a, b, c));
+        }
+
+        let (a, b, c) = (
+            Vector2::from([0f32, 0f32]),
+            Vector2::from([2f32, 0f32]),
+            Vector2::from([0f32, 2f32]),
+        );
+        assert_eq!(area(a, b, c), 2f32);
+        assert_eq!(signed_area(a, b, c), 2f32);
+        assert_eq!(signed_area(a, c, b), -2f32);
+        assert_eq!(circumcenter(a, b, c), Some(Vector2::from([1f32, 1f32])));
+        let r = 2f32 - 2f32.sqrt();
+        assert!(incenter(a, b, c).unwrap().dist(Vector2::from([r, r])) < 1e-6);
+        assert_eq!(
+            normal(
+                Vector3::from([0f32, 0f32, 0f32]),
+                Vector3::from([1f32, 0f32, 0f32]),
+                Vector3::from([0f32, 1f32, 0f32])
+            ),
+            Some(Vector3::from([0f32, 0f32, 1f32]))
+        );
+    }
+
+    #[test]
+    fn test_triangle_degenerate() {
+        let collinear = (
+            Vector3::from([0f32, 0f32, 0f32]),
+            Vector3::from([1f32, 1f32, 1f32]),
+            Vector3::from([3f32, 3f32, 3f32]),
+        );
+        let point = Vector3::from([2f32, 2f32, 2f32]);
+        let coincident = (point, point, point);
+        for (a, b, c) in [collinear, coincident] {
+            assert_eq!(barycentric(point, a, b, c), None);
+            assert!(!point_in_triangle(point, a, b, c));
+            assert_eq!(area(a, b, c), 0f32);
+            assert_eq!(normal(a, b, c), None);
+            assert_eq!(circumcenter(a, b, c), None);
+            assert_eq!(incenter(a, b, c), None);
+        }
+        assert_eq!(
+            centroid(collinear.0, collinear.1, collinear.2),
+            Vector3::from([4f32 / 3f32; 3])
+        );
+
+        // nearly collinear, off by less than rounding
+        let a = Vector2::from([0f32, 0f32]);
+        let b = Vector2::from([1e4f32, 1e-5f32]);
+        let c = Vector2::from([2e4f32, 0f32]);
+        assert_eq!(barycentric(a, a, b, c), None);
+    }
+
+    #[test]
+    fn test_triangle_interpolate() {
+        let weights = Vector3::from([0.5f32, 0.25f32, 0.25f32]);
+        assert_eq!(interpolate(weights, 4f32, 8f32, 0f32), 4f32);
+        assert_eq!(
+            interpolate(
+                weights,
+                Vector2::from([0f32, 4f32]),
+                Vector2::from([4f32, 0f32]),
+                Vector2::from([0f32, 0f32])
+            ),
+            Vector2::from([1f32, 2f32])
+        );
+        assert_eq!(
+            interpolate(
+                weights,
+                Vector4::from([1f32, 0f32, 0f32, 1f32]),
+                Vector4::from([0f32, 1f32, 0f32, 1f32]),
+                Vector4::from([0f32, 0f32, 1f32, 1f32])
+            ),
+            Vector4::from([0.5f32, 0.25f32, 0.25f32, 1f32])
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_curves {
+    use crate::{
+        curves::{
+            ArcLengthTable, BSpline, CatmullRom, CubicBezier, CubicHermite, Curve, QuadraticBezier,
+        },
+        test_util::{self, rand_vector3},
+        vector::{Vector2, Vector3, Vector4},
+    };
+
+    /// Checks the derivative against central differences
+    fn check_derivative(curve: &impl Curve<Value = Vector3<f64>>) {
+        let (start, end) = curve.domain();
+        let h = 1e-6f64;
+        for cx in 1..20 {
+            // off the joints, where the derivative may jump
+            let t = start + (end - start) * (cx as f64 - 0.37f64) / 20f64;
+            let numeric = curve
+                .evaluate(t + h)
+                .sub(curve.evaluate(t - h))
+                .div_scalar(2f64 * h);
+            assert!(curve.derivative(t).dist(numeric) < 1e-4, "{t}");
+        }
+    }
+
+    #[test]
+    fn test_curves_bezier() {
+        let mut rng = test_util::rng();
+        let [p0, p1, p2, p3] = [0; 4].map(|_| rand_vector3(&mut rng, 5f64));
+
+        let quadratic = QuadraticBezier::new(p0, p1, p2);
+        assert_eq!(quadratic.evaluate(0f64), p0);
+        assert_eq!(quadratic.evaluate(1f64), p2);
+        assert_eq!(quadratic.evaluate(2f64), p2);
+        assert!(quadratic.derivative(0f64).dist(p1.sub(p0).mul_scalar(2f64)) < 1e-9);
+        check_derivative(&quadratic);
+        let cubic = quadratic.to_cubic();
+        for cx in 0..=10 {
+            let t = cx as f64 / 10f64;
+            assert!(cubic.evaluate(t).dist(quadratic.evaluate(t)) < 1e-9);
+        }
+
+        let cubic = CubicBezier::new(p0, p1, p2, p3);
+        assert_eq!(cubic.evaluate(0f64), p0);
+        assert_eq!(cubic.evaluate(1f64), p3);
+        assert!(cubic.derivative(1f64).dist(p3.sub(p2).mul_scalar(3f64)) < 1e-9);
+        check_derivative(&cubic);
+
+        // both halves of a split trace the original
+        let (left, right) = cubic.split(0.3f64);
+        let (q_left, q_right) = quadratic.split(0.3f64);
+        for cx in 0..=10 {
+            let t = cx as f64 / 10f64;
+            assert!(left.evaluate(t).dist(cubic.evaluate(t * 0.3f64)) < 1e-9);
+            assert!(right.evaluate(t).dist(cubic.evaluate(0.3f64 + t * 0.7f64)) < 1e-9);
+            assert!(q_left.evaluate(t).dist(quadratic.evaluate(t * 0.3f64)) < 1e-9);
+            assert!(
+                q_right
+                    .evaluate(t)
+                    .dist(quadratic.evaluate(0.3f64 + t * 0.7f64))
+                    < 1e-9
+            );
+        }
+
+        // on scalars and the other Vector sizes
+        let scalar = CubicBezier::new(0f32, 1f32, 1f32, 0f32);
+        assert_eq!(scalar.evaluate(0.5f32), 0.75f32);
+        assert_eq!(scalar.derivative(0.5f32), 0f32);
+        let quadratic = QuadraticBezier::new(
+            Vector2::from([0f32, 0f32]),
+            Vector2::from([1f32, 2f32]),
+            Vector2::from([2f32, 0f32]),
+        );
+        assert_eq!(quadratic.evaluate(0.5f32), Vector2::from([1f32, 1f32]));
+        let cubic = CubicBezier::new(
+            Vector4::from([0f32; 4]),
+            Vector4::from([0f32; 4]),
+            Vector4::from([1f32; 4]),
+            Vector4::from([1f32; 4]),
+        );
+        assert_eq!(cubic.evaluate(0.5f32), Vector4::from([0.5f32; 4]));
+    }
+
+    #[test]
+    fn test_curves_hermite() {
+        let mut rng = test_util::rng();
+        let [p0, m0, p1, m1] = [0; 4].map(|_| rand_vector3(&mut rng, 5f64));
+        let hermite = CubicHermite::new(p0, m0, p1, m1);
+        assert_eq!(hermite.evaluate(0f64), p0);
+        assert!(hermite.evaluate(1f64).dist(p1) < 1e-12);
+        assert!(hermite.derivative(0f64).dist(m0) < 1e-12);
+        assert!(hermite.derivative(1f64).dist(m1) < 1e-12);
+        check_derivative(&hermite);
+
+        let bezier = hermite.to_bezier();
+        let back = bezier.to_hermite();
+        for cx in 0..=10 {
+            let t = cx as f64 / 10f64;
+            assert!(bezier.evaluate(t).dist(hermite.evaluate(t)) < 1e-9);
+            assert!(bezier.derivative(t).dist(hermite.derivative(t)) < 1e-9);
+        }
+        assert!(back.m0.dist(m0) < 1e-9 && back.m1.dist(m1) < 1e-9);
+    }
+
+    #[test]
+    fn test_curves_catmull_rom() {
+        let mut rng = test_util::rng();
+        let points: Vec<_> = (0..6).map(|_| rand_vector3(&mut rng, 5f64)).collect();
+        let spline = CatmullRom::centripetal(points.clone()).unwrap();
+        assert_eq!(spline.domain(), (0f64, 5f64));
+        assert_eq!(spline.segment_count(), 5);
+        for (cx, ca) in points.iter().enumerate() {
+            assert!(spline.evaluate(cx as f64).dist(*ca) < 1e-9);
+        }
+        check_derivative(&spline);
+
+        // the tangent keeps its direction across the points
+        for cx in 1..5 {
+            let before = spline.segment(cx - 1).derivative(1f64).normalize_or_zero();
+            let after = spline.segment(cx).derivative(0f64).normalize_or_zero();
+            assert!(before.dist(after) < 1e-9);
+        }
+
+        // repeated points stay finite
+        let p = Vector3::from([1f64, 2f64, 3f64]);
+        let q = Vector3::from([2f64, 2f64, 3f64]);
+        let spline = CatmullRom::centripetal(vec![p, p, q, q]).unwrap();
+        for cx in 0..=30 {
+            let point = spline.evaluate(cx as f64 / 10f64);
+            assert!(point.0.iter().all(|ca| ca.is_finite()));
+        }
+
+        let uniform = CatmullRom::new(vec![0f32, 1f32, 2f32, 3f32], 0f32).unwrap();
+        assert_eq!(uniform.evaluate(1.5f32), 1.5f32);
+        assert_eq!(uniform.points(), &[0f32, 1f32, 2f32, 3f32]);
+        assert_eq!(uniform.alpha(), 0f32);
+        assert_eq!(uniform.segment_count(), 3);
+        assert!(CatmullRom::centripetal(vec![p]).is_none());
+        assert!(CatmullRom::<f32>::centripetal(vec![]).is_none());
+    }
+
+    #[test]
+    fn test_curves_b_spline() {
+        let mut rng = test_util::rng();
+        let points: Vec<_> = (0..7).map(|_| rand_vector3(&mut rng, 5f64)).collect();
+
+        // the uniform cubic B-spline starts each segment at (p0 + 4 p1 + p2) / 6
+        let uniform = BSpline::uniform(points.clone(), 3).unwrap();
+        assert_eq!(uniform.domain(), (3f64, 7f64));
+        for cx in 0..4 {
+            let expected = points[cx]
+                .add(points[cx + 1].mul_scalar(4f64))
+                .add(points[cx + 2])
+                .div_scalar(6f64);
+            assert!(uniform.evaluate(cx as f64 + 3f64).dist(expected) < 1e-9);
+        }
+        check_derivative(&uniform);
+
+        // a clamped B-spline with 4 points is a cubic Bézier
+        let clamped = BSpline::clamped(points[..4].to_vec(), 3).unwrap();
+        assert_eq!(
+            clamped.knots(),
+            &[0f64, 0f64, 0f64, 0f64, 1f64, 1f64, 1f64, 1f64]
+        );
+        let bezier = CubicBezier::new(points[0], points[1], points[2], points[3]);
+        for cx in 0..=10 {
+            let t = cx as f64 / 10f64;
+            assert!(clamped.evaluate(t).dist(bezier.evaluate(t)) < 1e-9);
+            assert!(clamped.derivative(t).dist(bezier.derivative(t)) < 1e-9);
+        }
+
+        // non-uniform knots, with a double knot in the middle
+        let knots = vec![0f64, 0f64, 0f64, 1f64, 2f64, 2f64, 4f64, 5f64, 5f64, 5f64];
+        let spline = BSpline::new(points.clone(), knots, 2).unwrap();
+        assert!(spline.evaluate(0f64).dist(points[0]) < 1e-9);
+        assert!(spline.evaluate(5f64).dist(points[6]) < 1e-9);
+        // at the double knot it passes through its point
+        assert!(spline.evaluate(2f64).dist(points[3]) < 1e-9);
+        assert_eq!(spline.degree(), 2);
+        assert_eq!(spline.points().len(), 7);
+
+        // the weights add up to 1
+        let constant = BSpline::uniform(vec![2f32; 5], 2).unwrap();
+        assert!((constant.evaluate(3.3f32) - 2f32).abs() < 1e-6);
+        assert_eq!(constant.derivative(3.3f32), 0f32);
+
+        assert!(BSpline::uniform(points[..3].to_vec(), 3).is_none());
+        assert!(BSpline::new(points.clone(), vec![0f64; 11], 3).is_none());
+        assert!(BSpline::new(points.clone(), vec![0f64; 10], 2).is_none());
+        let decreasing = (0..10).map(|cx| 10f64 - cx as f64).collect();
+        assert!(BSpline::new(points, decreasing, 2).is_none());
+    }
+
+    #[test]
+    fn test_curves_arc_length() {
+        // a straight line with its control points bunched at the start
+        let line = CubicBezier::new(
+            Vector2::from([0f64, 0f64]),
+            Vector2::from([0.1f64, 0f64]),
+            Vector2::from([0.2f64, 0f64]),
+            Vector2::from([4f64, 0f64]),
+        );
+        let table = ArcLengthTable::new(&line, 1000);
+        assert!((table.length() - 4f64).abs() < 1e-9);
+        for cx in 0..=8 {
+            let distance = cx as f64 / 2f64;
+            let point = line.evaluate(table.param_at(distance));
+            assert!((point[0] - distance).abs() < 1e-3);
+        }
+        assert_eq!(table.param_at(-1f64), 0f64);
+        assert_eq!(table.param_at(10f64), 1f64);
+        assert!((table.distance_at(table.param_at(1.5f64)) - 1.5f64).abs() < 1e-9);
+
+        // the usual cubic approximation of a quarter circle
+        let k = 0.5522847498f64;
+        let arc = CubicBezier::new(
+            Vector2::from([1f64, 0f64]),
+            Vector2::from([1f64, k]),
+            Vector2::from([k, 1f64]),
+            Vector2::from([0f64, 1f64]),
+        );
+        let table = ArcLengthTable::new(&arc, 1000);
+        assert!((table.length() - std::f64::consts::FRAC_PI_2).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_curves_closest_point() {
+        let mut rng = test_util::rng();
+        let points: Vec<_> = (0..5).map(|_| rand_vector3(&mut rng, 5f64)).collect();
+        let spline = CatmullRom::centripetal(points.clone()).unwrap();
+        let bezier = CubicBezier::new(points[0], points[1], points[2], points[3]);
+        for _ in 0..20 {
+            let point = rand_vector3(&mut rng, 5f64);
+            for (closest, curve) in [
+                (
+                    spline.closest_point(point, 64),
+                    &spline as &dyn Curve<Value = _>,
+                ),
+                (bezier.closest_point(point, 64), &bezier),
+            ] {
+                let (start, end) = curve.domain();
+                let reference = (0..=20000)
+                    .map(|cx| curve.evaluate(start + (end - start) * cx as f64 / 20000f64))
+                    .map(|ca| ca.dist(point))
+                    .fold(f64::INFINITY, f64::min);
+                assert!(closest.dist(point) <= reference + 1e-9);
+            }
+        }
+        let t = CubicBezier::new(0f64, 1f64, 2f64, 3f64).closest_param(1.5f64, 8);
+        assert!((t - 0.5f64).abs() < 1e-9);
+    }
+}
+
+#[cfg(test)]
+mod test_scalar {
+    use crate::{
+        scalar::{
+            critically_damped_spring, easing, exp_decay, inverse_lerp, lerp, remap, smootherstep,
+        },
+        vector::{Vector2, Vector3},
+    };
+
+    type Easing = fn(f64) -> f64;
+
+    /// The easings as (in, out, in_out), and if they stay within 0 to 1
+    fn easings() -> [(Easing, Easing, Easing, bool); 10] {
+        [
+            (easing::in_quad, easing::out_quad, easing::in_out_quad, true),
+            (
+                easing::in_cubic,
+                easing::out_cubic,
+                easing::in_out_cubic,
+                true,
+            ),
+            (
+                easing::in_quart,
+                easing::out_quart,
+                easing::in_out_quart,
+                true,
+            ),
+            (
+                easing::in_quint,
+                easing::out_quint,
+                easing::in_out_quint,
+                true,
+            ),
+            (easing::in_sine, easing::out_sine, easing::in_out_sine, true),
+            (easing::in_expo, easing::out_expo, easing::in_out_expo, true),
+            (easing::in_circ, easing::out_circ, easing::in_out_circ, true),
+            (
+                easing::in_back,
+                easing::out_back,
+                easing::in_out_back,
+                false,
+            ),
+            (
+                easing::in_elastic,
+                easing::out_elastic,
+                easing::in_out_elastic,
+                false,
+            ),
+            (
+                easing::in_bounce,
+                easing::out_bounce,
+                easing::in_out_bounce,
+                true,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_scalar_easing_boundaries() {
+        for (ease_in, ease_out, ease_in_out, bounded) in easings() {
+            for ease in [ease_in, ease_out, ease_in_out] {
+                assert!(ease(0f64).abs() < 1e-12);
+                assert!((ease(1f64) - 1f64).abs() < 1e-12);
+            }
+            assert!((ease_in_out(0.5f64) - 0.5f64).abs() < 1e-12);
+            for cx in 0..=100 {
+                let t = cx as f64 / 100f64;
+                // out mirrors in, and in_out is symmetric about the middle
+                assert!((ease_out(t) - (1f64 - ease_in(1f64 - t))).abs() < 1e-12);
+                assert!((ease_in_out(t) - (1f64 - ease_in_out(1f64 - t))).abs() < 1e-12);
+                if bounded {
+                    assert!((0f64..=1f64).contains(&ease_in(t)));
+                    assert!((0f64..=1f64).contains(&ease_in_out(t)));
+                }
+            }
+        }
+
+        // the easings with no overshoot or bounce only go up
+        for (ease_in, ease_out, ease_in_out, _) in &easings()[..7] {
+            for ease in [ease_in, ease_out, ease_in_out] {
+                for cx in 0..100 {
+                    let t = cx as f64 / 100f64;
+                    assert!(ease(t) <= ease(t + 0.01f64));
+                }
+            }
+        }
+
+        assert!(easing::in_back(0.5f64) < 0f64);
+        assert!(easing::out_back(0.5f64) > 0.5f64);
+        assert!((0..100).any(|cx| easing::in_elastic(cx as f64 / 100f64) < 0f64));
+        assert!((0..100).any(|cx| easing::out_elastic(cx as f64 / 100f64) > 1f64));
+        assert!((easing::out_bounce(1f64 / 2.75f64) - 1f64).abs() < 1e-12);
+        assert_eq!(easing::in_quad(0.5f32), 0.25f32);
+    }
+
+    #[test]
+    fn test_scalar_interpolation() {
+        assert_eq!(inverse_lerp(2f32, 6f32, 2f32), 0f32);
+        assert_eq!(inverse_lerp(2f32, 6f32, 6f32), 1f32);
+        assert_eq!(inverse_lerp(6f32, 2f32, 5f32), 0.25f32);
+        for cx in 0..=10 {
+            let t = cx as f64 / 10f64;
+            assert!((inverse_lerp(-3f64, 7f64, lerp(-3f64, 7f64, t)) - t).abs() < 1e-12);
+        }
+        assert_eq!(remap(0f32, 1f32, -1f32, 1f32, 0f32), -1f32);
+        assert_eq!(remap(0f32, 1f32, -1f32, 1f32, 1f32), 1f32);
+        assert_eq!(remap(0f32, 1f32, -1f32, 1f32, 2f32), 3f32);
+
+        assert_eq!(smootherstep(0f32, 1f32, -1f32), 0f32);
+        assert_eq!(smootherstep(0f32, 1f32, 0f32), 0f32);
+        assert_eq!(smootherstep(0f32, 1f32, 0.5f32), 0.5f32);
+        assert_eq!(smootherstep(0f32, 1f32, 1f32), 1f32);
+        assert_eq!(smootherstep(0f32, 1f32, 2f32), 1f32);
+        // flat at the edges
+        let h = 1e-4f64;
+        assert!(smootherstep(0f64, 1f64, h) / h < 1e-6);
+        assert!((1f64 - smootherstep(0f64, 1f64, 1f64 - h)) / h < 1e-6);
+    }
+
+    #[test]
+    fn test_scalar_damping() {
+        assert_eq!(exp_decay(3f32, 10f32, 4f32, 0f32), 3f32);
+        assert_eq!(exp_decay(3f32, 10f32, 4f32, 1e3f32), 10f32);
+        assert_eq!(exp_decay(10f32, 10f32, 4f32, 0.1f32), 10f32);
+
+        // the spring gets there at any frame rate, and never overshoots
+        for dt in [1f64 / 30f64, 1f64 / 60f64, 1f64 / 144f64, 0.5f64] {
+            for (start, target) in [(0f64, 10f64), (5f64, -5f64)] {
+                let mut value = start;
+                let mut velocity = 0f64;
+                for _ in 0..(3f64 / dt) as usize {
+                    value = critically_damped_spring(value, target, &mut velocity, 0.2f64, dt);
+                    assert!((value - start).abs() <= (target - start).abs());
+                }
+                assert!((value - target).abs() < 1e-3, "{dt} {value}");
+            }
+        }
+        let mut velocity = 0f32;
+        assert_eq!(
+            critically_damped_spring(1f32, 1f32, &mut velocity, 0.2f32, 0.1f32),
+            1f32
+        );
+        assert_eq!(velocity, 0f32);
+    }
+
+    #[test]
+    fn test_scalar_vectors() {
+        let v = Vector3::from([0.25f32, 2f32, -1f32]);
+        let a = Vector3::from([0f32, 1f32, -3f32]);
+        let b = Vector3::from([1f32, 3f32, 1f32]);
+        assert_eq!(
+            v.inverse_lerp(a, b),
+            Vector3::from([0.25f32, 0.5f32, 0.5f32])
+        );
+        assert_eq!(
+            v.remap(a, b, Vector3::from([0f32; 3]), Vector3::from([4f32; 3])),
+            Vector3::from([1f32, 2f32, 2f32])
+        );
+        assert_eq!(
+            v.smootherstep(a, b),
+            Vector3::from([smootherstep(0f32, 1f32, 0.25f32), 0.5f32, 0.5f32])
+        );
+        let decayed = v.exp_decay(b, 3f32, 0.1f32);
+        for cx in 0..3 {
+            assert_eq!(decayed[cx], exp_decay(v[cx], b[cx], 3f32, 0.1f32));
+        }
+
+        let mut value = Vector2::from([0f32, 4f32]);
+        let mut velocity = Vector2::from([0f32; 2]);
+        let mut scalar = (0f32, 0f32);
+        for _ in 0..10 {
+            value = value.critically_damped_spring(
+                Vector2::from([2f32, -4f32]),
+                &mut velocity,
+                0.3f32,
+                0.05f32,
+            );
+            scalar.0 = critically_damped_spring(scalar.0, 2f32, &mut scalar.1, 0.3f32, 0.05f32);
+            assert_eq!(value[0], scalar.0);
+            assert_eq!(velocity[0], scalar.1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_angle {
+    use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+    use crate::{
+        angle::{Deg, Rad},
+        vector::{Vector2, Vector3},
+    };
+
+    #[test]
+    fn test_angle_conversion() {
+        assert_eq!(Rad::from(Deg(180f64)), Rad(PI));
+        assert_eq!(Deg::from(Rad(FRAC_PI_2)), Deg(90f64));
+        assert_eq!(Rad::from(1.5f32), Rad(1.5f32));
+        assert_eq!(Rad::<f32>::full_turn(), Rad(std::f32::consts::TAU));
+        assert_eq!(Deg::<f32>::half_turn(), Deg(180f32));
+        assert_eq!(Deg(30f64).sin(), Rad::from(Deg(30f64)).sin());
+        assert!((Deg(30f64).sin() - 0.5f64).abs() < 1e-12);
+        let (sin, cos) = Deg(90f32).sin_cos();
+        assert!((sin - 1f32).abs() < 1e-6 && cos.abs() < 1e-6);
+        assert!((Deg(45f64).tan() - 1f64).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_angle_arithmetic() {
+        let mut a = Deg(30f32);
+        assert_eq!(a + Deg(15f32), Deg(45f32));
+        assert_eq!(a - Deg(45f32), Deg(-15f32));
+        assert_eq!(-a, Deg(-30f32));
+        assert_eq!(a * 3f32, Deg(90f32));
+        assert_eq!(a / 2f32, Deg(15f32));
+        assert_eq!(Deg(90f32) / a, 3f32);
+        a += Deg(10f32);
+        a -= Deg(4f32);
+        a *= 2f32;
+        a /= 4f32;
+        assert_eq!(a, Deg(18f32));
+        assert!(Rad(1f32) < Rad(2f32));
+    }
+
+    #[test]
+    fn test_angle_normalize() {
+        // the boundaries go to the start of the range
+        assert_eq!(Deg(0f64).normalize(), Deg(0f64));
+        assert_eq!(Deg(360f64).normalize(), Deg(0f64));
+        assert_eq!(Deg(-360f64).normalize(), Deg(0f64));
+        assert_eq!(Deg(720f64 + 45f64).normalize(), Deg(45f64));
+        assert_eq!(Deg(-45f64).normalize(), Deg(315f64));
+        assert_eq!(Deg(180f64).normalize_signed(), Deg(-180f64));
+        assert_eq!(Deg(-180f64).normalize_signed(), Deg(-180f64));
+        assert_eq!(Deg(270f64).normalize_signed(), Deg(-90f64));
+        assert_eq!(Deg(-1e-20f64).normalize(), Deg(0f64));
+        assert_eq!(Rad(TAU).normalize(), Rad(0f64));
+        assert!((Rad(3f64 * PI).normalize().0 - PI).abs() < 1e-12);
+
+        for cx in -100..100 {
+            let angle = Rad(cx as f64 * 0.37f64);
+            let wrapped = angle.normalize();
+            let signed = angle.normalize_signed();
+            assert!((0f64..TAU).contains(&wrapped.0));
+            assert!((-PI..PI).contains(&signed.0));
+            assert!((wrapped.sin() - angle.sin()).abs() < 1e-9);
+            assert!((signed.cos() - angle.cos()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_angle_shortest_difference() {
+        assert_eq!(Deg(350f32).shortest_difference(Deg(10f32)), Deg(20f32));
+        assert_eq!(Deg(10f32).shortest_difference(Deg(350f32)), Deg(-20f32));
+        assert_eq!(Deg(-170f32).shortest_difference(Deg(170f32)), Deg(-20f32));
+        assert_eq!(Deg(0f32).shortest_difference(Deg(180f32)), Deg(-180f32));
+        assert_eq!(
+            Deg(90f32).shortest_difference(Deg(90f32 + 720f32)),
+            Deg(0f32)
+        );
+        assert_eq!(Deg(350f32).lerp_shortest(Deg(10f32), 0.5f32), Deg(360f32));
+    }
+
+    #[test]
+    fn test_angle_constructors() {
+        let polar = Vector2::from_polar(Deg(60f32), 2f32);
+        assert!(polar.dist(Vector2::from_polar(Rad(PI as f32 / 3f32), 2f32)) < 1e-6);
+        assert!(polar.dist(Vector2::from([1f32, 3f32.sqrt()])) < 1e-6);
+        let spherical = Vector3::from_spherical(Deg(90f64), Deg(90f64), 3f64);
+        assert!(spherical.dist(Vector3::from([0f64, 3f64, 0f64])) < 1e-12);
+        assert_eq!(
+            Vector3::from_spherical(Deg(45f64), Rad(0.5f64), 1f64),
+            Vector3::from_spherical(PI / 4f64, 0.5f64, 1f64)
+        );
+    }
+
+    #[cfg(feature = "quaternions")]
+    #[test]
+    fn test_angle_quaternions() {
+        use crate::quaternions::Quaternion;
+
+        let axis = Vector3::from([1f64, 2f64, -1f64]);
+        let q = Quaternion::from_axis_angle(axis, Deg(120f64));
+        assert!(q.eq_fast(Quaternion::from_axis_angle(axis, 2f64 * PI / 3f64), 1e-12));
+        assert!(q.eq_fast(
+            Quaternion::from_axis_angle(axis, Rad(2f64 * PI / 3f64)),
+            1e-12
+        ));
+        let e = Quaternion::from_euler(Deg(10f64), Deg(-20f64), Deg(30f64));
+        let (x, y, z) = e.to_euler();
+        assert!((Deg::from(Rad(x)).0 - 10f64).abs() < 1e-9);
+        assert!((Deg::from(Rad(y)).0 + 20f64).abs() < 1e-9);
+        assert!((Deg::from(Rad(z)).0 - 30f64).abs() < 1e-9);
+        assert!(e.eq_fast(
+            Quaternion::from_euler(Rad(10f64.to_radians()), -20f64.to_radians(), Deg(30f64)),
+            1e-12
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test_coordinates {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use rand::Rng;
+
+    use crate::{
+        angle::{Deg, Rad},
+        geodetic::Ellipsoid,
+        test_util::{self, rand_vector3},
+        vector::{Vector2, Vector3},
+    };
+
+    /// The unit axes and their negatives, then some diagonals
+    fn directions() -> Vec<Vector3<f64>> {
+        let mut ret = vec![
+            Vector3([1f64, 0f64, 0f64]),
+            Vector3([-1f64, 0f64, 0f64]),
+            Vector3([0f64, 1f64, 0f64]),
+            Vector3([0f64, -1f64, 0f64]),
+            Vector3([0f64, 0f64, 1f64]),
+            Vector3([0f64, 0f64, -1f64]),
+            Vector3([-1f64, -1f64, -1f64]),
+            Vector3([1f64, -2f64, 3f64]),
+            Vector3([-0f64, -0f64, 2f64]),
+            Vector3([-3f64, -0f64, 0f64]),
+        ];
+        let mut rng = test_util::rng();
+        for _ in 0..200 {
+            ret.push(rand_vector3(&mut rng, 10f64));
+        }
+        ret
+    }
+
+    #[test]
+    fn test_polar() {
+        assert_eq!(Vector2([2f64, 0f64]).to_polar(), (Rad(0f64), 2f64));
+        assert_eq!(Vector2([0f64, 2f64]).to_polar(), (Rad(FRAC_PI_2), 2f64));
+        assert_eq!(Vector2([-2f64, 0f64]).to_polar(), (Rad(PI), 2f64));
+        assert_eq!(Vector2([-2f64, -0f64]).to_polar(), (Rad(PI), 2f64));
+        assert_eq!(Vector2([0f64, -2f64]).to_polar(), (Rad(-FRAC_PI_2), 2f64));
+        assert_eq!(Vector2([0f64, 0f64]).to_polar(), (Rad(0f64), 0f64));
+        assert_eq!(Vector2([-0f64, -0f64]).to_polar(), (Rad(0f64), 0f64));
+
+        for v in directions() {
+            let v = Vector2([v[0], v[1]]);
+            let (phi, magnitude) = v.to_polar();
+            assert!(phi.0 > -PI && phi.0 <= PI);
+            assert!(Vector2::from_polar(phi, magnitude).dist(v) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_spherical() {
+        assert_eq!(
+            Vector3([0f64, 0f64, 3f64]).to_spherical(),
+            (Rad(0f64), Rad(0f64), 3f64)
+        );
+        assert_eq!(
+            Vector3([0f64, 0f64, -3f64]).to_spherical(),
+            (Rad(PI), Rad(0f64), 3f64)
+        );
+        assert_eq!(
+            Vector3([-0f64, -0f64, -3f64]).to_spherical(),
+            (Rad(PI), Rad(0f64), 3f64)
+        );
+        assert_eq!(
+            Vector3([-1f64, 0f64, 0f64]).to_spherical(),
+            (Rad(FRAC_PI_2), Rad(PI), 1f64)
+        );
+        assert_eq!(
+            Vector3([0f64, -1f64, 0f64]).to_spherical(),
+            (Rad(FRAC_PI_2), Rad(-FRAC_PI_2), 1f64)
+        );
+        assert_eq!(
+            Vector3([0f64, 0f64, 0f64]).to_spherical(),
+            (Rad(0f64), Rad(0f64), 0f64)
+        );
+
+        for v in directions() {
+            let (theta, phi, magnitude) = v.to_spherical();
+            assert!(theta.0 >= 0f64 && theta.0 <= PI);
+            assert!(phi.0 > -PI && phi.0 <= PI);
+            assert!((magnitude - v.len()).abs() < 1e-12);
+            assert!(Vector3::from_spherical(theta, phi, magnitude).dist(v) < 1e-12);
+        }
+
+        // degrees come back the same way
+        let v = Vector3::from_spherical(Deg(120f64), Deg(-45f64), 2f64);
+        let (theta, phi, magnitude) = v.to_spherical();
+        assert!((Deg::from(theta).0 - 120f64).abs() < 1e-9);
+        assert!((Deg::from(phi).0 + 45f64).abs() < 1e-9);
+        assert!((magnitude - 2f64).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cylindrical() {
+        assert_eq!(
+            Vector3([0f64, 0f64, -4f64]).to_cylindrical(),
+            (Rad(0f64), 0f64, -4f64)
+        );
+        assert_eq!(
+            Vector3([-2f64, 0f64, 1f64]).to_cylindrical(),
+            (Rad(PI), 2f64, 1f64)
+        );
+
+        for v in directions() {
+            let (phi, radius, height) = v.to_cylindrical();
+            assert!(phi.0 > -PI && phi.0 <= PI);
+            assert_eq!(height, v[2]);
+            assert!(Vector3::from_cylindrical(phi, radius, height).dist(v) < 1e-12);
+
+            // the same angle as polar coordinates of x and y
+            assert_eq!((phi, radius), Vector2([v[0], v[1]]).to_polar());
+        }
+    }
+
+    #[test]
+    fn test_geodetic_sphere() {
+        let sphere = Ellipsoid::sphere(2f64);
+        let north = Vector3::from_geodetic(Rad(FRAC_PI_2), Rad(0f64), 1f64, sphere);
+        assert!(north.dist(Vector3([0f64, 0f64, 3f64])) < 1e-12);
+
+        // latitude is the complement of the spherical theta
+        for v in directions() {
+            let (theta, phi, magnitude) = v.to_spherical();
+            let (latitude, longitude, height) = v.to_geodetic(sphere);
+            assert!((latitude.0 - (FRAC_PI_2 - theta.0)).abs() < 1e-12);
+            assert!((longitude.0 - phi.0).abs() < 1e-12);
+            assert!((height - (magnitude - 2f64)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_geodetic_wgs84() {
+        let wgs84 = Ellipsoid::<f64>::wgs84();
+        let a = wgs84.semi_major;
+        let b = wgs84.semi_minor;
+        assert!((b - 6356752.314245f64).abs() < 1e-5);
+
+        // the poles and the axes on the equator
+        let cases = [
+            (Vector3([0f64, 0f64, b]), (FRAC_PI_2, 0f64)),
+            (Vector3([0f64, 0f64, -b]), (-FRAC_PI_2, 0f64)),
+            (Vector3([a, 0f64, 0f64]), (0f64, 0f64)),
+            (Vector3([-a, 0f64, 0f64]), (0f64, PI)),
+            (Vector3([0f64, a, 0f64]), (0f64, FRAC_PI_2)),
+            (Vector3([0f64, -a, 0f64]), (0f64, -FRAC_PI_2)),
+        ];
+        for (v, (latitude, longitude)) in cases {
+            let ret = v.to_geodetic(wgs84);
+            assert!((ret.0 .0 - latitude).abs() < 1e-12, "{v:?} {ret:?}");
+            assert!((ret.1 .0 - longitude).abs() < 1e-12, "{v:?} {ret:?}");
+            assert!(ret.2.abs() < 1e-6, "{v:?} {ret:?}");
+            let back = Vector3::from_geodetic(ret.0, ret.1, ret.2, wgs84);
+            assert!(back.dist(v) < 1e-6);
+        }
+
+        let mut rng = test_util::rng();
+        for _ in 0..1000 {
+            let latitude = rng.gen_range(-90f64..=90f64);
+            let longitude = rng.gen_range(-179.9f64..180f64);
+            let height = rng.gen_range(-10_000f64..1_000_000f64);
+            let v = Vector3::from_geodetic(Deg(latitude), Deg(longitude), height, wgs84);
+            let ret = v.to_geodetic(wgs84);
+            assert!(
+                (Deg::from(ret.0).0 - latitude).abs() < 1e-9,
+                "{latitude} {ret:?}"
+            );
+            assert!((ret.2 - height).abs() < 1e-6, "{height} {ret:?}");
+            if latitude.abs() < 89.9f64 {
+                assert!((Deg::from(ret.1).0 - longitude).abs() < 1e-9);
+            }
+            assert!(Vector3::from_geodetic(ret.0, ret.1, ret.2, wgs84).dist(v) < 1e-6);
+
+            // the height is along the normal of the surface
+            let surface = Vector3::from_geodetic(ret.0, ret.1, 0f64, wgs84);
+            let gradient = Vector3([
+                surface[0] / (a * a),
+                surface[1] / (a * a),
+                surface[2] / (b * b),
+            ]);
+            let offset = v.sub(surface);
+            if offset.len() > 1f64 {
+                let cos = offset
+                    .normalize_or_zero()
+                    .mul_inner(gradient.normalize_or_zero());
+                assert!((cos.abs() - 1f64).abs() < 1e-9);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "quaternions"))]
+mod test_coordinate_system {
+    use rand::Rng;
+
+    use crate::{
+        angle::Deg,
+        coordinate_system::{Axis, ConversionBuilder, CoordinateSystem, Handedness},
+        matrix::{Matrix3x3, Matrix4x4},
+        test_util::{self, rand_vector3},
+        vector::Vector3,
+    };
+
+    fn systems() -> Vec<CoordinateSystem> {
+        vec![
+            CoordinateSystem::GLTF,
+            CoordinateSystem::BLENDER,
+            CoordinateSystem::UNITY,
+            // Unreal's, with +X forward
+            CoordinateSystem::new(Axis::PositiveZ, Axis::PositiveX, Handedness::Left).unwrap(),
+            CoordinateSystem::new(Axis::NegativeX, Axis::NegativeZ, Handedness::Right).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_coordinate_system_axes() {
+        assert_eq!(CoordinateSystem::GLTF.right(), Axis::NegativeX);
+        assert_eq!(CoordinateSystem::BLENDER.right(), Axis::NegativeX);
+        assert_eq!(CoordinateSystem::UNITY.right(), Axis::PositiveX);
+        assert_eq!(systems()[3].right(), Axis::PositiveY);
+        assert_eq!(
+            CoordinateSystem::new(Axis::PositiveY, Axis::NegativeY, Handedness::Right),
+            None
+        );
+        assert_eq!(
+            Axis::NegativeZ.to_vector::<f32>(),
+            Vector3([0f32, 0f32, -1f32])
+        );
+        assert_eq!(Axis::NegativeZ.negate(), Axis::PositiveZ);
+
+        for system in systems() {
+            let basis = system.basis::<f64>();
+            let right = system.right().to_vector::<f64>();
+            let up = system.up().to_vector::<f64>();
+            let forward = system.forward().to_vector::<f64>();
+            assert_eq!(basis.mul_vector(Vector3([1f64, 0f64, 0f64])), right);
+            assert_eq!(basis.mul_vector(Vector3([0f64, 1f64, 0f64])), up);
+            assert_eq!(basis.mul_vector(Vector3([0f64, 0f64, 1f64])), forward);
+            // the handedness is where forward cross up points
+            let expected = match system.handedness() {
+                Handedness::Right => right,
+                Handedness::Left => right.mul_scalar(-1f64),
+            };
+            assert_eq!(forward.mul_cross(up), expected);
+        }
+    }
+
+    #[test]
+    fn test_coordinate_system_known() {
+        let v = Vector3([1f64, 2f64, 3f64]);
+        let convert = |source, target| ConversionBuilder::new(source, target).build();
+
+        let blender_to_gltf = convert(CoordinateSystem::BLENDER, CoordinateSystem::GLTF);
+        assert!(!blender_to_gltf.flips_handedness());
+        assert_eq!(
+            blender_to_gltf.convert_point(v),
+            Vector3([1f64, 3f64, -2f64])
+        );
+        let gltf_to_unity = convert(CoordinateSystem::GLTF, CoordinateSystem::UNITY);
+        assert!(gltf_to_unity.flips_handedness());
+        assert_eq!(gltf_to_unity.convert_point(v), Vector3([-1f64, 2f64, 3f64]));
+        let blender_to_unity = convert(CoordinateSystem::BLENDER, CoordinateSystem::UNITY);
+        assert_eq!(
+            blender_to_unity.matrix3(),
+            gltf_to_unity
+                .matrix3()
+                .mul_matrix(blender_to_gltf.matrix3())
+        );
+        assert_eq!(
+            convert(CoordinateSystem::UNITY, CoordinateSystem::UNITY).matrix3(),
+            Matrix3x3::ident()
+        );
+
+        // the same turn to the left, about up, in glTF and in Unity
+        let turn_left = crate::quaternions::Quaternion::from_axis_angle(
+            Vector3([0f64, 1f64, 0f64]),
+            Deg(90f64),
+        );
+        let forward = Vector3([0f64, 0f64, 1f64]);
+        let left_gltf = turn_left.rotate_vector(forward);
+        assert!(left_gltf.dist(Vector3([1f64, 0f64, 0f64])) < 1e-12);
+        let turn_left_unity = gltf_to_unity.convert_quaternion(turn_left);
+        let left_unity = turn_left_unity.rotate_vector(forward);
+        assert!(left_unity.dist(Vector3([-1f64, 0f64, 0f64])) < 1e-12);
+        let expected = crate::quaternions::Quaternion::from_axis_angle(
+            Vector3([0f64, -1f64, 0f64]),
+            Deg(90f64),
+        );
+        assert!(turn_left_unity.dist(expected) < 1e-12);
+    }
+
+    #[test]
+    fn test_coordinate_system_conversions() {
+        let mut rng = test_util::rng();
+        for source in systems() {
+            for target in systems() {
+                let scale = rng.gen_range(0.01f64..100f64);
+                let conversion = ConversionBuilder::new(source, target).scale(scale).build();
+                let basis = conversion.matrix3();
+                assert_eq!(conversion.scale(), scale);
+                assert_eq!(
+                    basis.det() < 0f64,
+                    conversion.flips_handedness(),
+                    "{source:?} {target:?}"
+                );
+                assert_eq!(
+                    conversion.flips_handedness(),
+                    source.handedness() != target.handedness()
+                );
+                assert_eq!(basis.mul_matrix(basis.transpose()), Matrix3x3::ident());
+
+                // up, forward and right are kept
+                for (from, to) in [
+                    (source.up(), target.up()),
+                    (source.forward(), target.forward()),
+                    (source.right(), target.right()),
+                ] {
+                    assert_eq!(
+                        conversion.convert_direction(from.to_vector()),
+                        to.to_vector()
+                    );
+                }
+
+                let inverse = conversion.inverse();
+                let matrix4 = conversion.matrix4();
+                for _ in 0..20 {
+                    let v = rand_vector3(&mut rng, 10f64);
+                    let point = conversion.convert_point(v);
+                    assert!(point.dist(basis.mul_vector(v).mul_scalar(scale)) < 1e-9);
+                    assert!(point.dist(matrix4.transform_point(v)) < 1e-9);
+                    assert!(inverse.convert_point(point).dist(v) < 1e-9);
+
+                    // rotations act the same way on the converted Vectors
+                    let axis = rand_vector3(&mut rng, 10f64).normalize_or_zero();
+                    let angle = rng.gen_range(-3f64..3f64);
+                    let rotation = crate::quaternions::Quaternion::from_axis_angle(axis, angle);
+                    let converted = conversion.convert_quaternion(rotation);
+                    let expected = conversion.convert_direction(rotation.rotate_vector(v));
+                    assert!(
+                        converted
+                            .rotate_vector(conversion.convert_direction(v))
+                            .dist(expected)
+                            < 1e-9
+                    );
+                    let back = inverse.convert_quaternion(converted);
+                    assert!(back.dist(rotation) < 1e-12);
+
+                    let matrix = Matrix3x3(std::array::from_fn(|_| rand_vector3(&mut rng, 10f64)));
+                    assert!(
+                        conversion
+                            .convert_matrix3(matrix)
+                            .mul_vector(conversion.convert_direction(v))
+                            .dist(conversion.convert_direction(matrix.mul_vector(v)))
+                            < 1e-9
+                    );
+
+                    let translation = rand_vector3(&mut rng, 10f64);
+                    let transform = Matrix4x4::from([
+                        matrix[0][0],
+                        matrix[0][1],
+                        matrix[0][2],
+                        translation[0],
+                        matrix[1][0],
+                        matrix[1][1],
+                        matrix[1][2],
+                        translation[1],
+                        matrix[2][0],
+                        matrix[2][1],
+                        matrix[2][2],
+                        translation[2],
+                        0f64,
+                        0f64,
+                        0f64,
+                        1f64,
+                    ]);
+                    let converted_transform = conversion.convert_transform(transform);
+                    assert!(
+                        converted_transform
+                            .transform_point(point)
+                            .dist(conversion.convert_point(transform.transform_point(v)))
+                            < 1e-9
+                    );
+                }
+            }
+        }
+    }
 }
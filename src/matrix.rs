@@ -38,6 +38,13 @@ macro_rules! GENERATE_MATRIX {
                     }
                 }
 
+                impl From<[<Matrix $n x $n>]<f32>> for [<Matrix $n x $n>]<f64> {
+                    #[inline(always)]
+                    fn from(value: [<Matrix $n x $n>]<f32>) -> Self {
+                        value.cast()
+                    }
+                }
+
                 impl<T: Element> Index<usize> for [<Matrix $n x $n>]<T> {
                     type Output = [<Vector $n>]<T>;
                     #[inline(always)]
@@ -64,6 +71,49 @@ macro_rules! GENERATE_MATRIX {
                     }
                 }
 
+                #[cfg(feature = "approx")]
+                impl<T: Element + approx::AbsDiffEq<Epsilon = T>> approx::AbsDiffEq for [<Matrix $n x $n>]<T> {
+                    type Epsilon = T;
+                    #[inline(always)]
+                    fn default_epsilon() -> T {
+                        T::default_epsilon()
+                    }
+                    #[inline(always)]
+                    fn abs_diff_eq(&self, other: &Self, epsilon: T) -> bool {
+                        self.into_iter()
+                            .zip(other.into_iter())
+                            .all(|(ca, cb)| ca.abs_diff_eq(&cb, epsilon))
+                    }
+                }
+
+                #[cfg(feature = "approx")]
+                impl<T: Element + approx::RelativeEq<Epsilon = T>> approx::RelativeEq for [<Matrix $n x $n>]<T> {
+                    #[inline(always)]
+                    fn default_max_relative() -> T {
+                        T::default_max_relative()
+                    }
+                    #[inline(always)]
+                    fn relative_eq(&self, other: &Self, epsilon: T, max_relative: T) -> bool {
+                        self.into_iter()
+                            .zip(other.into_iter())
+                            .all(|(ca, cb)| ca.relative_eq(&cb, epsilon, max_relative))
+                    }
+                }
+
+                #[cfg(feature = "approx")]
+                impl<T: Element + approx::UlpsEq<Epsilon = T>> approx::UlpsEq for [<Matrix $n x $n>]<T> {
+                    #[inline(always)]
+                    fn default_max_ulps() -> u32 {
+                        T::default_max_ulps()
+                    }
+                    #[inline(always)]
+                    fn ulps_eq(&self, other: &Self, epsilon: T, max_ulps: u32) -> bool {
+                        self.into_iter()
+                            .zip(other.into_iter())
+                            .all(|(ca, cb)| ca.ulps_eq(&cb, epsilon, max_ulps))
+                    }
+                }
+
                 impl<T: Element> IntoIterator for [<Matrix $n x $n>]<T> {
                     type Item = [<Vector $n>]<T>;
                     type IntoIter = std::array::IntoIter<[<Vector $n>]<T>, $n>;
@@ -81,7 +131,85 @@ macro_rules! GENERATE_MATRIX {
                          Self(unsafe {MaybeUninit::<[[<Vector $n>]<T>; $n]>::uninit().assume_init()})
                     }
 
+                    /// Creates a Matrix from its rows.
+                    /// This is the same as `Self::from`, since
+                    /// Matrices are stored row-major
+                    #[inline(always)]
+                    pub fn from_rows(rows: [[<Vector $n>]<T>; $n]) -> Self {
+                        Self(rows)
+                    }
+
+                    /// Creates a Matrix from its columns
+                    #[inline(always)]
+                    pub fn from_cols(cols: [[<Vector $n>]<T>; $n]) -> Self {
+                        Self(cols).transpose()
+                    }
+
+                    /// Gets the row at the given index
+                    #[inline(always)]
+                    pub fn row(self, index: usize) -> [<Vector $n>]<T> {
+                        self[index]
+                    }
+
+                    /// Gets the column at the given index
+                    #[inline(always)]
+                    pub fn col(self, index: usize) -> [<Vector $n>]<T> {
+                        let mut ret = [<Vector $n>]::new_uninit();
+                        for cx in 0..$n {
+                            ret[cx] = self[cx][index];
+                        }
+                        ret
+                    }
+
+                    /// Sets the row at the given index
+                    #[inline(always)]
+                    pub fn set_row(&mut self, index: usize, row: [<Vector $n>]<T>) {
+                        self[index] = row;
+                    }
+
+                    /// Sets the column at the given index
+                    #[inline(always)]
+                    pub fn set_col(&mut self, index: usize, col: [<Vector $n>]<T>) {
+                        for cx in 0..$n {
+                            self[cx][index] = col[cx];
+                        }
+                    }
+
+                    /// Gives the elements row after row (row-major),
+                    /// which is how the Matrix is stored
+                    #[inline(always)]
+                    pub fn to_rows_array(self) -> [T; $n * $n] {
+                        let mut ret = [T::zero(); $n * $n];
+                        for cx in 0..$n {
+                            for cy in 0..$n {
+                                ret[cx * $n + cy] = self[cx][cy];
+                            }
+                        }
+                        ret
+                    }
+
+                    /// Gives the elements column after column (column-major).
+                    /// This is the layout OpenGL (without transpose), Vulkan and WGSL expect,
+                    /// so it can be uploaded directly (e.g. through `bytemuck::cast_slice`)
+                    #[inline(always)]
+                    pub fn to_cols_array(self) -> [T; $n * $n] {
+                        self.transpose().to_rows_array()
+                    }
+
+                    /// Gives the rows as arrays
+                    #[inline(always)]
+                    pub fn to_rows_array_2d(self) -> [[T; $n]; $n] {
+                        self.0.map(|row| row.0)
+                    }
+
+                    /// Gives the columns as arrays
+                    #[inline(always)]
+                    pub fn to_cols_array_2d(self) -> [[T; $n]; $n] {
+                        self.transpose().to_rows_array_2d()
+                    }
+
                     /// Does element-wise addition
+                    #[allow(clippy::should_implement_trait)]
                     #[inline(always)]
                     pub fn add(mut self, addend: Self) -> Self {
                         for cx in 0..$n {
@@ -91,6 +219,7 @@ macro_rules! GENERATE_MATRIX {
                     }
 
                     /// Does element-wise subtraction
+                    #[allow(clippy::should_implement_trait)]
                     #[inline(always)]
                     pub fn sub(mut self, subtrahend: Self) -> Self {
                         for cx in 0..$n {
@@ -114,6 +243,10 @@ macro_rules! GENERATE_MATRIX {
                     /// Multiplies the Matrix and Vector together
                     #[inline(always)]
                     pub fn mul_vector(self, vector:[<Vector $n>]<T>) -> [<Vector $n>]<T> {
+                        #[cfg(feature = "simd")]
+                        if let (Some(ca), Some(cb)) = (crate::simd::downcast(self), crate::simd::downcast(vector)) {
+                            return crate::simd::downcast(crate::simd::mul_vector4x4(ca, cb)).unwrap();
+                        }
                         let mut ret = [<Vector $n>]::new_uninit();
                         for cx in 0..$n {
                             ret[cx] = self[cx].mul_inner(vector);
@@ -124,6 +257,10 @@ macro_rules! GENERATE_MATRIX {
                     /// Multiplies two Matrix's together
                     #[inline(always)]
                     pub fn mul_matrix(self, other: Self) -> Self {
+                        #[cfg(feature = "simd")]
+                        if let (Some(ca), Some(cb)) = (crate::simd::downcast(self), crate::simd::downcast(other)) {
+                            return crate::simd::downcast(crate::simd::mul_matrix4x4(ca, cb)).unwrap();
+                        }
                         let other_transpose = other.transpose();
                         let mut data = unsafe { MaybeUninit::<[T; $n *$n]>::uninit().assume_init() };
 
@@ -148,6 +285,31 @@ macro_rules! GENERATE_MATRIX {
                         ret
                     }
 
+                    /// Converts each element to another Element type,
+                    /// see [`Vector2::cast`]
+                    #[inline(always)]
+                    pub fn cast<U: Element>(self) -> [<Matrix $n x $n>]<U> {
+                        [<Matrix $n x $n>](self.0.map(|row| row.cast()))
+                    }
+
+                    /// Converts each element to another Element type,
+                    /// see [`Vector2::try_cast`]
+                    #[inline(always)]
+                    pub fn try_cast<U: Element>(self) -> Option<[<Matrix $n x $n>]<U>> {
+                        let mut ret = [<Matrix $n x $n>]([[<Vector $n>]([U::zero(); $n]); $n]);
+                        for cx in 0..$n {
+                            ret[cx] = self[cx].try_cast()?;
+                        }
+                        Some(ret)
+                    }
+
+                    /// Does by-value comparison to see if two Matrices are exactly equal,
+                    /// unlike `==` which compares the rows within `T::epsilon()`
+                    #[inline(always)]
+                    pub fn eq_exact(self, other: Self) -> bool {
+                        self.to_rows_array_2d() == other.to_rows_array_2d()
+                    }
+
                     /// Gives the trace of the Matrix
                     #[inline(always)]
                     pub fn trace(self) -> T {
@@ -392,6 +554,13 @@ impl<T: Element> Matrix4x4<T> {
         ])
     }
 
+    /// Gives the upper-left 3x3 part of the Matrix, which is
+    /// the rotation and scale of an affine transform
+    #[inline(always)]
+    pub fn to_matrix3x3(self) -> Matrix3x3<T> {
+        Matrix3x3([0, 1, 2].map(|cx| Vector3([self[cx][0], self[cx][1], self[cx][2]])))
+    }
+
     /// Gives the determinant of the Matrix
     ///
     /// # Examples
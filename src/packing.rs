@@ -0,0 +1,273 @@
+//! Packing of Vectors into compact vertex attribute formats.
+//!
+//! The `pack_*`/`unpack_*` functions follow the GLSL built-ins of the same name
+//! (`packUnorm4x8`, `packHalf2x16`, ...), so the first component always goes into
+//! the least significant bits. [`octahedral_encode`] maps unit normals onto
+//! a square, so they can be stored in 2 components
+
+use crate::vector::{Vector2, Vector3, Vector4};
+use crate::Element;
+
+#[inline(always)]
+fn to_unorm(value: f32, max: f32) -> u32 {
+    (value.clamp(0f32, 1f32) * max).round() as u32
+}
+
+#[inline(always)]
+fn to_snorm(value: f32, max: f32) -> i32 {
+    (value.clamp(-1f32, 1f32) * max).round() as i32
+}
+
+#[inline(always)]
+fn from_snorm(value: i32, max: f32) -> f32 {
+    (value as f32 / max).clamp(-1f32, 1f32)
+}
+
+/// GLSL `packUnorm4x8`. Clamps each element to [0,1]
+/// and stores it as an 8 bit unsigned normalized value
+///
+/// # Examples
+///
+/// ```
+/// use glmath::packing::pack_unorm8x4;
+/// use glmath::vector::Vector4;
+///
+/// assert_eq!(pack_unorm8x4(Vector4::from([1f32, 0f32, 0.5f32, 2f32])), 0xff80_00ff);
+/// ```
+#[inline(always)]
+pub fn pack_unorm8x4(value: Vector4<f32>) -> u32 {
+    let mut ret = 0u32;
+    for cx in 0..4 {
+        ret |= to_unorm(value[cx], 255f32) << (cx * 8);
+    }
+    ret
+}
+
+/// GLSL `unpackUnorm4x8`
+#[inline(always)]
+pub fn unpack_unorm8x4(value: u32) -> Vector4<f32> {
+    Vector4([0, 1, 2, 3].map(|cx| ((value >> (cx * 8)) & 0xff) as f32 / 255f32))
+}
+
+/// GLSL `packSnorm4x8`. Clamps each element to [-1,1]
+/// and stores it as an 8 bit signed normalized value
+#[inline(always)]
+pub fn pack_snorm8x4(value: Vector4<f32>) -> u32 {
+    let mut ret = 0u32;
+    for cx in 0..4 {
+        ret |= ((to_snorm(value[cx], 127f32) as u32) & 0xff) << (cx * 8);
+    }
+    ret
+}
+
+/// GLSL `unpackSnorm4x8`
+#[inline(always)]
+pub fn unpack_snorm8x4(value: u32) -> Vector4<f32> {
+    Vector4([0, 1, 2, 3].map(|cx| from_snorm(((value >> (cx * 8)) as u8 as i8) as i32, 127f32)))
+}
+
+/// GLSL `packUnorm2x16`. Clamps each element to [0,1]
+/// and stores it as a 16 bit unsigned normalized value
+#[inline(always)]
+pub fn pack_unorm16x2(value: Vector2<f32>) -> u32 {
+    to_unorm(value[0], 65535f32) | (to_unorm(value[1], 65535f32) << 16)
+}
+
+/// GLSL `unpackUnorm2x16`
+#[inline(always)]
+pub fn unpack_unorm16x2(value: u32) -> Vector2<f32> {
+    Vector2([
+        (value & 0xffff) as f32 / 65535f32,
+        (value >> 16) as f32 / 65535f32,
+    ])
+}
+
+/// GLSL `packSnorm2x16`. Clamps each element to [-1,1]
+/// and stores it as a 16 bit signed normalized value
+///
+/// # Examples
+///
+/// ```
+/// use glmath::packing::pack_snorm16x2;
+/// use glmath::vector::Vector2;
+///
+/// assert_eq!(pack_snorm16x2(Vector2::from([1f32, -1f32])), 0x8001_7fff);
+/// ```
+#[inline(always)]
+pub fn pack_snorm16x2(value: Vector2<f32>) -> u32 {
+    ((to_snorm(value[0], 32767f32) as u32) & 0xffff) | ((to_snorm(value[1], 32767f32) as u32) << 16)
+}
+
+/// GLSL `unpackSnorm2x16`
+#[inline(always)]
+pub fn unpack_snorm16x2(value: u32) -> Vector2<f32> {
+    Vector2([
+        from_snorm(value as u16 as i16 as i32, 32767f32),
+        from_snorm((value >> 16) as u16 as i16 as i32, 32767f32),
+    ])
+}
+
+/// Converts the f32 to the bits of an IEEE 754 half float,
+/// rounding to the nearest even value
+#[inline(always)]
+pub fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let man = bits & 0x7f_ffff;
+
+    // infinity and NaN, keeping NaNs quiet
+    if exp == 0xff {
+        return sign
+            | 0x7c00
+            | if man != 0 {
+                0x200 | (man >> 13) as u16
+            } else {
+                0
+            };
+    }
+    let half_exp = exp - 127 + 15;
+    // too large, becomes infinity
+    if half_exp >= 0x1f {
+        return sign | 0x7c00;
+    }
+    // too small for a normal half, becomes subnormal or 0
+    if half_exp <= 0 {
+        if half_exp < -10 {
+            return sign;
+        }
+        let man = man | 0x80_0000;
+        let shift = (14 - half_exp) as u32;
+        let half_man = man >> shift;
+        let rem = man & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        let round_up = rem > halfway || (rem == halfway && half_man & 1 != 0);
+        return sign | (half_man + round_up as u32) as u16;
+    }
+    let half = ((half_exp as u32) << 10) | (man >> 13);
+    let rem = man & 0x1fff;
+    let round_up = rem > 0x1000 || (rem == 0x1000 && half & 1 != 0);
+    // rounding up can carry into the exponent, which is still correct
+    sign | (half + round_up as u32) as u16
+}
+
+/// Converts the bits of an IEEE 754 half float to an f32.
+/// This is exact, since every half float is an f32
+#[inline(always)]
+pub fn f16_bits_to_f32(value: u16) -> f32 {
+    let sign = ((value & 0x8000) as u32) << 16;
+    let exp = ((value >> 10) & 0x1f) as u32;
+    let man = (value & 0x3ff) as u32;
+    match exp {
+        0 => {
+            let ret = man as f32 * 2f32.powi(-24);
+            if sign != 0 {
+                -ret
+            } else {
+                ret
+            }
+        }
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (man << 13)),
+        _ => f32::from_bits(sign | ((exp + 112) << 23) | (man << 13)),
+    }
+}
+
+/// GLSL `packHalf2x16`. Converts each element to a half float
+///
+/// # Examples
+///
+/// ```
+/// use glmath::packing::pack_half2x16;
+/// use glmath::vector::Vector2;
+///
+/// assert_eq!(pack_half2x16(Vector2::from([1f32, -2f32])), 0xc000_3c00);
+/// ```
+#[inline(always)]
+pub fn pack_half2x16(value: Vector2<f32>) -> u32 {
+    f32_to_f16_bits(value[0]) as u32 | ((f32_to_f16_bits(value[1]) as u32) << 16)
+}
+
+/// GLSL `unpackHalf2x16`
+#[inline(always)]
+pub fn unpack_half2x16(value: u32) -> Vector2<f32> {
+    Vector2([
+        f16_bits_to_f32(value as u16),
+        f16_bits_to_f32((value >> 16) as u16),
+    ])
+}
+
+/// Packs the Vector as RGB10A2 (`GL_UNSIGNED_INT_2_10_10_10_REV`).
+/// Clamps each element to [0,1], storing x, y and z as 10 bit
+/// and w as 2 bit unsigned normalized values
+#[inline(always)]
+pub fn pack_rgb10a2(value: Vector4<f32>) -> u32 {
+    to_unorm(value[0], 1023f32)
+        | (to_unorm(value[1], 1023f32) << 10)
+        | (to_unorm(value[2], 1023f32) << 20)
+        | (to_unorm(value[3], 3f32) << 30)
+}
+
+/// Unpacks RGB10A2, see [`pack_rgb10a2`]
+#[inline(always)]
+pub fn unpack_rgb10a2(value: u32) -> Vector4<f32> {
+    Vector4([
+        (value & 0x3ff) as f32 / 1023f32,
+        ((value >> 10) & 0x3ff) as f32 / 1023f32,
+        ((value >> 20) & 0x3ff) as f32 / 1023f32,
+        (value >> 30) as f32 / 3f32,
+    ])
+}
+
+/// Gives 1 for positive values (including +0), otherwise -1
+#[inline(always)]
+fn sign_not_zero<T: Element>(value: T) -> T {
+    if value >= T::zero() {
+        T::one()
+    } else {
+        -T::one()
+    }
+}
+
+/// Maps the unit Vector onto the octahedron, then unfolds
+/// that onto the square [-1,1]x[-1,1].
+/// The result is usually stored with [`pack_snorm16x2`]
+///
+/// # Examples
+///
+/// ```
+/// use glmath::packing::{octahedral_decode, octahedral_encode};
+/// use glmath::vector::Vector3;
+///
+/// let normal = Vector3::from([0f32, 0f32, -1f32]);
+/// let encoded = octahedral_encode(normal);
+///
+/// assert!(octahedral_decode(encoded).eq_fast(normal, 1e-6));
+/// ```
+#[inline(always)]
+pub fn octahedral_encode<T: Element>(normal: Vector3<T>) -> Vector2<T> {
+    let normal = normal.div_scalar(normal.abs().sum());
+    if normal[2] >= T::zero() {
+        Vector2([normal[0], normal[1]])
+    } else {
+        Vector2([
+            (T::one() - normal[1].abs()) * sign_not_zero(normal[0]),
+            (T::one() - normal[0].abs()) * sign_not_zero(normal[1]),
+        ])
+    }
+}
+
+/// Gives the unit Vector back from [`octahedral_encode`]
+#[inline(always)]
+pub fn octahedral_decode<T: Element>(encoded: Vector2<T>) -> Vector3<T> {
+    let z = T::one() - encoded[0].abs() - encoded[1].abs();
+    let ret = if z >= T::zero() {
+        Vector3([encoded[0], encoded[1], z])
+    } else {
+        Vector3([
+            (T::one() - encoded[1].abs()) * sign_not_zero(encoded[0]),
+            (T::one() - encoded[0].abs()) * sign_not_zero(encoded[1]),
+            z,
+        ])
+    };
+    ret.normalize_or_zero()
+}
@@ -2,14 +2,18 @@
 
 use std::ops::{Index, IndexMut};
 
-//TODO: Examples/Test for everything
 use crate::{
+    angle::Rad,
+    matrix::{Matrix3x3, Matrix4x4},
     vector::{Vector3, Vector4},
     Element,
 };
 
+/// A Quaternion stored as (x,y,z,w), where
+/// (x,y,z) is the vector part and w is the scalar part
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Quaternion<T: Element>(Vector4<T>);
 
 #[cfg(feature = "bytemuck")]
@@ -23,20 +27,53 @@ unsafe impl<T: Element> bytemuck::Zeroable for Quaternion<T> {
 }
 
 impl<T: Element> From<Vector4<T>> for Quaternion<T> {
+    #[inline(always)]
     fn from(value: Vector4<T>) -> Self {
         Self(value)
     }
 }
 impl<T: Element> From<[T; 4]> for Quaternion<T> {
+    #[inline(always)]
     fn from(value: [T; 4]) -> Self {
         Self(Vector4::from(value))
     }
 }
+impl<T: Element> From<&[T; 4]> for Quaternion<T> {
+    #[inline(always)]
+    fn from(value: &[T; 4]) -> Self {
+        Self(Vector4::from(value))
+    }
+}
+impl<T: Element> From<(T, T, T, T)> for Quaternion<T> {
+    #[inline(always)]
+    fn from(value: (T, T, T, T)) -> Self {
+        Self(Vector4::from(value))
+    }
+}
 impl<T: Element> From<(Vector3<T>, T)> for Quaternion<T> {
+    #[inline(always)]
     fn from(value: (Vector3<T>, T)) -> Self {
         Self(Vector4::from(value))
     }
 }
+impl<T: Element> From<Quaternion<T>> for Vector4<T> {
+    #[inline(always)]
+    fn from(value: Quaternion<T>) -> Self {
+        value.0
+    }
+}
+impl<T: Element> From<Quaternion<T>> for [T; 4] {
+    #[inline(always)]
+    fn from(value: Quaternion<T>) -> Self {
+        value.0 .0
+    }
+}
+impl From<Quaternion<f32>> for Quaternion<f64> {
+    #[inline(always)]
+    fn from(value: Quaternion<f32>) -> Self {
+        value.cast()
+    }
+}
 
 impl<T: Element> Index<usize> for Quaternion<T> {
     type Output = T;
@@ -53,28 +90,207 @@ impl<T: Element> IndexMut<usize> for Quaternion<T> {
     }
 }
 
+impl<T: Element> IntoIterator for Quaternion<T> {
+    type Item = T;
+    type IntoIter = core::array::IntoIter<T, 4>;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T: Element + approx::AbsDiffEq<Epsilon = T>> approx::AbsDiffEq for Quaternion<T> {
+    type Epsilon = T;
+    #[inline(always)]
+    fn default_epsilon() -> T {
+        T::default_epsilon()
+    }
+    #[inline(always)]
+    fn abs_diff_eq(&self, other: &Self, epsilon: T) -> bool {
+        self.0.abs_diff_eq(&other.0, epsilon)
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T: Element + approx::RelativeEq<Epsilon = T>> approx::RelativeEq for Quaternion<T> {
+    #[inline(always)]
+    fn default_max_relative() -> T {
+        T::default_max_relative()
+    }
+    #[inline(always)]
+    fn relative_eq(&self, other: &Self, epsilon: T, max_relative: T) -> bool {
+        self.0.relative_eq(&other.0, epsilon, max_relative)
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T: Element + approx::UlpsEq<Epsilon = T>> approx::UlpsEq for Quaternion<T> {
+    #[inline(always)]
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+    #[inline(always)]
+    fn ulps_eq(&self, other: &Self, epsilon: T, max_ulps: u32) -> bool {
+        self.0.ulps_eq(&other.0, epsilon, max_ulps)
+    }
+}
+
 impl<T: Element> Quaternion<T> {
+    /// Creates a new Quaternion using uninitialized data
+    #[inline(always)]
+    pub fn new_uninit() -> Self {
+        Self(Vector4::new_uninit())
+    }
+
+    /// Creates a Quaternion from its components given scalar-first.
+    /// Note that the storage order is still (x,y,z,w)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use glmath::quaternions::Quaternion;
+    ///
+    /// let q = Quaternion::from_wxyz(4f32, 1f32, 2f32, 3f32);
+    ///
+    /// assert_eq!(q, Quaternion::from([1f32, 2f32, 3f32, 4f32]));
+    /// assert_eq!(q.to_wxyz(), [4f32, 1f32, 2f32, 3f32]);
+    /// ```
+    #[inline(always)]
+    pub fn from_wxyz(w: T, x: T, y: T, z: T) -> Self {
+        Self::from([x, y, z, w])
+    }
+
+    /// Creates a Quaternion from the scalar part
+    /// and the vector part, in that order
+    #[inline(always)]
+    pub fn from_scalar_vector(w: T, vector: Vector3<T>) -> Self {
+        Self::from((vector, w))
+    }
+
+    /// Gives the components scalar-first, as (w,x,y,z)
+    #[inline(always)]
+    pub fn to_wxyz(self) -> [T; 4] {
+        [self[3], self[0], self[1], self[2]]
+    }
+
+    /// Gives the components in storage order, as (x,y,z,w)
+    #[inline(always)]
+    pub fn to_xyzw(self) -> [T; 4] {
+        self.0 .0
+    }
+
+    /// Converts each component to another Element type,
+    /// see [`Vector4::cast`]
+    #[inline(always)]
+    pub fn cast<U: Element>(self) -> Quaternion<U> {
+        Quaternion(self.0.cast())
+    }
+
+    /// Converts each component to another Element type,
+    /// see [`Vector4::try_cast`]
+    #[inline(always)]
+    pub fn try_cast<U: Element>(self) -> Option<Quaternion<U>> {
+        self.0.try_cast().map(Quaternion)
+    }
+
+    /// Gets the x (i) component
+    #[inline(always)]
+    pub fn x(self) -> T {
+        self[0]
+    }
+    /// Gets the y (j) component
+    #[inline(always)]
+    pub fn y(self) -> T {
+        self[1]
+    }
+    /// Gets the z (k) component
+    #[inline(always)]
+    pub fn z(self) -> T {
+        self[2]
+    }
+    /// Gets the w (scalar) component
+    #[inline(always)]
+    pub fn w(self) -> T {
+        self[3]
+    }
+    /// Gets the vector part (x,y,z)
+    #[inline(always)]
+    pub fn vector(self) -> Vector3<T> {
+        Vector3([self[0], self[1], self[2]])
+    }
+    /// Gets the scalar part (w)
+    #[inline(always)]
+    pub fn scalar(self) -> T {
+        self[3]
+    }
+
     /// Separates the Quaternion into Vector and Scalar components.
     /// Note that we define Quaternions to be (x,y,z,w)
     #[inline(always)]
+    pub fn separate(self) -> (Vector3<T>, T) {
+        (self.vector(), self.scalar())
+    }
+    /// Separates the Quaternion into Vector and Scalar components.
+    #[deprecated(note = "use `Quaternion::separate` instead")]
+    #[inline(always)]
     pub fn seperate(self) -> (Vector3<T>, T) {
-        (Vector3([self[0], self[1], self[2]]), self[3])
+        self.separate()
     }
     /// Does element-wise addition
+    #[allow(clippy::should_implement_trait)]
     #[inline(always)]
     pub fn add(self, addend: Self) -> Self {
         Self(self.0.add(addend.0))
     }
     /// Does element-wise subtraction
+    #[allow(clippy::should_implement_trait)]
     #[inline(always)]
     pub fn sub(self, subtrahend: Self) -> Self {
         Self(self.0.sub(subtrahend.0))
     }
-    /// Gets the norm of the Quaternion
+    /// Gives the normalized (unit) Quaternion.
+    /// Same as [`Quaternion::normalize`]
     #[inline(always)]
     pub fn norm(self) -> Self {
         Self(self.0.norm())
     }
+    /// Gives the normalized (unit) Quaternion, with exactly unit length.
+    /// The zero Quaternion stays zero, see [`Vector4::norm`]
+    #[inline(always)]
+    pub fn normalize(self) -> Self {
+        self.norm()
+    }
+    /// Normalizes the Quaternion to exactly unit length.
+    /// Gives None for the zero Quaternion, or if any element is not finite.
+    /// See [`Vector4::try_normalize`]
+    #[inline(always)]
+    pub fn try_normalize(self) -> Option<Self> {
+        self.0.try_normalize().map(Self)
+    }
+    /// Gets the length (magnitude) of the Quaternion
+    #[inline(always)]
+    pub fn len(self) -> T {
+        self.0.len()
+    }
+    /// Gets the length (magnitude) of the Quaternion.
+    /// Same as [`Quaternion::len`]
+    #[inline(always)]
+    pub fn length(self) -> T {
+        self.len()
+    }
+    /// Gets the squared length of the Quaternion,
+    /// which avoids the square root
+    #[inline(always)]
+    pub fn length_squared(self) -> T {
+        self.0.length_squared()
+    }
+    /// Checks if the Quaternion has unit length,
+    /// within the given epsilon value
+    #[inline(always)]
+    pub fn is_normalized(self, epsilon: T) -> bool {
+        (self.length_squared() - T::one()).abs() <= epsilon
+    }
     /// Does scalar-wise division
     #[inline(always)]
     pub fn div_scalar(self, scalar: T) -> Self {
@@ -90,16 +306,65 @@ impl<T: Element> Quaternion<T> {
     pub fn mul_inner(self, other: Self) -> T {
         self.0.mul_inner(other.0)
     }
+    /// Does inner product (aka dot product).
+    /// Same as [`Quaternion::mul_inner`]
+    #[inline(always)]
+    pub fn dot(self, other: Self) -> T {
+        self.mul_inner(other)
+    }
+    /// Gets the distance between both Quaternions
+    #[inline(always)]
+    pub fn dist(self, other: Self) -> T {
+        self.0.dist(other.0)
+    }
+    /// Does [`crate::scalar::lerp`] on each element.
+    /// Note that the result is generally not normalized,
+    /// see [`Quaternion::nlerp`] and [`Quaternion::slerp`]
+    #[inline(always)]
+    pub fn lerp(self, other: Self, t: T) -> Self {
+        Self(self.0.lerp(other.0, t))
+    }
+    /// Does by-value comparison to see if two Quaternion's are exactly equal,
+    /// unlike `==` which compares within `T::epsilon()`
+    #[inline(always)]
+    pub fn eq_exact(self, other: Self) -> bool {
+        self.0.eq_exact(other.0)
+    }
+    /// Does by-value comparison to see if two Quaternion's are equal
+    /// by using the given epsilon value
+    #[inline(always)]
+    pub fn eq_fast(self, other: Self, epsilon: T) -> bool {
+        self.0.eq_fast(other.0, epsilon)
+    }
     /// Gives the identity Quaternion
     #[inline(always)]
     pub fn identity() -> Self {
         Quaternion::from([T::zero(), T::zero(), T::zero(), T::one()])
     }
-    /// Multiply two Quaternions
+    /// Multiply two Quaternions (the Hamilton product)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use glmath::quaternions::Quaternion;
+    ///
+    /// let i = Quaternion::from_wxyz(0f32, 1f32, 0f32, 0f32);
+    /// let j = Quaternion::from_wxyz(0f32, 0f32, 1f32, 0f32);
+    /// let k = Quaternion::from_wxyz(0f32, 0f32, 0f32, 1f32);
+    ///
+    /// assert_eq!(i.mul(j), k);
+    /// assert_eq!(j.mul(i), k.mul_scalar(-1f32));
+    /// assert_eq!(i.mul(i), Quaternion::identity().mul_scalar(-1f32));
+    /// ```
+    #[allow(clippy::should_implement_trait)]
     #[inline(always)]
     pub fn mul(self, other: Self) -> Self {
-        let (self_v3, self_w) = self.seperate();
-        let (other_v3, other_w) = other.seperate();
+        #[cfg(feature = "simd")]
+        if let (Some(ca), Some(cb)) = (crate::simd::downcast(self), crate::simd::downcast(other)) {
+            return crate::simd::downcast(crate::simd::mul_quaternion(ca, cb)).unwrap();
+        }
+        let (self_v3, self_w) = self.separate();
+        let (other_v3, other_w) = other.separate();
         Quaternion::from((
             self_v3
                 .mul_cross(other_v3)
@@ -112,4 +377,177 @@ impl<T: Element> Quaternion<T> {
     pub fn conjugate(self) -> Self {
         Self::from([-self[0], -self[1], -self[2], self[3]])
     }
+    /// Inverts the Quaternion, but first checks
+    /// to see if its length is 0.
+    /// For unit Quaternions this is the same as
+    /// [`Quaternion::conjugate`]
+    #[inline(always)]
+    pub fn inverse(self) -> Option<Self> {
+        let length_squared = self.length_squared();
+        if length_squared == T::zero() {
+            None
+        } else {
+            Some(self.conjugate().div_scalar(length_squared))
+        }
+    }
+
+    /// Creates the Quaternion representing a rotation
+    /// of angle around the given axis. The angle is in radians,
+    /// unless given as [`crate::angle::Deg`]. The axis does not need to be normalized
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use glmath::angle::Deg;
+    /// use glmath::quaternions::Quaternion;
+    /// use glmath::vector::Vector3;
+    /// use std::f32::consts::FRAC_PI_2;
+    ///
+    /// let q = Quaternion::from_axis_angle(Vector3::from([0f32, 0f32, 1f32]), FRAC_PI_2);
+    /// let v = Vector3::from([1f32, 0f32, 0f32]);
+    ///
+    /// assert!(q.rotate_vector(v).eq_fast(Vector3::from([0f32, 1f32, 0f32]), 1e-6));
+    ///
+    /// let d = Quaternion::from_axis_angle(Vector3::from([0f32, 0f32, 1f32]), Deg(90f32));
+    /// assert!(d.eq_fast(q, 1e-6));
+    /// ```
+    #[inline(always)]
+    pub fn from_axis_angle(axis: Vector3<T>, angle: impl Into<Rad<T>>) -> Self {
+        let half = angle.into().0 / (T::one() + T::one());
+        let (half_sin, half_cos) = half.sin_cos();
+        Self::from((axis.normalize_or_zero().mul_scalar(half_sin), half_cos))
+    }
+
+    /// Gives the axis and angle (in radians) of
+    /// the rotation this unit Quaternion represents.
+    /// The identity rotation gives the x axis with an angle of 0
+    #[inline(always)]
+    pub fn to_axis_angle(self) -> (Vector3<T>, T) {
+        let (vector, w) = self.separate();
+        let sin_len = vector.len();
+        let angle = (T::one() + T::one()) * sin_len.atan2(w);
+        if sin_len == T::zero() {
+            (Vector3([T::one(), T::zero(), T::zero()]), angle)
+        } else {
+            (vector.div_scalar(sin_len), angle)
+        }
+    }
+
+    /// Creates the Quaternion from Euler angles (in radians, unless given as [`crate::angle::Deg`]),
+    /// rotating around the x axis first, then y, then z. That is `qz * qy * qx`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use glmath::quaternions::Quaternion;
+    /// use glmath::vector::Vector3;
+    /// use std::f32::consts::FRAC_PI_2;
+    ///
+    /// let q = Quaternion::from_euler(0f32, 0f32, FRAC_PI_2);
+    /// let z = Quaternion::from_axis_angle(Vector3::from([0f32, 0f32, 1f32]), FRAC_PI_2);
+    ///
+    /// assert!(q.eq_fast(z, 1e-6));
+    /// ```
+    #[inline(always)]
+    pub fn from_euler(x: impl Into<Rad<T>>, y: impl Into<Rad<T>>, z: impl Into<Rad<T>>) -> Self {
+        let half = T::one() / (T::one() + T::one());
+        let (x, y, z) = (x.into().0, y.into().0, z.into().0);
+        let (sx, cx) = (x * half).sin_cos();
+        let (sy, cy) = (y * half).sin_cos();
+        let (sz, cz) = (z * half).sin_cos();
+        Self::from([
+            sx * cy * cz - cx * sy * sz,
+            cx * sy * cz + sx * cy * sz,
+            cx * cy * sz - sx * sy * cz,
+            cx * cy * cz + sx * sy * sz,
+        ])
+    }
+
+    /// Gives the Euler angles (in radians) of this unit Quaternion,
+    /// in the order of [`Quaternion::from_euler`].
+    /// The y angle is in [-pi/2, pi/2], the others in [-pi, pi]
+    #[inline(always)]
+    pub fn to_euler(self) -> (T, T, T) {
+        let two = T::one() + T::one();
+        let [x, y, z, w] = self.to_xyzw();
+        let ex = (two * (w * x + y * z)).atan2(T::one() - two * (x * x + y * y));
+        let ey = (two * (w * y - z * x)).max(-T::one()).min(T::one()).asin();
+        let ez = (two * (w * z + x * y)).atan2(T::one() - two * (y * y + z * z));
+        (ex, ey, ez)
+    }
+
+    /// Rotates the Vector by this unit Quaternion.
+    /// This is the same as `q * v * q^-1`, but computed
+    /// without the intermediate Quaternion products
+    #[inline(always)]
+    pub fn rotate_vector(self, vector: Vector3<T>) -> Vector3<T> {
+        let (q_v3, q_w) = self.separate();
+        let t = q_v3.mul_cross(vector).mul_scalar(T::one() + T::one());
+        vector.add(t.mul_scalar(q_w)).add(q_v3.mul_cross(t))
+    }
+
+    /// Normalized linear interpolation between two Quaternions.
+    /// This takes the shortest path between the rotations
+    #[inline(always)]
+    pub fn nlerp(self, mut other: Self, t: T) -> Self {
+        if self.mul_inner(other) < T::zero() {
+            other = other.mul_scalar(-T::one());
+        }
+        self.lerp(other, t).norm()
+    }
+
+    /// Spherical linear interpolation between two unit Quaternions.
+    /// This takes the shortest path between the rotations,
+    /// and falls back to [`Quaternion::nlerp`] when they are nearly parallel
+    #[inline(always)]
+    pub fn slerp(self, mut other: Self, t: T) -> Self {
+        let mut cos_theta = self.mul_inner(other);
+        if cos_theta < T::zero() {
+            other = other.mul_scalar(-T::one());
+            cos_theta = -cos_theta;
+        }
+        if cos_theta > T::one() - T::epsilon().sqrt() {
+            return self.nlerp(other, t);
+        }
+        let theta = cos_theta.acos();
+        let sin_theta = theta.sin();
+        let a = ((T::one() - t) * theta).sin() / sin_theta;
+        let b = (t * theta).sin() / sin_theta;
+        self.mul_scalar(a).add(other.mul_scalar(b))
+    }
+
+    /// Gives the rotation Matrix of this unit Quaternion,
+    /// to be used with [`Matrix3x3::mul_vector`]
+    #[inline(always)]
+    pub fn to_matrix3x3(self) -> Matrix3x3<T> {
+        let two = T::one() + T::one();
+        let [x, y, z, w] = self.to_xyzw();
+        let (xx, yy, zz) = (x * x, y * y, z * z);
+        let (xy, xz, yz) = (x * y, x * z, y * z);
+        let (wx, wy, wz) = (w * x, w * y, w * z);
+        Matrix3x3::from([
+            T::one() - two * (yy + zz),
+            two * (xy - wz),
+            two * (xz + wy),
+            two * (xy + wz),
+            T::one() - two * (xx + zz),
+            two * (yz - wx),
+            two * (xz - wy),
+            two * (yz + wx),
+            T::one() - two * (xx + yy),
+        ])
+    }
+
+    /// Gives the homogeneous rotation Matrix of this unit Quaternion,
+    /// to be used with [`Matrix4x4::mul_vector`]
+    #[inline(always)]
+    pub fn to_matrix4x4(self) -> Matrix4x4<T> {
+        let m = self.to_matrix3x3();
+        Matrix4x4::from([
+            Vector4::from((m[0], T::zero())),
+            Vector4::from((m[1], T::zero())),
+            Vector4::from((m[2], T::zero())),
+            Vector4::from([T::zero(), T::zero(), T::zero(), T::one()]),
+        ])
+    }
 }
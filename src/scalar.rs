@@ -28,3 +28,448 @@ use crate::Element;
 pub fn lerp<T: Element>(value0: T, value1: T, t: T) -> T {
     value0 + (value1 - value0) * t
 }
+
+/// GLSL `step`. Gives 0 if x < edge, otherwise gives 1
+///
+/// # Examples
+///
+/// ```
+/// use glmath::scalar::step;
+///
+/// assert_eq!(step(0.5f32, 0.25f32), 0f32);
+/// assert_eq!(step(0.5f32, 0.5f32), 1f32);
+/// assert_eq!(step(0.5f32, 0.75f32), 1f32);
+/// ```
+#[inline(always)]
+pub fn step<T: Element>(edge: T, x: T) -> T {
+    if x < edge {
+        T::zero()
+    } else {
+        T::one()
+    }
+}
+
+/// GLSL `smoothstep`. Does smooth Hermite interpolation
+/// between 0 and 1 when edge0 < x < edge1.
+/// Results are undefined (as in GLSL) if edge0 >= edge1
+///
+/// # Examples
+///
+/// ```
+/// use glmath::scalar::smoothstep;
+/// use approx::assert_relative_eq;
+///
+/// assert_relative_eq!(smoothstep(1f32, 3f32, 0f32), 0f32);
+/// assert_relative_eq!(smoothstep(1f32, 3f32, 1.5f32), 0.15625f32);
+/// assert_relative_eq!(smoothstep(1f32, 3f32, 2f32), 0.5f32);
+/// assert_relative_eq!(smoothstep(1f32, 3f32, 2.5f32), 0.84375f32);
+/// assert_relative_eq!(smoothstep(1f32, 3f32, 4f32), 1f32);
+/// ```
+#[inline(always)]
+pub fn smoothstep<T: Element>(edge0: T, edge1: T, x: T) -> T {
+    let t = num::clamp((x - edge0) / (edge1 - edge0), T::zero(), T::one());
+    let three = T::one() + T::one() + T::one();
+    t * t * (three - (T::one() + T::one()) * t)
+}
+
+/// GLSL `mix`. Same as [`lerp`]
+///
+/// # Examples
+///
+/// ```
+/// use glmath::scalar::mix;
+/// use approx::assert_relative_eq;
+///
+/// assert_relative_eq!(mix(2f32, 4f32, 0.25f32), 2.5f32);
+/// ```
+#[inline(always)]
+pub fn mix<T: Element>(x: T, y: T, a: T) -> T {
+    lerp(x, y, a)
+}
+
+/// GLSL `inversesqrt`. Gives 1 / sqrt(x)
+///
+/// # Examples
+///
+/// ```
+/// use glmath::scalar::inversesqrt;
+/// use approx::assert_relative_eq;
+///
+/// assert_relative_eq!(inversesqrt(4f32), 0.5f32);
+/// assert_relative_eq!(inversesqrt(0.0625f32), 4f32);
+/// ```
+#[inline(always)]
+pub fn inversesqrt<T: Element>(x: T) -> T {
+    x.sqrt().recip()
+}
+
+/// Gives the t that [`lerp`] would need to give value,
+/// so 0 at value0 and 1 at value1.
+/// Results are undefined if value0 == value1
+///
+/// # Examples
+///
+/// ```
+/// use glmath::scalar::inverse_lerp;
+/// use approx::assert_relative_eq;
+///
+/// assert_relative_eq!(inverse_lerp(4f32, 12f32, 4f32), 0f32);
+/// assert_relative_eq!(inverse_lerp(4f32, 12f32, 6f32), 0.25f32);
+/// assert_relative_eq!(inverse_lerp(4f32, 12f32, 12f32), 1f32);
+/// assert_relative_eq!(inverse_lerp(4f32, 12f32, 20f32), 2f32);
+/// ```
+#[inline(always)]
+pub fn inverse_lerp<T: Element>(value0: T, value1: T, value: T) -> T {
+    (value - value0) / (value1 - value0)
+}
+
+/// Maps value from the range from0 to from1 onto the range to0 to to1,
+/// without clamping.
+/// Results are undefined if from0 == from1
+///
+/// # Examples
+///
+/// ```
+/// use glmath::scalar::remap;
+/// use approx::assert_relative_eq;
+///
+/// assert_relative_eq!(remap(0f32, 10f32, 100f32, 200f32, 0f32), 100f32);
+/// assert_relative_eq!(remap(0f32, 10f32, 100f32, 200f32, 2.5f32), 125f32);
+/// assert_relative_eq!(remap(0f32, 10f32, 200f32, 100f32, 2.5f32), 175f32);
+/// ```
+#[inline(always)]
+pub fn remap<T: Element>(from0: T, from1: T, to0: T, to1: T, value: T) -> T {
+    lerp(to0, to1, inverse_lerp(from0, from1, value))
+}
+
+/// Ken Perlin's `smootherstep`. Like [`smoothstep`], but with
+/// first and second derivatives of 0 at the edges.
+/// Results are undefined if edge0 >= edge1
+///
+/// # Examples
+///
+/// ```
+/// use glmath::scalar::smootherstep;
+/// use approx::assert_relative_eq;
+///
+/// assert_relative_eq!(smootherstep(1f32, 3f32, 0f32), 0f32);
+/// assert_relative_eq!(smootherstep(1f32, 3f32, 1.5f32), 0.103515625f32);
+/// assert_relative_eq!(smootherstep(1f32, 3f32, 2f32), 0.5f32);
+/// assert_relative_eq!(smootherstep(1f32, 3f32, 4f32), 1f32);
+/// ```
+#[inline(always)]
+pub fn smootherstep<T: Element>(edge0: T, edge1: T, x: T) -> T {
+    let t = num::clamp((x - edge0) / (edge1 - edge0), T::zero(), T::one());
+    let six: T = num::cast(6).unwrap();
+    let ten: T = num::cast(10).unwrap();
+    let fifteen: T = num::cast(15).unwrap();
+    t * t * t * (t * (t * six - fifteen) + ten)
+}
+
+/// Moves value towards target, closing the same fraction of the gap
+/// in the same time whatever the frame rate. Decay is how fast,
+/// with about a third of the gap left after 1 / decay seconds.
+///
+/// Use this instead of `lerp(value, target, speed * dt)`,
+/// which moves at different speeds for different dt
+///
+/// # Examples
+///
+/// ```
+/// use glmath::scalar::exp_decay;
+/// use approx::assert_relative_eq;
+///
+/// // one step of 0.2 is the same as two steps of 0.1
+/// let once = exp_decay(0f32, 10f32, 5f32, 0.2f32);
+/// let twice = exp_decay(exp_decay(0f32, 10f32, 5f32, 0.1f32), 10f32, 5f32, 0.1f32);
+/// assert_relative_eq!(once, twice);
+/// assert_relative_eq!(once, 10f32 * (1f32 - (-1f32).exp()));
+/// ```
+#[inline(always)]
+pub fn exp_decay<T: Element>(value: T, target: T, decay: T, dt: T) -> T {
+    target + (value - target) * (-decay * dt).exp()
+}
+
+/// Moves value towards target like a critically damped spring,
+/// which gets there as fast as it can without overshooting.
+/// This is Unity's `SmoothDamp`: velocity is state kept between calls,
+/// starting at 0, and smooth_time is about how long it takes to reach the target
+///
+/// # Examples
+///
+/// ```
+/// use glmath::scalar::critically_damped_spring;
+///
+/// let mut value = 0f32;
+/// let mut velocity = 0f32;
+/// for _ in 0..120 {
+///     value = critically_damped_spring(value, 10f32, &mut velocity, 0.25f32, 1f32 / 60f32);
+///     assert!(value <= 10f32);
+/// }
+/// assert!((value - 10f32).abs() < 1e-2);
+/// ```
+#[inline(always)]
+pub fn critically_damped_spring<T: Element>(
+    value: T,
+    target: T,
+    velocity: &mut T,
+    smooth_time: T,
+    dt: T,
+) -> T {
+    let two = T::one() + T::one();
+    let omega = two / smooth_time.max(T::epsilon());
+    let x = omega * dt;
+    // a Padé approximation of exp(-x)
+    let decay = T::one()
+        / (T::one()
+            + x
+            + num::cast::<f64, T>(0.48).unwrap() * x * x
+            + num::cast::<f64, T>(0.235).unwrap() * x * x * x);
+    let change = value - target;
+    let temp = (*velocity + omega * change) * dt;
+    *velocity = (*velocity - omega * temp) * decay;
+    let ret = target + (change + temp) * decay;
+    // the approximation can overshoot, so stop at the target
+    if (target > value) == (ret > target) {
+        *velocity = T::zero();
+        return target;
+    }
+    ret
+}
+
+/// Robert Penner's easing functions.
+///
+/// Each maps t from 0 to 1 onto 0 to 1, starting slowly (`in_*`),
+/// ending slowly (`out_*`) or both (`in_out_*`). The back and elastic
+/// easings overshoot past 0 or 1 on the way.
+/// Use [`crate::vector::Vector3::map`] to ease each element of a Vector
+///
+/// # Examples
+///
+/// ```
+/// use glmath::scalar::easing;
+/// use glmath::vector::Vector2;
+///
+/// assert_eq!(easing::in_quad(0.5f32), 0.25f32);
+/// assert_eq!(easing::out_quad(0.5f32), 0.75f32);
+/// assert_eq!(
+///     Vector2::from([0.5f32, 1f32]).map(easing::in_cubic),
+///     Vector2::from([0.125f32, 1f32])
+/// );
+/// ```
+pub mod easing {
+    use crate::Element;
+
+    #[inline(always)]
+    fn constant<T: Element>(value: f64) -> T {
+        num::cast(value).unwrap()
+    }
+
+    /// Turns an `in_*` easing into its `in_out_*`
+    #[inline(always)]
+    fn in_out<T: Element>(t: T, ease_in: fn(T) -> T) -> T {
+        let half = constant::<T>(0.5);
+        let two = constant::<T>(2.0);
+        if t < half {
+            ease_in(two * t) * half
+        } else {
+            T::one() - ease_in(two - two * t) * half
+        }
+    }
+
+    /// Turns an `in_*` easing into its `out_*`
+    #[inline(always)]
+    fn out<T: Element>(t: T, ease_in: fn(T) -> T) -> T {
+        T::one() - ease_in(T::one() - t)
+    }
+
+    #[inline(always)]
+    pub fn in_quad<T: Element>(t: T) -> T {
+        t * t
+    }
+
+    #[inline(always)]
+    pub fn out_quad<T: Element>(t: T) -> T {
+        out(t, in_quad)
+    }
+
+    #[inline(always)]
+    pub fn in_out_quad<T: Element>(t: T) -> T {
+        in_out(t, in_quad)
+    }
+
+    #[inline(always)]
+    pub fn in_cubic<T: Element>(t: T) -> T {
+        t * t * t
+    }
+
+    #[inline(always)]
+    pub fn out_cubic<T: Element>(t: T) -> T {
+        out(t, in_cubic)
+    }
+
+    #[inline(always)]
+    pub fn in_out_cubic<T: Element>(t: T) -> T {
+        in_out(t, in_cubic)
+    }
+
+    #[inline(always)]
+    pub fn in_quart<T: Element>(t: T) -> T {
+        t * t * t * t
+    }
+
+    #[inline(always)]
+    pub fn out_quart<T: Element>(t: T) -> T {
+        out(t, in_quart)
+    }
+
+    #[inline(always)]
+    pub fn in_out_quart<T: Element>(t: T) -> T {
+        in_out(t, in_quart)
+    }
+
+    #[inline(always)]
+    pub fn in_quint<T: Element>(t: T) -> T {
+        t * t * t * t * t
+    }
+
+    #[inline(always)]
+    pub fn out_quint<T: Element>(t: T) -> T {
+        out(t, in_quint)
+    }
+
+    #[inline(always)]
+    pub fn in_out_quint<T: Element>(t: T) -> T {
+        in_out(t, in_quint)
+    }
+
+    #[inline(always)]
+    pub fn in_sine<T: Element>(t: T) -> T {
+        T::one() - (t * constant(std::f64::consts::FRAC_PI_2)).cos()
+    }
+
+    #[inline(always)]
+    pub fn out_sine<T: Element>(t: T) -> T {
+        out(t, in_sine)
+    }
+
+    #[inline(always)]
+    pub fn in_out_sine<T: Element>(t: T) -> T {
+        in_out(t, in_sine)
+    }
+
+    /// Exactly 0 at 0, though the curve itself only gets to 2^-10 there
+    #[inline(always)]
+    pub fn in_expo<T: Element>(t: T) -> T {
+        if t <= T::zero() {
+            return T::zero();
+        }
+        constant::<T>(2.0).powf(constant::<T>(10.0) * t - constant(10.0))
+    }
+
+    #[inline(always)]
+    pub fn out_expo<T: Element>(t: T) -> T {
+        out(t, in_expo)
+    }
+
+    #[inline(always)]
+    pub fn in_out_expo<T: Element>(t: T) -> T {
+        in_out(t, in_expo)
+    }
+
+    #[inline(always)]
+    pub fn in_circ<T: Element>(t: T) -> T {
+        T::one() - (T::one() - t * t).max(T::zero()).sqrt()
+    }
+
+    #[inline(always)]
+    pub fn out_circ<T: Element>(t: T) -> T {
+        out(t, in_circ)
+    }
+
+    #[inline(always)]
+    pub fn in_out_circ<T: Element>(t: T) -> T {
+        in_out(t, in_circ)
+    }
+
+    /// Pulls back below 0 before going to 1
+    #[inline(always)]
+    pub fn in_back<T: Element>(t: T) -> T {
+        let c1 = constant::<T>(1.70158);
+        (c1 + T::one()) * t * t * t - c1 * t * t
+    }
+
+    #[inline(always)]
+    pub fn out_back<T: Element>(t: T) -> T {
+        out(t, in_back)
+    }
+
+    /// Uses a stronger back than [`in_back`], as Penner's does
+    #[inline(always)]
+    pub fn in_out_back<T: Element>(t: T) -> T {
+        in_out(t, |t| {
+            let c2 = constant::<T>(1.70158 * 1.525);
+            (c2 + T::one()) * t * t * t - c2 * t * t
+        })
+    }
+
+    /// Oscillates around 0 with a growing amplitude before reaching 1
+    #[inline(always)]
+    pub fn in_elastic<T: Element>(t: T) -> T {
+        if t <= T::zero() || t >= T::one() {
+            return num::clamp(t, T::zero(), T::one());
+        }
+        let c4 = constant::<T>(std::f64::consts::TAU / 3.0);
+        let ten = constant::<T>(10.0);
+        -constant::<T>(2.0).powf(ten * t - ten) * ((ten * t - constant(10.75)) * c4).sin()
+    }
+
+    #[inline(always)]
+    pub fn out_elastic<T: Element>(t: T) -> T {
+        out(t, in_elastic)
+    }
+
+    /// Uses a longer period than [`in_elastic`], as Penner's does
+    #[inline(always)]
+    pub fn in_out_elastic<T: Element>(t: T) -> T {
+        in_out(t, |t| {
+            if t <= T::zero() || t >= T::one() {
+                return num::clamp(t, T::zero(), T::one());
+            }
+            let c5 = constant::<T>(std::f64::consts::TAU / 4.5);
+            let ten = constant::<T>(10.0);
+            -constant::<T>(2.0).powf(ten * t - ten) * ((ten * t - constant(11.125)) * c5).sin()
+        })
+    }
+
+    /// Bounces off 0 with growing bounces before reaching 1
+    #[inline(always)]
+    pub fn in_bounce<T: Element>(t: T) -> T {
+        out(t, out_bounce)
+    }
+
+    /// Falls to 1 and bounces off it
+    #[inline(always)]
+    pub fn out_bounce<T: Element>(t: T) -> T {
+        let n1 = constant::<T>(7.5625);
+        let d1 = constant::<T>(2.75);
+        let bounce = |offset: f64, height: f64| {
+            let t = t - constant::<T>(offset) / d1;
+            n1 * t * t + constant(height)
+        };
+        if t < T::one() / d1 {
+            n1 * t * t
+        } else if t < constant::<T>(2.0) / d1 {
+            bounce(1.5, 0.75)
+        } else if t < constant::<T>(2.5) / d1 {
+            bounce(2.25, 0.9375)
+        } else {
+            bounce(2.625, 0.984375)
+        }
+    }
+
+    #[inline(always)]
+    pub fn in_out_bounce<T: Element>(t: T) -> T {
+        in_out(t, in_bounce)
+    }
+}
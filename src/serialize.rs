@@ -0,0 +1,290 @@
+//! Alternative serde representations, to be picked per field with `#[serde(with = ...)]`.
+//!
+//! By default Vectors and Quaternions serialize as arrays (`[1.0, 2.0, 3.0]`)
+//! and Matrices as arrays of rows. The modules here give other forms:
+//!
+//! - [`mod@array`]: the default form, spelled out
+//! - [`xyzw`]: a struct, like `{"x": 1.0, "y": 2.0, "z": 3.0}`
+//! - [`column_major`]: a flat column-major array, as glTF stores its matrices
+//! - [`axis_angle`]: a Quaternion as `{"axis": [0.0, 0.0, 1.0], "angle": 1.57}`, in radians
+//! - [`euler_degrees`]: a Quaternion as `{"x": 0.0, "y": 0.0, "z": 90.0}`, see [`Quaternion::from_euler`]
+//!
+//! # Examples
+//!
+//! ```
+//! use glmath::matrix::Matrix4x4;
+//! use glmath::vector::Vector3;
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Node {
+//!     #[serde(with = "glmath::serialize::xyzw")]
+//!     position: Vector3<f32>,
+//!     #[serde(with = "glmath::serialize::column_major")]
+//!     matrix: Matrix4x4<f32>,
+//! }
+//! ```
+
+#[cfg(feature = "quaternions")]
+use crate::quaternions::Quaternion;
+
+/// The default form: Vectors and Quaternions as arrays, Matrices as arrays of rows.
+/// Useful to override a container-wide choice
+pub mod array {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[inline(always)]
+    pub fn serialize<V: Serialize, S: Serializer>(
+        value: &V,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+
+    #[inline(always)]
+    pub fn deserialize<'de, V: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<V, D::Error> {
+        V::deserialize(deserializer)
+    }
+}
+
+/// Vectors and Quaternions as structs with named components
+pub mod xyzw {
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::vector::{Vector2, Vector3, Vector4};
+    use crate::Element;
+
+    /// The struct form of a Vector2
+    #[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Xy<T> {
+        pub x: T,
+        pub y: T,
+    }
+
+    /// The struct form of a Vector3
+    #[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Xyz<T> {
+        pub x: T,
+        pub y: T,
+        pub z: T,
+    }
+
+    /// The struct form of a Vector4 or Quaternion
+    #[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Xyzw<T> {
+        pub x: T,
+        pub y: T,
+        pub z: T,
+        pub w: T,
+    }
+
+    /// Types that have a struct form
+    pub trait AsXyzw: Sized {
+        type Repr: Serialize + DeserializeOwned;
+        fn to_xyzw_repr(&self) -> Self::Repr;
+        fn from_xyzw_repr(repr: Self::Repr) -> Self;
+    }
+
+    impl<T: Element + Serialize + DeserializeOwned> AsXyzw for Vector2<T> {
+        type Repr = Xy<T>;
+        #[inline(always)]
+        fn to_xyzw_repr(&self) -> Self::Repr {
+            Xy {
+                x: self[0],
+                y: self[1],
+            }
+        }
+        #[inline(always)]
+        fn from_xyzw_repr(repr: Self::Repr) -> Self {
+            Vector2([repr.x, repr.y])
+        }
+    }
+
+    impl<T: Element + Serialize + DeserializeOwned> AsXyzw for Vector3<T> {
+        type Repr = Xyz<T>;
+        #[inline(always)]
+        fn to_xyzw_repr(&self) -> Self::Repr {
+            Xyz {
+                x: self[0],
+                y: self[1],
+                z: self[2],
+            }
+        }
+        #[inline(always)]
+        fn from_xyzw_repr(repr: Self::Repr) -> Self {
+            Vector3([repr.x, repr.y, repr.z])
+        }
+    }
+
+    impl<T: Element + Serialize + DeserializeOwned> AsXyzw for Vector4<T> {
+        type Repr = Xyzw<T>;
+        #[inline(always)]
+        fn to_xyzw_repr(&self) -> Self::Repr {
+            Xyzw {
+                x: self[0],
+                y: self[1],
+                z: self[2],
+                w: self[3],
+            }
+        }
+        #[inline(always)]
+        fn from_xyzw_repr(repr: Self::Repr) -> Self {
+            Vector4([repr.x, repr.y, repr.z, repr.w])
+        }
+    }
+
+    #[cfg(feature = "quaternions")]
+    impl<T: Element + Serialize + DeserializeOwned> AsXyzw for super::Quaternion<T> {
+        type Repr = Xyzw<T>;
+        #[inline(always)]
+        fn to_xyzw_repr(&self) -> Self::Repr {
+            Vector4::from(*self).to_xyzw_repr()
+        }
+        #[inline(always)]
+        fn from_xyzw_repr(repr: Self::Repr) -> Self {
+            Self::from(Vector4::from_xyzw_repr(repr))
+        }
+    }
+
+    #[inline(always)]
+    pub fn serialize<V: AsXyzw, S: Serializer>(
+        value: &V,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.to_xyzw_repr().serialize(serializer)
+    }
+
+    #[inline(always)]
+    pub fn deserialize<'de, V: AsXyzw, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<V, D::Error> {
+        V::Repr::deserialize(deserializer).map(V::from_xyzw_repr)
+    }
+}
+
+/// Matrices as flat arrays in column-major order,
+/// which is how glTF stores the `matrix` of a node
+pub mod column_major {
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::matrix::{Matrix2x2, Matrix3x3, Matrix4x4};
+    use crate::Element;
+
+    /// Matrices that have a flat column-major form
+    pub trait AsColumnMajor: Sized {
+        type Repr: Serialize + DeserializeOwned;
+        fn to_column_major_repr(&self) -> Self::Repr;
+        fn from_column_major_repr(repr: Self::Repr) -> Self;
+    }
+
+    macro_rules! IMPL_COLUMN_MAJOR {
+        ($(($mat:ident, $len:expr)),*) => {
+            $(
+                impl<T: Element + Serialize + DeserializeOwned> AsColumnMajor for $mat<T> {
+                    type Repr = [T; $len];
+                    #[inline(always)]
+                    fn to_column_major_repr(&self) -> Self::Repr {
+                        self.to_cols_array()
+                    }
+                    #[inline(always)]
+                    fn from_column_major_repr(repr: Self::Repr) -> Self {
+                        // filled as rows, so the transpose has them as columns
+                        $mat::from(repr).transpose()
+                    }
+                }
+            )*
+        };
+    }
+
+    IMPL_COLUMN_MAJOR!((Matrix2x2, 4), (Matrix3x3, 9), (Matrix4x4, 16));
+
+    #[inline(always)]
+    pub fn serialize<M: AsColumnMajor, S: Serializer>(
+        value: &M,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.to_column_major_repr().serialize(serializer)
+    }
+
+    #[inline(always)]
+    pub fn deserialize<'de, M: AsColumnMajor, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<M, D::Error> {
+        M::Repr::deserialize(deserializer).map(M::from_column_major_repr)
+    }
+}
+
+/// Quaternions as an axis and an angle in radians.
+/// The axis doesn't have to be normalized when deserializing
+#[cfg(feature = "quaternions")]
+pub mod axis_angle {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Quaternion;
+    use crate::vector::Vector3;
+    use crate::Element;
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct AxisAngle<T: Element> {
+        axis: Vector3<T>,
+        angle: T,
+    }
+
+    #[inline(always)]
+    pub fn serialize<T: Element + Serialize, S: Serializer>(
+        value: &Quaternion<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let (axis, angle) = value.to_axis_angle();
+        AxisAngle { axis, angle }.serialize(serializer)
+    }
+
+    #[inline(always)]
+    pub fn deserialize<'de, T: Element + Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Quaternion<T>, D::Error> {
+        let repr = AxisAngle::deserialize(deserializer)?;
+        Ok(Quaternion::from_axis_angle(repr.axis, repr.angle))
+    }
+}
+
+/// Quaternions as Euler angles in degrees,
+/// in the order of [`Quaternion::from_euler`]
+#[cfg(feature = "quaternions")]
+pub mod euler_degrees {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::xyzw::Xyz;
+    use super::Quaternion;
+    use crate::angle::Deg;
+    use crate::Element;
+
+    #[inline(always)]
+    pub fn serialize<T: Element + Serialize, S: Serializer>(
+        value: &Quaternion<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let (x, y, z) = value.to_euler();
+        Xyz {
+            x: x.to_degrees(),
+            y: y.to_degrees(),
+            z: z.to_degrees(),
+        }
+        .serialize(serializer)
+    }
+
+    #[inline(always)]
+    pub fn deserialize<'de, T: Element + Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Quaternion<T>, D::Error> {
+        let repr = Xyz::<T>::deserialize(deserializer)?;
+        Ok(Quaternion::from_euler(
+            Deg(repr.x),
+            Deg(repr.y),
+            Deg(repr.z),
+        ))
+    }
+}
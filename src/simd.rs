@@ -0,0 +1,509 @@
+#![cfg(feature = "simd")]
+
+//! SIMD kernels for the f32 [`Vector4`], [`Matrix4x4`] and Quaternion operations.
+//!
+//! With the `simd` feature enabled, the generic methods (like [`Matrix4x4::mul_matrix`])
+//! check if they are working with f32 and forward to these kernels.
+//! The kernels use `core::arch` intrinsics for x86_64 (SSE2, plus SSE4.1 and FMA when
+//! they are enabled at compile time), aarch64 (NEON) and wasm32 (simd128),
+//! and fall back to plain arrays everywhere else.
+//!
+//! Note that when FMA is used, results can differ from [`scalar`] in the last bits
+
+use std::any::TypeId;
+
+#[cfg(feature = "quaternions")]
+use crate::quaternions::Quaternion;
+use crate::{
+    matrix::{Matrix3x3, Matrix4x4},
+    vector::Vector4,
+};
+
+/// Gives the value as U if T and U are the same type.
+/// This is how the generic methods pick out f32,
+/// and it is resolved at compile time
+#[inline(always)]
+pub(crate) fn downcast<T: 'static + Copy, U: 'static + Copy>(value: T) -> Option<U> {
+    if TypeId::of::<T>() == TypeId::of::<U>() {
+        // T and U are the same type, so this is a plain copy
+        Some(unsafe { core::mem::transmute_copy(&value) })
+    } else {
+        None
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[allow(unused_unsafe)]
+mod arch {
+    use core::arch::x86_64::*;
+
+    pub type F32x4 = __m128;
+
+    #[inline(always)]
+    pub fn load(value: [f32; 4]) -> F32x4 {
+        unsafe { _mm_loadu_ps(value.as_ptr()) }
+    }
+    #[inline(always)]
+    pub fn store(value: F32x4) -> [f32; 4] {
+        let mut ret = [0f32; 4];
+        unsafe { _mm_storeu_ps(ret.as_mut_ptr(), value) };
+        ret
+    }
+    #[inline(always)]
+    pub fn splat(value: f32) -> F32x4 {
+        unsafe { _mm_set1_ps(value) }
+    }
+    #[inline(always)]
+    pub fn add(a: F32x4, b: F32x4) -> F32x4 {
+        unsafe { _mm_add_ps(a, b) }
+    }
+    #[inline(always)]
+    pub fn sub(a: F32x4, b: F32x4) -> F32x4 {
+        unsafe { _mm_sub_ps(a, b) }
+    }
+    #[inline(always)]
+    pub fn mul(a: F32x4, b: F32x4) -> F32x4 {
+        unsafe { _mm_mul_ps(a, b) }
+    }
+    #[inline(always)]
+    pub fn sqrt(a: F32x4) -> F32x4 {
+        unsafe { _mm_sqrt_ps(a) }
+    }
+    /// Gives a * b + c
+    #[cfg(target_feature = "fma")]
+    #[inline(always)]
+    pub fn mul_add(a: F32x4, b: F32x4, c: F32x4) -> F32x4 {
+        unsafe { _mm_fmadd_ps(a, b, c) }
+    }
+    /// Gives a * b + c
+    #[cfg(not(target_feature = "fma"))]
+    #[inline(always)]
+    pub fn mul_add(a: F32x4, b: F32x4, c: F32x4) -> F32x4 {
+        unsafe { _mm_add_ps(_mm_mul_ps(a, b), c) }
+    }
+    /// Gives the sums of all four lanes of each input
+    #[inline(always)]
+    pub fn sum4(p0: F32x4, p1: F32x4, p2: F32x4, p3: F32x4) -> F32x4 {
+        unsafe {
+            let s01 = _mm_add_ps(_mm_unpacklo_ps(p0, p1), _mm_unpackhi_ps(p0, p1));
+            let s23 = _mm_add_ps(_mm_unpacklo_ps(p2, p3), _mm_unpackhi_ps(p2, p3));
+            _mm_add_ps(_mm_movelh_ps(s01, s23), _mm_movehl_ps(s23, s01))
+        }
+    }
+    #[cfg(target_feature = "sse4.1")]
+    #[inline(always)]
+    pub fn dot(a: F32x4, b: F32x4) -> f32 {
+        unsafe { _mm_cvtss_f32(_mm_dp_ps::<0xF1>(a, b)) }
+    }
+    #[cfg(not(target_feature = "sse4.1"))]
+    #[inline(always)]
+    pub fn dot(a: F32x4, b: F32x4) -> f32 {
+        let p = mul(a, b);
+        unsafe { _mm_cvtss_f32(sum4(p, p, p, p)) }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+#[allow(unused_unsafe)]
+mod arch {
+    use core::arch::aarch64::*;
+
+    pub type F32x4 = float32x4_t;
+
+    #[inline(always)]
+    pub fn load(value: [f32; 4]) -> F32x4 {
+        unsafe { vld1q_f32(value.as_ptr()) }
+    }
+    #[inline(always)]
+    pub fn store(value: F32x4) -> [f32; 4] {
+        let mut ret = [0f32; 4];
+        unsafe { vst1q_f32(ret.as_mut_ptr(), value) };
+        ret
+    }
+    #[inline(always)]
+    pub fn splat(value: f32) -> F32x4 {
+        unsafe { vdupq_n_f32(value) }
+    }
+    #[inline(always)]
+    pub fn add(a: F32x4, b: F32x4) -> F32x4 {
+        unsafe { vaddq_f32(a, b) }
+    }
+    #[inline(always)]
+    pub fn sub(a: F32x4, b: F32x4) -> F32x4 {
+        unsafe { vsubq_f32(a, b) }
+    }
+    #[inline(always)]
+    pub fn mul(a: F32x4, b: F32x4) -> F32x4 {
+        unsafe { vmulq_f32(a, b) }
+    }
+    #[inline(always)]
+    pub fn sqrt(a: F32x4) -> F32x4 {
+        unsafe { vsqrtq_f32(a) }
+    }
+    /// Gives a * b + c
+    #[inline(always)]
+    pub fn mul_add(a: F32x4, b: F32x4, c: F32x4) -> F32x4 {
+        unsafe { vfmaq_f32(c, a, b) }
+    }
+    /// Gives the sums of all four lanes of each input
+    #[inline(always)]
+    pub fn sum4(p0: F32x4, p1: F32x4, p2: F32x4, p3: F32x4) -> F32x4 {
+        unsafe { vpaddq_f32(vpaddq_f32(p0, p1), vpaddq_f32(p2, p3)) }
+    }
+    #[inline(always)]
+    pub fn dot(a: F32x4, b: F32x4) -> f32 {
+        unsafe { vaddvq_f32(vmulq_f32(a, b)) }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod arch {
+    use core::arch::wasm32::*;
+
+    pub type F32x4 = v128;
+
+    #[inline(always)]
+    pub fn load(value: [f32; 4]) -> F32x4 {
+        f32x4(value[0], value[1], value[2], value[3])
+    }
+    #[inline(always)]
+    pub fn store(value: F32x4) -> [f32; 4] {
+        [
+            f32x4_extract_lane::<0>(value),
+            f32x4_extract_lane::<1>(value),
+            f32x4_extract_lane::<2>(value),
+            f32x4_extract_lane::<3>(value),
+        ]
+    }
+    #[inline(always)]
+    pub fn splat(value: f32) -> F32x4 {
+        f32x4_splat(value)
+    }
+    #[inline(always)]
+    pub fn add(a: F32x4, b: F32x4) -> F32x4 {
+        f32x4_add(a, b)
+    }
+    #[inline(always)]
+    pub fn sub(a: F32x4, b: F32x4) -> F32x4 {
+        f32x4_sub(a, b)
+    }
+    #[inline(always)]
+    pub fn mul(a: F32x4, b: F32x4) -> F32x4 {
+        f32x4_mul(a, b)
+    }
+    #[inline(always)]
+    pub fn sqrt(a: F32x4) -> F32x4 {
+        f32x4_sqrt(a)
+    }
+    /// Gives a * b + c
+    #[inline(always)]
+    pub fn mul_add(a: F32x4, b: F32x4, c: F32x4) -> F32x4 {
+        f32x4_add(f32x4_mul(a, b), c)
+    }
+    /// Gives the sums of all four lanes of each input
+    #[inline(always)]
+    pub fn sum4(p0: F32x4, p1: F32x4, p2: F32x4, p3: F32x4) -> F32x4 {
+        let s01 = f32x4_add(
+            i32x4_shuffle::<0, 4, 1, 5>(p0, p1),
+            i32x4_shuffle::<2, 6, 3, 7>(p0, p1),
+        );
+        let s23 = f32x4_add(
+            i32x4_shuffle::<0, 4, 1, 5>(p2, p3),
+            i32x4_shuffle::<2, 6, 3, 7>(p2, p3),
+        );
+        f32x4_add(
+            i32x4_shuffle::<0, 1, 4, 5>(s01, s23),
+            i32x4_shuffle::<2, 3, 6, 7>(s01, s23),
+        )
+    }
+    #[inline(always)]
+    pub fn dot(a: F32x4, b: F32x4) -> f32 {
+        let p = mul(a, b);
+        f32x4_extract_lane::<0>(sum4(p, p, p, p))
+    }
+}
+
+#[cfg(not(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+)))]
+mod arch {
+    pub type F32x4 = [f32; 4];
+
+    #[inline(always)]
+    pub fn load(value: [f32; 4]) -> F32x4 {
+        value
+    }
+    #[inline(always)]
+    pub fn store(value: F32x4) -> [f32; 4] {
+        value
+    }
+    #[inline(always)]
+    pub fn splat(value: f32) -> F32x4 {
+        [value; 4]
+    }
+    #[inline(always)]
+    pub fn add(a: F32x4, b: F32x4) -> F32x4 {
+        [a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]]
+    }
+    #[inline(always)]
+    pub fn sub(a: F32x4, b: F32x4) -> F32x4 {
+        [a[0] - b[0], a[1] - b[1], a[2] - b[2], a[3] - b[3]]
+    }
+    #[inline(always)]
+    pub fn mul(a: F32x4, b: F32x4) -> F32x4 {
+        [a[0] * b[0], a[1] * b[1], a[2] * b[2], a[3] * b[3]]
+    }
+    #[inline(always)]
+    pub fn sqrt(a: F32x4) -> F32x4 {
+        a.map(f32::sqrt)
+    }
+    /// Gives a * b + c
+    #[inline(always)]
+    pub fn mul_add(a: F32x4, b: F32x4, c: F32x4) -> F32x4 {
+        let p = mul(a, b);
+        [p[0] + c[0], p[1] + c[1], p[2] + c[2], p[3] + c[3]]
+    }
+    /// Gives the sums of all four lanes of each input
+    #[inline(always)]
+    pub fn sum4(p0: F32x4, p1: F32x4, p2: F32x4, p3: F32x4) -> F32x4 {
+        [p0, p1, p2, p3].map(|p| p[0] + p[1] + p[2] + p[3])
+    }
+    #[inline(always)]
+    pub fn dot(a: F32x4, b: F32x4) -> f32 {
+        let p = mul(a, b);
+        p[0] + p[1] + p[2] + p[3]
+    }
+}
+
+/// Does inner product (aka dot product) of two f32 Vector4's
+#[inline(always)]
+pub fn mul_inner4(a: Vector4<f32>, b: Vector4<f32>) -> f32 {
+    arch::dot(arch::load(a.0), arch::load(b.0))
+}
+
+/// Multiplies the f32 Matrix4x4 and Vector4 together
+#[inline(always)]
+pub fn mul_vector4x4(m: Matrix4x4<f32>, v: Vector4<f32>) -> Vector4<f32> {
+    let v = arch::load(v.0);
+    Vector4(arch::store(arch::sum4(
+        arch::mul(arch::load(m[0].0), v),
+        arch::mul(arch::load(m[1].0), v),
+        arch::mul(arch::load(m[2].0), v),
+        arch::mul(arch::load(m[3].0), v),
+    )))
+}
+
+/// Multiplies two f32 Matrix4x4's together
+#[inline(always)]
+pub fn mul_matrix4x4(a: Matrix4x4<f32>, b: Matrix4x4<f32>) -> Matrix4x4<f32> {
+    // each row of the result is a linear
+    // combination of the rows of b
+    let b_rows = b.0.map(|row| arch::load(row.0));
+    Matrix4x4(a.0.map(|row| {
+        let mut ret = arch::mul(arch::splat(row[0]), b_rows[0]);
+        ret = arch::mul_add(arch::splat(row[1]), b_rows[1], ret);
+        ret = arch::mul_add(arch::splat(row[2]), b_rows[2], ret);
+        ret = arch::mul_add(arch::splat(row[3]), b_rows[3], ret);
+        Vector4(arch::store(ret))
+    }))
+}
+
+/// Multiplies two f32 Quaternions (the Hamilton product)
+#[cfg(feature = "quaternions")]
+#[inline(always)]
+pub fn mul_quaternion(a: Quaternion<f32>, b: Quaternion<f32>) -> Quaternion<f32> {
+    let [ax, ay, az, aw] = a.to_xyzw();
+    let [bx, by, bz, bw] = b.to_xyzw();
+    let mut ret = arch::mul(arch::splat(aw), arch::load([bx, by, bz, bw]));
+    ret = arch::mul_add(arch::splat(ax), arch::load([bw, -bz, by, -bx]), ret);
+    ret = arch::mul_add(arch::splat(ay), arch::load([bz, bw, -bx, -by]), ret);
+    ret = arch::mul_add(arch::splat(az), arch::load([-by, bx, bw, -bz]), ret);
+    Quaternion::from(arch::store(ret))
+}
+
+/// Loads the 4 lanes of value starting at index
+#[inline(always)]
+fn load_lanes<const N: usize>(value: &[f32; N], index: usize) -> arch::F32x4 {
+    arch::load([
+        value[index],
+        value[index + 1],
+        value[index + 2],
+        value[index + 3],
+    ])
+}
+
+/// Stores the 4 lanes into value starting at index
+#[inline(always)]
+fn store_lanes<const N: usize>(value: &mut [f32; N], index: usize, lanes: arch::F32x4) {
+    value[index..index + 4].copy_from_slice(&arch::store(lanes));
+}
+
+// The kernels for the f32 wide Vectors, working on their `[[f32; N]; 3]` arrays,
+// where N is 4 or 8. They are plain loops rather than closures so that they
+// always inline. They do the operations in the same order as the generic code,
+// so without FMA their results are the same to the bit
+
+#[inline(always)]
+pub(crate) fn wide_add<const N: usize>(a: [[f32; N]; 3], b: [[f32; N]; 3]) -> [[f32; N]; 3] {
+    let mut ret = [[0f32; N]; 3];
+    for cx in 0..3 {
+        for cy in (0..N).step_by(4) {
+            let lanes = arch::add(load_lanes(&a[cx], cy), load_lanes(&b[cx], cy));
+            store_lanes(&mut ret[cx], cy, lanes);
+        }
+    }
+    ret
+}
+
+#[inline(always)]
+pub(crate) fn wide_sub<const N: usize>(a: [[f32; N]; 3], b: [[f32; N]; 3]) -> [[f32; N]; 3] {
+    let mut ret = [[0f32; N]; 3];
+    for cx in 0..3 {
+        for cy in (0..N).step_by(4) {
+            let lanes = arch::sub(load_lanes(&a[cx], cy), load_lanes(&b[cx], cy));
+            store_lanes(&mut ret[cx], cy, lanes);
+        }
+    }
+    ret
+}
+
+#[inline(always)]
+pub(crate) fn wide_mul_lanes<const N: usize>(a: [[f32; N]; 3], scalars: [f32; N]) -> [[f32; N]; 3] {
+    let mut ret = [[0f32; N]; 3];
+    for cx in 0..3 {
+        for cy in (0..N).step_by(4) {
+            let lanes = arch::mul(load_lanes(&a[cx], cy), load_lanes(&scalars, cy));
+            store_lanes(&mut ret[cx], cy, lanes);
+        }
+    }
+    ret
+}
+
+#[inline(always)]
+pub(crate) fn wide_mul_inner<const N: usize>(a: [[f32; N]; 3], b: [[f32; N]; 3]) -> [f32; N] {
+    let mut ret = [0f32; N];
+    for cy in (0..N).step_by(4) {
+        let mut lanes = arch::mul(load_lanes(&a[0], cy), load_lanes(&b[0], cy));
+        lanes = arch::mul_add(load_lanes(&a[1], cy), load_lanes(&b[1], cy), lanes);
+        lanes = arch::mul_add(load_lanes(&a[2], cy), load_lanes(&b[2], cy), lanes);
+        store_lanes(&mut ret, cy, lanes);
+    }
+    ret
+}
+
+#[inline(always)]
+pub(crate) fn wide_mul_cross<const N: usize>(a: [[f32; N]; 3], b: [[f32; N]; 3]) -> [[f32; N]; 3] {
+    let mut ret = [[0f32; N]; 3];
+    for (cx, row) in ret.iter_mut().enumerate() {
+        let (i1, i2) = ((cx + 1) % 3, (cx + 2) % 3);
+        for cy in (0..N).step_by(4) {
+            let lanes = arch::sub(
+                arch::mul(load_lanes(&a[i1], cy), load_lanes(&b[i2], cy)),
+                arch::mul(load_lanes(&a[i2], cy), load_lanes(&b[i1], cy)),
+            );
+            store_lanes(row, cy, lanes);
+        }
+    }
+    ret
+}
+
+#[inline(always)]
+pub(crate) fn wide_sqrt<const N: usize>(a: [f32; N]) -> [f32; N] {
+    let mut ret = [0f32; N];
+    for cy in (0..N).step_by(4) {
+        store_lanes(&mut ret, cy, arch::sqrt(load_lanes(&a, cy)));
+    }
+    ret
+}
+
+/// Multiplies the first 3 rows of the Matrix, the first 3 elements of each,
+/// with every lane, and adds the 4th element when affine is set
+#[inline(always)]
+fn wide_mul_rows<const N: usize, const M: usize>(
+    rows: [[f32; M]; M],
+    v: [[f32; N]; 3],
+    affine: bool,
+) -> [[f32; N]; 3] {
+    let mut ret = [[0f32; N]; 3];
+    for cx in 0..3 {
+        let row = rows[cx];
+        for cy in (0..N).step_by(4) {
+            let mut lanes = arch::mul(arch::splat(row[0]), load_lanes(&v[0], cy));
+            lanes = arch::mul_add(arch::splat(row[1]), load_lanes(&v[1], cy), lanes);
+            lanes = arch::mul_add(arch::splat(row[2]), load_lanes(&v[2], cy), lanes);
+            if affine {
+                lanes = arch::add(lanes, arch::splat(row[3]));
+            }
+            store_lanes(&mut ret[cx], cy, lanes);
+        }
+    }
+    ret
+}
+
+#[inline(always)]
+pub(crate) fn wide_mul_matrix3x3<const N: usize>(
+    m: Matrix3x3<f32>,
+    v: [[f32; N]; 3],
+) -> [[f32; N]; 3] {
+    wide_mul_rows(m.to_rows_array_2d(), v, false)
+}
+
+/// Treats every lane as a point (w = 1)
+#[inline(always)]
+pub(crate) fn wide_mul_matrix4x4<const N: usize>(
+    m: Matrix4x4<f32>,
+    v: [[f32; N]; 3],
+) -> [[f32; N]; 3] {
+    wide_mul_rows(m.to_rows_array_2d(), v, true)
+}
+
+/// Plain scalar versions of the kernels.
+/// These are the reference that the SIMD versions are checked against
+pub mod scalar {
+    #[cfg(feature = "quaternions")]
+    use crate::quaternions::Quaternion;
+    use crate::{matrix::Matrix4x4, vector::Vector4};
+
+    /// Does inner product (aka dot product) of two f32 Vector4's
+    #[inline(always)]
+    pub fn mul_inner4(a: Vector4<f32>, b: Vector4<f32>) -> f32 {
+        a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3]
+    }
+
+    /// Multiplies the f32 Matrix4x4 and Vector4 together
+    #[inline(always)]
+    pub fn mul_vector4x4(m: Matrix4x4<f32>, v: Vector4<f32>) -> Vector4<f32> {
+        Vector4(m.0.map(|row| mul_inner4(row, v)))
+    }
+
+    /// Multiplies two f32 Matrix4x4's together
+    #[inline(always)]
+    pub fn mul_matrix4x4(a: Matrix4x4<f32>, b: Matrix4x4<f32>) -> Matrix4x4<f32> {
+        let mut ret = Matrix4x4([Vector4([0f32; 4]); 4]);
+        for cx in 0..4 {
+            for cy in 0..4 {
+                ret[cx][cy] = a[cx][0] * b[0][cy]
+                    + a[cx][1] * b[1][cy]
+                    + a[cx][2] * b[2][cy]
+                    + a[cx][3] * b[3][cy];
+            }
+        }
+        ret
+    }
+
+    /// Multiplies two f32 Quaternions (the Hamilton product)
+    #[cfg(feature = "quaternions")]
+    #[inline(always)]
+    pub fn mul_quaternion(a: Quaternion<f32>, b: Quaternion<f32>) -> Quaternion<f32> {
+        let [ax, ay, az, aw] = a.to_xyzw();
+        let [bx, by, bz, bw] = b.to_xyzw();
+        Quaternion::from([
+            aw * bx + ax * bw + ay * bz - az * by,
+            aw * by - ax * bz + ay * bw + az * bx,
+            aw * bz + ax * by - ay * bx + az * bw,
+            aw * bw - ax * bx - ay * by - az * bz,
+        ])
+    }
+}
@@ -0,0 +1,236 @@
+//! Barycentric coordinates and other utilities for triangles,
+//! in 2D with Vector2 or in 3D with Vector3.
+//!
+//! Barycentric coordinates are the weights of the corners a, b and c
+//! in a Vector3, and add up to 1. They can interpolate any [`Attribute`]
+//! given at the corners, like colors or texture coordinates.
+//!
+//! A triangle with its corners on a line (or on a single point) is degenerate.
+//! It has no area, so the functions that need one give None for it
+//! instead of dividing by 0.
+//!
+//! # Examples
+//!
+//! ```
+//! use glmath::triangle::{barycentric, interpolate};
+//! use glmath::vector::{Vector2, Vector4};
+//!
+//! let (a, b, c) = (
+//!     Vector2::from([0f32, 0f32]),
+//!     Vector2::from([4f32, 0f32]),
+//!     Vector2::from([0f32, 4f32]),
+//! );
+//! let weights = barycentric(Vector2::from([1f32, 2f32]), a, b, c).unwrap();
+//! assert_eq!(weights, glmath::vector::Vector3::from([0.25f32, 0.25f32, 0.5f32]));
+//!
+//! let red = Vector4::from([1f32, 0f32, 0f32, 1f32]);
+//! let blue = Vector4::from([0f32, 0f32, 1f32, 1f32]);
+//! let color = interpolate(weights, red, red, blue);
+//! assert_eq!(color, Vector4::from([0.5f32, 0f32, 0.5f32, 1f32]));
+//!
+//! let line = (a, b, Vector2::from([8f32, 0f32]));
+//! assert_eq!(barycentric(Vector2::from([1f32, 2f32]), line.0, line.1, line.2), None);
+//! ```
+
+use crate::vector::{Vector2, Vector3, Vector4};
+use crate::Element;
+
+/// Values that can be interpolated across a triangle or along a curve:
+/// scalars and Vectors of any size
+pub trait Attribute: Copy {
+    type Scalar: Element;
+    fn add(self, other: Self) -> Self;
+    fn mul_scalar(self, scalar: Self::Scalar) -> Self;
+}
+
+/// Values with a distance between them, that triangles and curves can be made of.
+/// These are also scalars and Vectors of any size
+pub trait Point: Attribute {
+    fn sub(self, other: Self) -> Self;
+    fn mul_inner(self, other: Self) -> Self::Scalar;
+}
+
+impl<T: Element> Attribute for T {
+    type Scalar = T;
+    #[inline(always)]
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+    #[inline(always)]
+    fn mul_scalar(self, scalar: T) -> Self {
+        self * scalar
+    }
+}
+
+macro_rules! IMPL_ATTRIBUTE {
+    ($($vec:ident),*) => {
+        $(
+            impl<T: Element> Attribute for $vec<T> {
+                type Scalar = T;
+                #[inline(always)]
+                fn add(self, other: Self) -> Self {
+                    $vec::add(self, other)
+                }
+                #[inline(always)]
+                fn mul_scalar(self, scalar: T) -> Self {
+                    $vec::mul_scalar(self, scalar)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! IMPL_POINT {
+    ($($vec:ident),*) => {
+        $(
+            impl<T: Element> Point for $vec<T> {
+                #[inline(always)]
+                fn sub(self, other: Self) -> Self {
+                    $vec::sub(self, other)
+                }
+                #[inline(always)]
+                fn mul_inner(self, other: Self) -> T {
+                    $vec::mul_inner(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl<T: Element> Point for T {
+    #[inline(always)]
+    fn sub(self, other: Self) -> Self {
+        self - other
+    }
+    #[inline(always)]
+    fn mul_inner(self, other: Self) -> T {
+        self * other
+    }
+}
+
+IMPL_ATTRIBUTE!(Vector2, Vector3, Vector4);
+IMPL_POINT!(Vector2, Vector3, Vector4);
+
+/// The squared lengths of the edges ab and ac, their dot product,
+/// and the squared area of the parallelogram they span
+#[inline(always)]
+fn edges<T: Element, P: Point<Scalar = T>>(a: P, b: P, c: P) -> (P, P, T, T, T, T) {
+    let ab = b.sub(a);
+    let ac = c.sub(a);
+    let d00 = ab.mul_inner(ab);
+    let d01 = ab.mul_inner(ac);
+    let d11 = ac.mul_inner(ac);
+    // the Gram determinant, clamped since rounding can take it below 0
+    let gram = (d00 * d11 - d01 * d01).max(T::zero());
+    (ab, ac, d00, d01, d11, gram)
+}
+
+/// Checks if the parallelogram is too thin, relative to its edges, to be
+/// told apart from a line
+#[inline(always)]
+fn is_degenerate<T: Element>(d00: T, d11: T, gram: T) -> bool {
+    gram <= T::epsilon() * d00 * d11
+}
+
+/// Gives the barycentric coordinates of p in the triangle abc, so that
+/// `p = a * w[0] + b * w[1] + c * w[2]`. In 3D, p is projected onto the
+/// plane of the triangle first.
+/// Gives None for a degenerate triangle
+#[inline(always)]
+pub fn barycentric<T: Element, P: Point<Scalar = T>>(p: P, a: P, b: P, c: P) -> Option<Vector3<T>> {
+    let (ab, ac, d00, d01, d11, gram) = edges(a, b, c);
+    if is_degenerate(d00, d11, gram) {
+        return None;
+    }
+    let ap = p.sub(a);
+    let d20 = ap.mul_inner(ab);
+    let d21 = ap.mul_inner(ac);
+    let v = (d11 * d20 - d01 * d21) / gram;
+    let w = (d00 * d21 - d01 * d20) / gram;
+    Some(Vector3([T::one() - v - w, v, w]))
+}
+
+/// Interpolates the values at the corners with barycentric weights
+#[inline(always)]
+pub fn interpolate<T: Element, A: Attribute<Scalar = T>>(
+    weights: Vector3<T>,
+    a: A,
+    b: A,
+    c: A,
+) -> A {
+    a.mul_scalar(weights[0])
+        .add(b.mul_scalar(weights[1]))
+        .add(c.mul_scalar(weights[2]))
+}
+
+/// Checks if p is in the triangle abc, including its edges.
+/// In 3D, p is projected onto the plane of the triangle first.
+/// Nothing is in a degenerate triangle
+#[inline(always)]
+pub fn point_in_triangle<T: Element, P: Point<Scalar = T>>(p: P, a: P, b: P, c: P) -> bool {
+    barycentric(p, a, b, c).is_some_and(|weights| weights.0.iter().all(|ca| *ca >= T::zero()))
+}
+
+/// Gives the area of the triangle, which is 0 for a degenerate triangle
+#[inline(always)]
+pub fn area<T: Element, P: Point<Scalar = T>>(a: P, b: P, c: P) -> T {
+    let (.., gram) = edges(a, b, c);
+    gram.sqrt() / (T::one() + T::one())
+}
+
+/// Gives the area of the 2D triangle, which is positive
+/// when the corners go counter-clockwise
+#[inline(always)]
+pub fn signed_area<T: Element>(a: Vector2<T>, b: Vector2<T>, c: Vector2<T>) -> T {
+    let ab = b.sub(a);
+    let ac = c.sub(a);
+    (ab[0] * ac[1] - ab[1] * ac[0]) / (T::one() + T::one())
+}
+
+/// Gives the unit normal of the 3D triangle, facing the side
+/// its corners go counter-clockwise from.
+/// Gives None for a degenerate triangle
+#[inline(always)]
+pub fn normal<T: Element>(a: Vector3<T>, b: Vector3<T>, c: Vector3<T>) -> Option<Vector3<T>> {
+    let (.., d00, _, d11, gram) = edges(a, b, c);
+    if is_degenerate(d00, d11, gram) {
+        return None;
+    }
+    b.sub(a).mul_cross(c.sub(a)).try_normalize()
+}
+
+/// Gives the centroid, the average of the corners
+#[inline(always)]
+pub fn centroid<T: Element, P: Point<Scalar = T>>(a: P, b: P, c: P) -> P {
+    let third = T::one() / (T::one() + T::one() + T::one());
+    interpolate(Vector3([third; 3]), a, b, c)
+}
+
+/// Gives the circumcenter, the center of the circle through the corners.
+/// Gives None for a degenerate triangle
+#[inline(always)]
+pub fn circumcenter<T: Element, P: Point<Scalar = T>>(a: P, b: P, c: P) -> Option<P> {
+    let (ab, ac, d00, d01, d11, gram) = edges(a, b, c);
+    if is_degenerate(d00, d11, gram) {
+        return None;
+    }
+    // solves for the point along ab and ac at the same distance from all corners
+    let two = T::one() + T::one();
+    let v = d11 * (d00 - d01) / (two * gram);
+    let w = d00 * (d11 - d01) / (two * gram);
+    Some(a.add(ab.mul_scalar(v)).add(ac.mul_scalar(w)))
+}
+
+/// Gives the incenter, the center of the largest circle inside the triangle.
+/// Gives None for a degenerate triangle
+#[inline(always)]
+pub fn incenter<T: Element, P: Point<Scalar = T>>(a: P, b: P, c: P) -> Option<P> {
+    let (.., d00, _, d11, gram) = edges(a, b, c);
+    if is_degenerate(d00, d11, gram) {
+        return None;
+    }
+    // each corner is weighted by the length of the edge across from it
+    let bc = c.sub(b);
+    let lengths = Vector3([bc.mul_inner(bc).sqrt(), d11.sqrt(), d00.sqrt()]);
+    Some(interpolate(lengths.div_scalar(lengths.sum()), a, b, c))
+}
@@ -1,4 +1,8 @@
-use crate::scalar::lerp;
+use crate::angle::Rad;
+use crate::scalar::{
+    critically_damped_spring, exp_decay, inverse_lerp, inversesqrt, lerp, remap, smootherstep,
+    smoothstep, step,
+};
 
 use super::Element;
 
@@ -65,6 +69,13 @@ macro_rules! GENERATE_VEC {
                     }
                 }
 
+                impl From<[<Vector $n>]<f32>> for [<Vector $n>]<f64> {
+                    #[inline(always)]
+                    fn from(value: [<Vector $n>]<f32>) -> Self {
+                        value.cast()
+                    }
+                }
+
                 impl<T:Element> Index<usize> for [<Vector $n>]<T> {
                     type Output = T;
                     #[inline(always)]
@@ -89,12 +100,57 @@ macro_rules! GENERATE_VEC {
                     }
                 }
 
+                /// Compares within `T::epsilon()`, see [`Self::eq_fast`].
+                /// Use [`Self::eq_exact`] to compare elements exactly
                 impl<T: Element> PartialEq for [<Vector $n>]<T> {
                     fn eq(&self, other: &Self) -> bool {
                         self.eq_fast(*other,T::epsilon())
                     }
                 }
 
+                #[cfg(feature = "approx")]
+                impl<T: Element + approx::AbsDiffEq<Epsilon = T>> approx::AbsDiffEq for [<Vector $n>]<T> {
+                    type Epsilon = T;
+                    #[inline(always)]
+                    fn default_epsilon() -> T {
+                        T::default_epsilon()
+                    }
+                    #[inline(always)]
+                    fn abs_diff_eq(&self, other: &Self, epsilon: T) -> bool {
+                        self.into_iter()
+                            .zip(other.into_iter())
+                            .all(|(ca, cb)| ca.abs_diff_eq(&cb, epsilon))
+                    }
+                }
+
+                #[cfg(feature = "approx")]
+                impl<T: Element + approx::RelativeEq<Epsilon = T>> approx::RelativeEq for [<Vector $n>]<T> {
+                    #[inline(always)]
+                    fn default_max_relative() -> T {
+                        T::default_max_relative()
+                    }
+                    #[inline(always)]
+                    fn relative_eq(&self, other: &Self, epsilon: T, max_relative: T) -> bool {
+                        self.into_iter()
+                            .zip(other.into_iter())
+                            .all(|(ca, cb)| ca.relative_eq(&cb, epsilon, max_relative))
+                    }
+                }
+
+                #[cfg(feature = "approx")]
+                impl<T: Element + approx::UlpsEq<Epsilon = T>> approx::UlpsEq for [<Vector $n>]<T> {
+                    #[inline(always)]
+                    fn default_max_ulps() -> u32 {
+                        T::default_max_ulps()
+                    }
+                    #[inline(always)]
+                    fn ulps_eq(&self, other: &Self, epsilon: T, max_ulps: u32) -> bool {
+                        self.into_iter()
+                            .zip(other.into_iter())
+                            .all(|(ca, cb)| ca.ulps_eq(&cb, epsilon, max_ulps))
+                    }
+                }
+
                 impl<T: Element> [<Vector $n>]<T> {
                     #[inline(always)]
                     pub fn new_uninit() -> Self {
@@ -102,6 +158,7 @@ macro_rules! GENERATE_VEC {
                     }
 
                     /// Does element-wise addition
+                    #[allow(clippy::should_implement_trait)]
                     #[inline(always)]
                     pub fn add(mut self, addend: Self) -> Self {
                         for cx in 0..$n {
@@ -111,6 +168,7 @@ macro_rules! GENERATE_VEC {
                     }
 
                     /// Does element-wise subtraction
+                    #[allow(clippy::should_implement_trait)]
                     #[inline(always)]
                     pub fn sub(mut self, subtrahend: Self) -> Self {
                         for cx in 0..$n {
@@ -140,6 +198,10 @@ macro_rules! GENERATE_VEC {
                     /// Does inner product (aka dot product)
                     #[inline(always)]
                     pub fn mul_inner(self, other: Self) -> T {
+                        #[cfg(feature = "simd")]
+                        if let (Some(ca), Some(cb)) = (crate::simd::downcast(self), crate::simd::downcast(other)) {
+                            return crate::simd::downcast(crate::simd::mul_inner4(ca, cb)).unwrap();
+                        }
                         self.into_iter()
                         .zip(other.into_iter())
                         .map(|(ca, cb)| ca * cb)
@@ -152,10 +214,59 @@ macro_rules! GENERATE_VEC {
                         self.mul_inner(self).sqrt()
                     }
 
-                    /// Gets the norm of the Vector
+                    /// Gets the norm of the Vector, which has exactly unit length.
+                    /// The zero Vector stays zero, same as [`Self::normalize_or_zero`]
                     #[inline(always)]
                     pub fn norm(self) -> Self {
-                        self.div_scalar(self.len() + T::epsilon())
+                        self.normalize_or_zero()
+                    }
+
+                    /// Gets the squared length of the Vector,
+                    /// which avoids the square root
+                    #[inline(always)]
+                    pub fn length_squared(self) -> T {
+                        self.mul_inner(self)
+                    }
+
+                    /// Normalizes the Vector to exactly unit length.
+                    /// Gives None for the zero Vector, or if any element is not finite.
+                    /// The Vector is first divided by its largest absolute element,
+                    /// so very large or tiny Vectors don't overflow or underflow
+                    #[inline(always)]
+                    pub fn try_normalize(self) -> Option<Self> {
+                        let max = self.into_iter().fold(T::zero(), |acc, ca| acc.max(ca.abs()));
+                        if !max.is_finite() || max == T::zero() {
+                            return None;
+                        }
+                        // the largest element is now 1, so the length is between 1 and sqrt(n)
+                        let scaled = self.div_scalar(max);
+                        let len_recip = scaled.len().recip();
+                        if len_recip.is_finite() {
+                            Some(scaled.mul_scalar(len_recip))
+                        } else {
+                            None
+                        }
+                    }
+
+                    /// Does [`Self::try_normalize`], but gives
+                    /// the default when the Vector cannot be normalized
+                    #[inline(always)]
+                    pub fn normalize_or(self, default: Self) -> Self {
+                        self.try_normalize().unwrap_or(default)
+                    }
+
+                    /// Does [`Self::try_normalize`], but gives
+                    /// the zero Vector when the Vector cannot be normalized
+                    #[inline(always)]
+                    pub fn normalize_or_zero(self) -> Self {
+                        self.normalize_or(Self([T::zero(); $n]))
+                    }
+
+                    /// Checks if the Vector has unit length,
+                    /// within the given epsilon value
+                    #[inline(always)]
+                    pub fn is_normalized(self, epsilon: T) -> bool {
+                        (self.length_squared() - T::one()).abs() <= epsilon
                     }
 
                     /// Gets the distance between both Vectors
@@ -183,6 +294,13 @@ macro_rules! GENERATE_VEC {
                         self
                     }
 
+                    /// Does by-value comparison to see if two Vector's are exactly equal,
+                    /// unlike `==` which compares within `T::epsilon()`
+                    #[inline(always)]
+                    pub fn eq_exact(self, other: Self) -> bool {
+                        self.0 == other.0
+                    }
+
                     /// Does by-value comparison to see if two Vector's are equal
                     /// by using the given epsilon value
                     #[inline(always)]
@@ -195,6 +313,305 @@ macro_rules! GENERATE_VEC {
                         }
                         true
                     }
+
+                    /// Applies the function to each element of the Vector
+                    #[inline(always)]
+                    pub fn map<F: FnMut(T) -> T>(self, f: F) -> Self {
+                        Self(self.0.map(f))
+                    }
+
+                    /// Converts each element to another Element type,
+                    /// like `f32` to `f16`. Values out of the range of U
+                    /// become infinite, so this is lossy
+                    #[inline(always)]
+                    pub fn cast<U: Element>(self) -> [<Vector $n>]<U> {
+                        [<Vector $n>](self.0.map(|ca| num::cast(ca).unwrap()))
+                    }
+
+                    /// Converts each element to another Element type through
+                    /// [`num::NumCast`], giving None if any of them can't be represented.
+                    /// That includes finite elements that overflow to infinity,
+                    /// like `1e300f64` as f32
+                    #[inline(always)]
+                    pub fn try_cast<U: Element>(self) -> Option<[<Vector $n>]<U>> {
+                        let mut ret = [<Vector $n>]([U::zero(); $n]);
+                        for cx in 0..$n {
+                            ret[cx] = num::cast(self[cx])?;
+                            if self[cx].is_finite() && !ret[cx].is_finite() {
+                                return None;
+                            }
+                        }
+                        Some(ret)
+                    }
+
+                    /// Applies the function to each pair of elements
+                    /// of the two Vectors
+                    #[inline(always)]
+                    pub fn zip_map<F: FnMut(T, T) -> T>(mut self, other: Self, mut f: F) -> Self {
+                        for cx in 0..$n {
+                            self[cx] = f(self[cx], other[cx]);
+                        }
+                        self
+                    }
+
+                    /// Does element-wise multiplication (aka Hadamard product)
+                    #[inline(always)]
+                    pub fn mul_elem(self, other: Self) -> Self {
+                        self.zip_map(other, |ca, cb| ca * cb)
+                    }
+
+                    /// Does element-wise division
+                    #[inline(always)]
+                    pub fn div_elem(self, other: Self) -> Self {
+                        self.zip_map(other, |ca, cb| ca / cb)
+                    }
+
+                    /// Gives the element-wise minimum of both Vectors
+                    #[inline(always)]
+                    pub fn min(self, other: Self) -> Self {
+                        self.zip_map(other, T::min)
+                    }
+
+                    /// Gives the element-wise maximum of both Vectors
+                    #[inline(always)]
+                    pub fn max(self, other: Self) -> Self {
+                        self.zip_map(other, T::max)
+                    }
+
+                    /// Clamps each element between the
+                    /// matching elements of min and max
+                    #[inline(always)]
+                    pub fn clamp(self, min: Self, max: Self) -> Self {
+                        self.max(min).min(max)
+                    }
+
+                    /// Gives the absolute value of each element
+                    #[inline(always)]
+                    pub fn abs(self) -> Self {
+                        self.map(T::abs)
+                    }
+
+                    /// Rounds each element down
+                    #[inline(always)]
+                    pub fn floor(self) -> Self {
+                        self.map(T::floor)
+                    }
+
+                    /// Rounds each element up
+                    #[inline(always)]
+                    pub fn ceil(self) -> Self {
+                        self.map(T::ceil)
+                    }
+
+                    /// Rounds each element to the nearest integer,
+                    /// with half-way cases away from 0
+                    #[inline(always)]
+                    pub fn round(self) -> Self {
+                        self.map(T::round)
+                    }
+
+                    /// Gives the fractional part of each element
+                    #[inline(always)]
+                    pub fn fract(self) -> Self {
+                        self.map(T::fract)
+                    }
+
+                    /// Gives the sign of each element
+                    /// (see [`num::Float::signum`])
+                    #[inline(always)]
+                    pub fn signum(self) -> Self {
+                        self.map(T::signum)
+                    }
+
+                    /// Gives the reciprocal (1/x) of each element
+                    #[inline(always)]
+                    pub fn recip(self) -> Self {
+                        self.map(T::recip)
+                    }
+
+                    /// Gives the square root of each element
+                    #[inline(always)]
+                    pub fn sqrt(self) -> Self {
+                        self.map(T::sqrt)
+                    }
+
+                    /// Raises each element to the given power
+                    #[inline(always)]
+                    pub fn powf(self, power: T) -> Self {
+                        self.map(|ca| ca.powf(power))
+                    }
+
+                    /// Gives the smallest element of the Vector
+                    #[inline(always)]
+                    pub fn min_element(self) -> T {
+                        self.into_iter().fold(T::infinity(), T::min)
+                    }
+
+                    /// Gives the largest element of the Vector
+                    #[inline(always)]
+                    pub fn max_element(self) -> T {
+                        self.into_iter().fold(T::neg_infinity(), T::max)
+                    }
+
+                    /// Gives the sum of all elements
+                    #[inline(always)]
+                    pub fn sum(self) -> T {
+                        self.into_iter().sum()
+                    }
+
+                    /// Gives the product of all elements
+                    #[inline(always)]
+                    pub fn product(self) -> T {
+                        self.into_iter().fold(T::one(), |ca, cb| ca * cb)
+                    }
+
+                    /// Gets the squared distance between both Vectors,
+                    /// which avoids the square root
+                    #[inline(always)]
+                    pub fn distance_squared(self, other: Self) -> T {
+                        let diff = other.sub(self);
+                        diff.mul_inner(diff)
+                    }
+
+                    /// GLSL `reflect`. Reflects the incident Vector
+                    /// off the surface with the given normal.
+                    /// The normal should be normalized
+                    #[inline(always)]
+                    pub fn reflect(self, normal: Self) -> Self {
+                        let two = T::one() + T::one();
+                        self.sub(normal.mul_scalar(two * normal.mul_inner(self)))
+                    }
+
+                    /// GLSL `refract`. Refracts the incident Vector
+                    /// through the surface with the given normal,
+                    /// where eta is the ratio of indices of refraction.
+                    /// Both Vectors should be normalized.
+                    /// Gives None on total internal reflection
+                    #[inline(always)]
+                    pub fn refract(self, normal: Self, eta: T) -> Option<Self> {
+                        let n_dot_i = normal.mul_inner(self);
+                        let k = T::one() - eta * eta * (T::one() - n_dot_i * n_dot_i);
+                        if k < T::zero() {
+                            None
+                        } else {
+                            Some(self.mul_scalar(eta).sub(normal.mul_scalar(eta * n_dot_i + k.sqrt())))
+                        }
+                    }
+
+                    /// GLSL `faceforward`. Gives self (the normal) if it points
+                    /// against the incident Vector (judged by the reference normal),
+                    /// otherwise gives the negated normal
+                    #[inline(always)]
+                    pub fn faceforward(self, incident: Self, reference: Self) -> Self {
+                        if reference.mul_inner(incident) < T::zero() {
+                            self
+                        } else {
+                            self.mul_scalar(-T::one())
+                        }
+                    }
+
+                    /// Gives the projection of self onto the other Vector
+                    #[inline(always)]
+                    pub fn project_onto(self, other: Self) -> Self {
+                        other.mul_scalar(self.mul_inner(other) / other.mul_inner(other))
+                    }
+
+                    /// Gives the part of self that is perpendicular
+                    /// to the other Vector (the rejection)
+                    #[inline(always)]
+                    pub fn reject_from(self, other: Self) -> Self {
+                        self.sub(self.project_onto(other))
+                    }
+
+                    /// GLSL `step` on each element.
+                    /// Gives 0 where self < edge, otherwise 1
+                    #[inline(always)]
+                    pub fn step(self, edge: Self) -> Self {
+                        self.zip_map(edge, |ca, cb| step(cb, ca))
+                    }
+
+                    /// GLSL `smoothstep` on each element,
+                    /// see [`crate::scalar::smoothstep`]
+                    #[inline(always)]
+                    pub fn smoothstep(mut self, edge0: Self, edge1: Self) -> Self {
+                        for cx in 0..$n {
+                            self[cx] = smoothstep(edge0[cx], edge1[cx], self[cx]);
+                        }
+                        self
+                    }
+
+                    /// GLSL `mix` with a Vector of interpolation
+                    /// parameters, one for each element
+                    #[inline(always)]
+                    pub fn mix(mut self, other: Self, t: Self) -> Self {
+                        for cx in 0..$n {
+                            self[cx] = lerp(self[cx], other[cx], t[cx]);
+                        }
+                        self
+                    }
+
+                    /// GLSL `inversesqrt` on each element
+                    #[inline(always)]
+                    pub fn inversesqrt(self) -> Self {
+                        self.map(inversesqrt)
+                    }
+
+                    /// [`crate::scalar::inverse_lerp`] on each element,
+                    /// with self as the value
+                    #[inline(always)]
+                    pub fn inverse_lerp(mut self, value0: Self, value1: Self) -> Self {
+                        for cx in 0..$n {
+                            self[cx] = inverse_lerp(value0[cx], value1[cx], self[cx]);
+                        }
+                        self
+                    }
+
+                    /// [`crate::scalar::remap`] on each element,
+                    /// with self as the value
+                    #[inline(always)]
+                    pub fn remap(mut self, from0: Self, from1: Self, to0: Self, to1: Self) -> Self {
+                        for cx in 0..$n {
+                            self[cx] = remap(from0[cx], from1[cx], to0[cx], to1[cx], self[cx]);
+                        }
+                        self
+                    }
+
+                    /// [`crate::scalar::smootherstep`] on each element
+                    #[inline(always)]
+                    pub fn smootherstep(mut self, edge0: Self, edge1: Self) -> Self {
+                        for cx in 0..$n {
+                            self[cx] = smootherstep(edge0[cx], edge1[cx], self[cx]);
+                        }
+                        self
+                    }
+
+                    /// [`crate::scalar::exp_decay`] on each element
+                    #[inline(always)]
+                    pub fn exp_decay(self, target: Self, decay: T, dt: T) -> Self {
+                        self.zip_map(target, |ca, cb| exp_decay(ca, cb, decay, dt))
+                    }
+
+                    /// [`crate::scalar::critically_damped_spring`] on each element,
+                    /// with a velocity for each
+                    #[inline(always)]
+                    pub fn critically_damped_spring(
+                        mut self,
+                        target: Self,
+                        velocity: &mut Self,
+                        smooth_time: T,
+                        dt: T,
+                    ) -> Self {
+                        for cx in 0..$n {
+                            self[cx] = critically_damped_spring(
+                                self[cx],
+                                target[cx],
+                                &mut velocity[cx],
+                                smooth_time,
+                                dt,
+                            );
+                        }
+                        self
+                    }
                 }// impl end
             }
         )*
@@ -236,19 +653,99 @@ impl<T: Element> From<(Vector2<T>, T)> for Vector3<T> {
     }
 }
 
+/// Gives the angle of (x, y) from the x axis, in (-pi, pi].
+/// Negative zeros are taken as zeros, so the negative x axis is pi,
+/// and the origin is 0
+#[inline(always)]
+fn polar_angle<T: Element>(x: T, y: T) -> Rad<T> {
+    Rad((y + T::zero()).atan2(x + T::zero()))
+}
+
 impl<T: Element> Vector3<T> {
     #[inline(always)]
     /// Creates a Vector from spherical coordinates
     /// theta is angle from top axis downwards
     /// phi is the standard polar angle
     /// magnitude is the scaling magnitude
+    /// The angles are in radians, unless given as [`crate::angle::Deg`]
     //TODO: add more/examples to documentation
-    pub fn from_spherical(theta: T, phi: T, magnitude: T) -> Self {
-        let (theta_sin, theta_cos) = theta.sin_cos();
-        let (phi_sin, phi_cos) = phi.sin_cos();
+    pub fn from_spherical(theta: impl Into<Rad<T>>, phi: impl Into<Rad<T>>, magnitude: T) -> Self {
+        let (theta_sin, theta_cos) = theta.into().sin_cos();
+        let (phi_sin, phi_cos) = phi.into().sin_cos();
 
         Self([theta_sin * phi_cos, theta_sin * phi_sin, theta_cos]).mul_scalar(magnitude)
     }
+
+    /// Gives the spherical coordinates (theta, phi, magnitude)
+    /// of the Vector, in the conventions of [`Self::from_spherical`].
+    /// Theta is in [0, pi] and phi in (-pi, pi].
+    /// On the z axis phi is 0, and the zero Vector gives all 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use glmath::angle::Rad;
+    /// use glmath::vector::Vector3;
+    /// use std::f32::consts::FRAC_PI_2;
+    ///
+    /// let (theta, phi, magnitude) = Vector3::from([0f32, 2f32, 0f32]).to_spherical();
+    /// assert_eq!((theta, phi, magnitude), (Rad(FRAC_PI_2), Rad(FRAC_PI_2), 2f32));
+    ///
+    /// let (theta, phi, magnitude) = Vector3::from([0f32, 0f32, -3f32]).to_spherical();
+    /// let v = Vector3::from_spherical(theta, phi, magnitude);
+    /// assert!(v.dist(Vector3::from([0f32, 0f32, -3f32])) < 1e-6);
+    /// ```
+    #[inline(always)]
+    pub fn to_spherical(self) -> (Rad<T>, Rad<T>, T) {
+        let magnitude = self.len();
+        if magnitude == T::zero() {
+            return (Rad(T::zero()), Rad(T::zero()), T::zero());
+        }
+        let theta = (self[2] / magnitude).max(-T::one()).min(T::one()).acos();
+        (Rad(theta), polar_angle(self[0], self[1]), magnitude)
+    }
+
+    /// Creates a Vector from cylindrical coordinates
+    /// phi is the standard polar angle around the z axis
+    /// radius is the distance from the z axis
+    /// height is the z coordinate
+    /// The angle is in radians, unless given as [`crate::angle::Deg`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use glmath::angle::Deg;
+    /// use glmath::vector::Vector3;
+    ///
+    /// let v = Vector3::from_cylindrical(Deg(90f32), 2f32, 5f32);
+    /// assert!(v.dist(Vector3::from([0f32, 2f32, 5f32])) < 1e-6);
+    /// ```
+    #[inline(always)]
+    pub fn from_cylindrical(phi: impl Into<Rad<T>>, radius: T, height: T) -> Self {
+        let (phi_sin, phi_cos) = phi.into().sin_cos();
+
+        Self([phi_cos * radius, phi_sin * radius, height])
+    }
+
+    /// Gives the cylindrical coordinates (phi, radius, height)
+    /// of the Vector, in the conventions of [`Self::from_cylindrical`].
+    /// Phi is in (-pi, pi], and 0 on the z axis
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use glmath::angle::Rad;
+    /// use glmath::vector::Vector3;
+    /// use std::f32::consts::PI;
+    ///
+    /// let (phi, radius, height) = Vector3::from([-2f32, 0f32, 5f32]).to_cylindrical();
+    /// assert_eq!((phi, radius, height), (Rad(PI), 2f32, 5f32));
+    /// ```
+    #[inline(always)]
+    pub fn to_cylindrical(self) -> (Rad<T>, T, T) {
+        let planar = Vector2([self[0], self[1]]);
+        (polar_angle(self[0], self[1]), planar.len(), self[2])
+    }
     /// Does cross product
     ///
     /// # Examples
@@ -284,12 +781,35 @@ impl<T: Element> Vector2<T> {
     /// Creates a Vector from polar coordinates
     /// phi is the standard polar angle
     /// magnitude is the scaling magnitude
+    /// The angle is in radians, unless given as [`crate::angle::Deg`]
     //TODO: add more/examples to documentation
-    pub fn from_polar(phi: T, magnitude: T) -> Self {
-        let (phi_sin, phi_cos) = phi.sin_cos();
+    pub fn from_polar(phi: impl Into<Rad<T>>, magnitude: T) -> Self {
+        let (phi_sin, phi_cos) = phi.into().sin_cos();
 
         Self([phi_cos, phi_sin]).mul_scalar(magnitude)
     }
+
+    /// Gives the polar coordinates (phi, magnitude) of the Vector,
+    /// in the conventions of [`Self::from_polar`].
+    /// Phi is in (-pi, pi], and the zero Vector gives (0, 0)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use glmath::angle::Rad;
+    /// use glmath::vector::Vector2;
+    /// use std::f32::consts::{FRAC_PI_2, PI};
+    ///
+    /// assert_eq!(Vector2::from([0f32, -3f32]).to_polar(), (Rad(-FRAC_PI_2), 3f32));
+    /// assert_eq!(Vector2::from([-1f32, 0f32]).to_polar(), (Rad(PI), 1f32));
+    ///
+    /// let (phi, magnitude) = Vector2::from([1f32, 1f32]).to_polar();
+    /// assert!(Vector2::from_polar(phi, magnitude).dist(Vector2::from([1f32, 1f32])) < 1e-6);
+    /// ```
+    #[inline(always)]
+    pub fn to_polar(self) -> (Rad<T>, T) {
+        (polar_angle(self[0], self[1]), self.len())
+    }
     /// Gets the perpendicular Vector
     /// See example for difference to [`crate::vector::Vector2::perp2`]
     ///
@@ -0,0 +1,350 @@
+//! "Wide" Vectors stored as a structure of arrays.
+//!
+//! `Vector3x4` holds 4 [`Vector3`]s, but stores all the x's together, then
+//! all the y's, then all the z's. Every operation then works on a whole
+//! lane of values at once.
+//!
+//! With the `simd` feature, the f32 operations use the 4-wide lanes of
+//! [`crate::simd`], one per component for `Vector3x4` and two for `Vector3x8`.
+//! Without it they are plain loops. The compiler often vectorizes those on
+//! their own, but not reliably: in a loop over a slice of wide Vectors it
+//! can vectorize across the slice instead, with gathers that are slower than
+//! plain [`Vector3`]s. Compare with `cargo bench --features="simd random_vectors" --bench=vector_wide`
+
+use crate::matrix::{Matrix3x3, Matrix4x4};
+use crate::vector::Vector3;
+use crate::Element;
+
+use std::ops::{Index, IndexMut};
+
+macro_rules! GENERATE_WIDE_VEC {
+    ($($n:expr),*) => {
+        $(
+            // Generate the struct using paste
+            paste::item! {
+
+                #[doc = concat!("A structure of arrays holding ", stringify!($n), " Vector3's.")]
+                /// Index 0 is all the x's, 1 the y's and 2 the z's
+                #[repr(transparent)]
+                #[derive(Debug, Clone, Copy)]
+                #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+                pub struct [<Vector3x $n>]<T: Element>(pub [[T; $n]; 3]);
+
+                #[cfg(feature = "bytemuck")]
+                unsafe impl<T: Element> bytemuck::Pod for [<Vector3x $n>]<T> {}
+
+                #[cfg(feature = "bytemuck")]
+                unsafe impl<T: Element> bytemuck::Zeroable for [<Vector3x $n>]<T> {
+                    fn zeroed() -> Self {
+                        Self([[T::zero(); $n]; 3])
+                    }
+                }
+
+                impl<T: Element> From<[Vector3<T>; $n]> for [<Vector3x $n>]<T> {
+                    #[inline(always)]
+                    fn from(value: [Vector3<T>; $n]) -> Self {
+                        Self::from_slice(&value)
+                    }
+                }
+
+                impl<T: Element> From<&[Vector3<T>; $n]> for [<Vector3x $n>]<T> {
+                    #[inline(always)]
+                    fn from(value: &[Vector3<T>; $n]) -> Self {
+                        Self::from_slice(value)
+                    }
+                }
+
+                impl<T: Element> From<[<Vector3x $n>]<T>> for [Vector3<T>; $n] {
+                    #[inline(always)]
+                    fn from(value: [<Vector3x $n>]<T>) -> Self {
+                        let mut ret = [Vector3([T::zero(); 3]); $n];
+                        value.write_to_slice(&mut ret);
+                        ret
+                    }
+                }
+
+                /// Compares within `T::epsilon()`, see [`Self::eq_fast`].
+                /// Use [`Self::eq_exact`] to compare elements exactly
+                impl<T: Element> PartialEq for [<Vector3x $n>]<T> {
+                    fn eq(&self, other: &Self) -> bool {
+                        self.eq_fast(*other, T::epsilon())
+                    }
+                }
+
+                impl<T: Element> Index<usize> for [<Vector3x $n>]<T> {
+                    type Output = [T; $n];
+                    #[inline(always)]
+                    fn index(&self, index: usize) -> &Self::Output {
+                        self.0.index(index)
+                    }
+                }
+
+                impl<T: Element> IndexMut<usize> for [<Vector3x $n>]<T> {
+                    #[inline(always)]
+                    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                        self.0.index_mut(index)
+                    }
+                }
+
+                impl<T: Element> [<Vector3x $n>]<T> {
+                    /// Gives every lane as the same Vector
+                    #[inline(always)]
+                    pub fn splat(value: Vector3<T>) -> Self {
+                        Self([[value[0]; $n], [value[1]; $n], [value[2]; $n]])
+                    }
+
+                    #[doc = concat!("Loads the first ", stringify!($n), " Vectors of the slice.")]
+                    /// Panics if the slice is shorter than that
+                    #[inline(always)]
+                    pub fn from_slice(value: &[Vector3<T>]) -> Self {
+                        let mut ret = Self([[T::zero(); $n]; 3]);
+                        for (cx, ca) in value[..$n].iter().enumerate() {
+                            ret[0][cx] = ca[0];
+                            ret[1][cx] = ca[1];
+                            ret[2][cx] = ca[2];
+                        }
+                        ret
+                    }
+
+                    #[doc = concat!("Stores the lanes into the first ", stringify!($n), " Vectors of the slice.")]
+                    /// Panics if the slice is shorter than that
+                    #[inline(always)]
+                    pub fn write_to_slice(self, value: &mut [Vector3<T>]) {
+                        for (cx, ca) in value[..$n].iter_mut().enumerate() {
+                            *ca = self.lane(cx);
+                        }
+                    }
+
+                    /// Gets a single Vector out of the given lane
+                    #[inline(always)]
+                    pub fn lane(self, index: usize) -> Vector3<T> {
+                        Vector3([self[0][index], self[1][index], self[2][index]])
+                    }
+
+                    /// Sets the given lane to the Vector
+                    #[inline(always)]
+                    pub fn set_lane(&mut self, index: usize, value: Vector3<T>) {
+                        self[0][index] = value[0];
+                        self[1][index] = value[1];
+                        self[2][index] = value[2];
+                    }
+
+                    /// Does element-wise addition
+                    #[allow(clippy::should_implement_trait)]
+                    #[inline(always)]
+                    pub fn add(mut self, addend: Self) -> Self {
+                        #[cfg(feature = "simd")]
+                        if let (Some(ca), Some(cb)) = (crate::simd::downcast(self.0), crate::simd::downcast(addend.0)) {
+                            return Self(crate::simd::downcast(crate::simd::wide_add::<$n>(ca, cb)).unwrap());
+                        }
+                        for cx in 0..3 {
+                            for cy in 0..$n {
+                                self[cx][cy] = self[cx][cy] + addend[cx][cy];
+                            }
+                        }
+                        self
+                    }
+
+                    /// Does element-wise subtraction
+                    #[allow(clippy::should_implement_trait)]
+                    #[inline(always)]
+                    pub fn sub(mut self, subtrahend: Self) -> Self {
+                        #[cfg(feature = "simd")]
+                        if let (Some(ca), Some(cb)) = (crate::simd::downcast(self.0), crate::simd::downcast(subtrahend.0)) {
+                            return Self(crate::simd::downcast(crate::simd::wide_sub::<$n>(ca, cb)).unwrap());
+                        }
+                        for cx in 0..3 {
+                            for cy in 0..$n {
+                                self[cx][cy] = self[cx][cy] - subtrahend[cx][cy];
+                            }
+                        }
+                        self
+                    }
+
+                    /// Does scalar-wise multiplication
+                    #[inline(always)]
+                    pub fn mul_scalar(self, scalar: T) -> Self {
+                        self.mul_lanes([scalar; $n])
+                    }
+
+                    /// Does scalar-wise division
+                    #[inline(always)]
+                    pub fn div_scalar(mut self, scalar: T) -> Self {
+                        for cx in 0..3 {
+                            for cy in 0..$n {
+                                self[cx][cy] = self[cx][cy] / scalar;
+                            }
+                        }
+                        self
+                    }
+
+                    /// Multiplies each lane by its own scalar
+                    #[inline(always)]
+                    pub fn mul_lanes(mut self, scalars: [T; $n]) -> Self {
+                        #[cfg(feature = "simd")]
+                        if let (Some(ca), Some(cb)) = (crate::simd::downcast(self.0), crate::simd::downcast(scalars)) {
+                            return Self(crate::simd::downcast(crate::simd::wide_mul_lanes::<$n>(ca, cb)).unwrap());
+                        }
+                        for cx in 0..3 {
+                            for cy in 0..$n {
+                                self[cx][cy] = self[cx][cy] * scalars[cy];
+                            }
+                        }
+                        self
+                    }
+
+                    /// Does inner product (aka dot product) for every lane
+                    #[inline(always)]
+                    pub fn mul_inner(self, other: Self) -> [T; $n] {
+                        #[cfg(feature = "simd")]
+                        if let (Some(ca), Some(cb)) = (crate::simd::downcast(self.0), crate::simd::downcast(other.0)) {
+                            return crate::simd::downcast(crate::simd::wide_mul_inner::<$n>(ca, cb)).unwrap();
+                        }
+                        let mut ret = [T::zero(); $n];
+                        for cy in 0..$n {
+                            ret[cy] = self[0][cy] * other[0][cy]
+                                + self[1][cy] * other[1][cy]
+                                + self[2][cy] * other[2][cy];
+                        }
+                        ret
+                    }
+
+                    /// Does cross product for every lane
+                    #[inline(always)]
+                    pub fn mul_cross(self, crossed: Self) -> Self {
+                        #[cfg(feature = "simd")]
+                        if let (Some(ca), Some(cb)) = (crate::simd::downcast(self.0), crate::simd::downcast(crossed.0)) {
+                            return Self(crate::simd::downcast(crate::simd::wide_mul_cross::<$n>(ca, cb)).unwrap());
+                        }
+                        let mut ret = self;
+                        for cy in 0..$n {
+                            ret[0][cy] = self[1][cy] * crossed[2][cy] - self[2][cy] * crossed[1][cy];
+                            ret[1][cy] = self[2][cy] * crossed[0][cy] - self[0][cy] * crossed[2][cy];
+                            ret[2][cy] = self[0][cy] * crossed[1][cy] - self[1][cy] * crossed[0][cy];
+                        }
+                        ret
+                    }
+
+                    /// Gets the length of every lane
+                    #[inline(always)]
+                    pub fn len(self) -> [T; $n] {
+                        let squared = self.mul_inner(self);
+                        #[cfg(feature = "simd")]
+                        if let Some(ca) = crate::simd::downcast(squared) {
+                            return crate::simd::downcast(crate::simd::wide_sqrt::<$n>(ca)).unwrap();
+                        }
+                        squared.map(T::sqrt)
+                    }
+
+                    /// Gets the norm of every lane.
+                    /// This matches [`Vector3::norm`]
+                    #[inline(always)]
+                    pub fn norm(self) -> Self {
+                        self.normalize_or_zero()
+                    }
+
+                    /// Normalizes every lane to exactly unit length.
+                    /// Lanes that cannot be normalized become 0,
+                    /// matching [`Vector3::normalize_or_zero`]
+                    #[inline(always)]
+                    pub fn normalize_or_zero(self) -> Self {
+                        // rescale every lane by its largest element first, like
+                        // Vector3::try_normalize, so huge and tiny lanes don't overflow
+                        let mut max = [T::zero(); $n];
+                        for cx in 0..3 {
+                            for cy in 0..$n {
+                                max[cy] = max[cy].max(self[cx][cy].abs());
+                            }
+                        }
+                        let mut scaled = self;
+                        for cx in 0..3 {
+                            for cy in 0..$n {
+                                scaled[cx][cy] = if max[cy].is_finite() && max[cy] != T::zero() {
+                                    self[cx][cy] / max[cy]
+                                } else {
+                                    T::zero()
+                                };
+                            }
+                        }
+                        let len_recip = scaled.len().map(T::recip);
+                        let mut ret = scaled.mul_lanes(len_recip);
+                        for cy in 0..$n {
+                            if !len_recip[cy].is_finite() {
+                                for cx in 0..3 {
+                                    ret[cx][cy] = T::zero();
+                                }
+                            }
+                        }
+                        ret
+                    }
+
+                    /// Does by-value comparison to see if two wide Vector's are exactly equal,
+                    /// unlike `==` which compares within `T::epsilon()`
+                    #[inline(always)]
+                    pub fn eq_exact(self, other: Self) -> bool {
+                        self.0 == other.0
+                    }
+
+                    /// Does by-value comparison to see if two wide Vector's are equal
+                    /// by using the given epsilon value
+                    #[inline(always)]
+                    pub fn eq_fast(self, other: Self, epsilon: T) -> bool {
+                        for cx in 0..3 {
+                            for cy in 0..$n {
+                                if (self[cx][cy] - other[cx][cy]).abs() > epsilon {
+                                    return false;
+                                }
+                            }
+                        }
+                        true
+                    }
+                }// impl end
+
+                impl<T: Element> Matrix3x3<T> {
+                    /// Multiplies the Matrix with every lane
+                    #[inline(always)]
+                    pub fn [<mul_vector3x $n>](self, vector: [<Vector3x $n>]<T>) -> [<Vector3x $n>]<T> {
+                        #[cfg(feature = "simd")]
+                        if let (Some(ca), Some(cb)) = (crate::simd::downcast(self), crate::simd::downcast(vector.0)) {
+                            return [<Vector3x $n>](crate::simd::downcast(crate::simd::wide_mul_matrix3x3::<$n>(ca, cb)).unwrap());
+                        }
+                        let mut ret = vector;
+                        for cx in 0..3 {
+                            for cy in 0..$n {
+                                ret[cx][cy] = self[cx][0] * vector[0][cy]
+                                    + self[cx][1] * vector[1][cy]
+                                    + self[cx][2] * vector[2][cy];
+                            }
+                        }
+                        ret
+                    }
+                }
+
+                impl<T: Element> Matrix4x4<T> {
+                    /// Multiplies the Matrix with every lane, treating them as
+                    /// points (w = 1). Like an affine transform,
+                    /// the bottom row of the Matrix is not used
+                    #[inline(always)]
+                    pub fn [<mul_vector3x $n>](self, vector: [<Vector3x $n>]<T>) -> [<Vector3x $n>]<T> {
+                        #[cfg(feature = "simd")]
+                        if let (Some(ca), Some(cb)) = (crate::simd::downcast(self), crate::simd::downcast(vector.0)) {
+                            return [<Vector3x $n>](crate::simd::downcast(crate::simd::wide_mul_matrix4x4::<$n>(ca, cb)).unwrap());
+                        }
+                        let mut ret = vector;
+                        for cx in 0..3 {
+                            for cy in 0..$n {
+                                ret[cx][cy] = self[cx][0] * vector[0][cy]
+                                    + self[cx][1] * vector[1][cy]
+                                    + self[cx][2] * vector[2][cy]
+                                    + self[cx][3];
+                            }
+                        }
+                        ret
+                    }
+                }
+            }
+        )*
+    };
+}
+
+GENERATE_WIDE_VEC!(4, 8);